@@ -54,6 +54,9 @@ pub struct Type {
     /// Whether the entire value is optional.
     optional: bool,
 
+    /// Whether the parameter collects all remaining arguments into a `Vec`.
+    variadic: bool,
+
     /// Whether the type should be borrowed.
     is_ref: bool,
 
@@ -81,6 +84,35 @@ pub enum TypeKind {
 
     /// Any type.
     Value,
+
+    /// The context the function is evaluated in. This does not correspond to an argument.
+    Ctxt,
+}
+
+/// Helper function to extract the single generic type argument of a path segment, such as `T` in
+/// `Option<T>`.
+fn single_generic_arg(segment: &syn::PathSegment) -> Result<&syn::Type> {
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(bracketed) if bracketed.args.len() == 1 => &bracketed.args,
+        _ => return Err(syn::Error::new(segment.ident.span(), "expected one angle-bracketed argument")),
+    };
+
+    let first_arg = args.first().unwrap();
+    match first_arg {
+        syn::GenericArgument::Type(ty) => Ok(ty),
+        _ => Err(syn::Error::new(first_arg.span(), "expected type as generic argument")),
+    }
+}
+
+/// Returns true if the return type is a `Result`, in which case the function can fail.
+fn is_result(ty: &ReturnType) -> bool {
+    match ty {
+        ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(ty) => ty.path.segments.last().is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
 }
 
 /// Helper function to match the first segment of a path in a type.
@@ -96,24 +128,23 @@ fn match_first_segment(ty: &syn::Type) -> Result<Type> {
 
     let ident_str = first.ident.to_string();
     if ident_str == "Option" {
-        let args = match &first.arguments {
-            syn::PathArguments::AngleBracketed(bracketed) if bracketed.args.len() == 1 => &bracketed.args,
-            _ => return Err(syn::Error::new(first.ident.span(), "expected one angle-bracketed argument")),
-        };
-
-        let first_arg = args.first().unwrap();
-        let ty = match first_arg {
-            syn::GenericArgument::Type(ty) => ty,
-            _ => return Err(syn::Error::new(first_arg.span(), "expected type as generic argument")),
-        };
         Ok(Type {
             optional: true,
+            variadic: false,
+            is_ref: false,
+            kind: match_first_segment(single_generic_arg(first)?)?.kind,
+        })
+    } else if ident_str == "Vec" {
+        Ok(Type {
+            optional: false,
+            variadic: true,
             is_ref: false,
-            kind: match_first_segment(ty)?.kind,
+            kind: match_first_segment(single_generic_arg(first)?)?.kind,
         })
     } else {
         Ok(Type {
             optional: false,
+            variadic: false,
             is_ref: false,
             kind: match &*ident_str {
                 "Float" => TypeKind::Float,
@@ -121,7 +152,8 @@ fn match_first_segment(ty: &syn::Type) -> Result<Type> {
                 "Complex" => TypeKind::Complex,
                 "bool" => TypeKind::Bool,
                 "Value" => TypeKind::Value,
-                _ => return Err(syn::Error::new(first.ident.span(), format!("expected `Float`, `Integer`, `Complex`, `bool`, or `Value`, found `{}`", ident_str))),
+                "Ctxt" => TypeKind::Ctxt,
+                _ => return Err(syn::Error::new(first.ident.span(), format!("expected `Float`, `Integer`, `Complex`, `bool`, `Value`, `Ctxt`, `Option`, or `Vec`, found `{}`", ident_str))),
             },
         })
     }
@@ -132,7 +164,7 @@ impl TryFrom<ReturnType> for Type {
 
     fn try_from(ty: ReturnType) -> Result<Self> {
         match ty {
-            ReturnType::Default => Ok(Type { optional: false, is_ref: false, kind: TypeKind::Unit }),
+            ReturnType::Default => Ok(Type { optional: false, variadic: false, is_ref: false, kind: TypeKind::Unit }),
             ReturnType::Type(_, ty) => match_first_segment(&ty),
         }
     }
@@ -143,7 +175,7 @@ impl TryFrom<syn::Type> for Type {
 
     fn try_from(ty: syn::Type) -> Result<Self> {
         match ty {
-            syn::Type::Reference(ty) => Ok(Type { optional: false, is_ref: true, kind: match_first_segment(&ty.elem)?.kind }),
+            syn::Type::Reference(ty) => Ok(Type { optional: false, variadic: false, is_ref: true, kind: match_first_segment(&ty.elem)?.kind }),
             _ => match_first_segment(&ty),
         }
     }
@@ -159,6 +191,7 @@ impl Type {
             TypeKind::Bool => quote! { bool },
             TypeKind::Unit => quote! { () },
             TypeKind::Value => quote! { Value },
+            TypeKind::Ctxt => quote! { Ctxt },
         };
 
         let reffed = if self.is_ref {
@@ -169,6 +202,8 @@ impl Type {
 
         if self.optional {
             quote! { Option<#reffed> }
+        } else if self.variadic {
+            quote! { Vec<#reffed> }
         } else {
             reffed
         }
@@ -183,6 +218,7 @@ impl Type {
             TypeKind::Bool => "Boolean",
             TypeKind::Unit => "Unit",
             TypeKind::Value => "Value",
+            TypeKind::Ctxt => "Context",
         }
     }
 
@@ -192,9 +228,10 @@ impl Type {
             TypeKind::Float => quote! { crate::numerical::value::Value::Float },
             TypeKind::Integer => quote! { crate::numerical::value::Value::Integer },
             TypeKind::Complex => quote! { crate::numerical::value::Value::Complex },
-            TypeKind::Bool => quote! { crate::numerical::value::Value::Bool },
+            TypeKind::Bool => quote! { crate::numerical::value::Value::Boolean },
             TypeKind::Unit => quote! { crate::numerical::value::Value::Unit },
             TypeKind::Value => quote! { crate::numerical::value::Value::Value },
+            TypeKind::Ctxt => unreachable!("context parameters do not correspond to a value"),
         }
     }
 }
@@ -286,15 +323,27 @@ pub struct Builtin {
 
     /// The parameters of the function.
     params: Vec<Param>,
+
+    /// Whether the function returns a `Result`. If so, the error type must be convertible into a
+    /// `BuiltinError`.
+    fallible: bool,
 }
 
 impl Builtin {
+    /// Returns an iterator over the parameters of the function that correspond to arguments, i.e.
+    /// all parameters except the context parameter, if there is one.
+    fn arg_params(&self) -> impl Iterator<Item = &Param> {
+        self.params.iter().filter(|param| param.ty.kind != TypeKind::Ctxt)
+    }
+
     /// Returns a string representation of the function's signature.
     pub fn signature(&self) -> String {
-        let params = self.params.iter().map(|param| {
+        let params = self.arg_params().map(|param| {
             let ty = param.ty.typename();
             if param.ty.optional {
                 format!("{}: {} (optional)", param.ident, ty)
+            } else if param.ty.variadic {
+                format!("{}: {}...", param.ident, ty)
             } else {
                 format!("{}: {}", param.ident, ty)
             }
@@ -302,11 +351,21 @@ impl Builtin {
         format!("{}({})", self.name, params)
     }
 
+    /// Returns true if the last parameter of the function is variadic.
+    pub fn is_variadic(&self) -> bool {
+        self.params.last().is_some_and(|param| param.ty.variadic)
+    }
+
+    /// Returns the number of non-variadic parameters of the function.
+    pub fn num_fixed_params(&self) -> usize {
+        self.arg_params().filter(|param| !param.ty.variadic).count()
+    }
+
     /// Generates the statements that typecheck the arguments.
     pub fn generate_check_stmts(&self, radian: Radian) -> TokenStream2 {
         let Self { name, .. } = self;
         let signature = self.signature();
-        let num_params = self.params.len();
+        let num_params = self.num_fixed_params();
 
         // for each parameter, we generate a binding with three patterns to typecheck the arguments, and
         // possibly use the default expression if the argument is not provided
@@ -326,13 +385,24 @@ impl Builtin {
         // - the argument given is a `Float` with a fractional part of 0 and the parameter is an
         // `Integer`
         //
+        // the context parameter, if there is one, is passed directly and needs no typechecking
+        //
         // TODO: arguments are cloned, which may or may not be ideal
-        let type_checkers = self.params
-            .iter()
+        let type_checkers = self.arg_params()
             .enumerate()
             .map(|(i, param)| {
                 let (ident, ty) = (&param.ident, &param.ty);
 
+                // accept all remaining arguments of any type
+                if ty.kind == TypeKind::Value && ty.variadic {
+                    return quote! {
+                        let mut #ident = Vec::new();
+                        while let Some(arg) = crate::funcs::helper::next_arg(args, &mut arg_count) {
+                            #ident.push(arg);
+                        }
+                    };
+                }
+
                 // accept argument of any type
                 if ty.kind == TypeKind::Value {
                     return quote! {
@@ -374,6 +444,29 @@ impl Builtin {
 
                 let full_getter = quote! { #base_call #type_coerce_expr #trig_convert_expr };
 
+                // collect all remaining arguments, typechecking each one
+                if ty.variadic {
+                    let user_ty = ty.typename();
+                    let ty = ty.value_tokens();
+                    return quote! {
+                        let mut #ident = Vec::new();
+                        while let Some(arg) = #full_getter {
+                            match arg {
+                                #ty(arg) => #ident.push(arg),
+                                bad_value => {
+                                    return Err(crate::numerical::builtin::error::BuiltinError::TypeMismatch(crate::numerical::error::kind::TypeMismatch {
+                                        name: stringify!(#name).to_owned(),
+                                        index: arg_count - 1,
+                                        expected: #user_ty,
+                                        given: bad_value.typename(),
+                                        signature: #signature.to_owned(),
+                                    }));
+                                },
+                            }
+                        }
+                    };
+                }
+
                 // if the parameter is optional, adjust the pattern matching
                 let (received_type, none_branch) = if ty.optional {
                     (
@@ -414,6 +507,11 @@ impl Builtin {
                 }
             });
 
+        // variadic functions consume all arguments, so there can never be too many
+        if self.is_variadic() {
+            return quote! { #( #type_checkers )* };
+        }
+
         quote! {
             #( #type_checkers )*
             if crate::funcs::helper::count_all_args(args, &mut arg_count) > #num_params {
@@ -432,15 +530,21 @@ impl Builtin {
         let pascal_name = &self.pascal_name;
         let param_idents = self.params.iter().map(|param| {
             let ident = &param.ident;
-            if param.ty.is_ref {
+            if param.ty.kind == TypeKind::Ctxt {
+                quote! { ctxt }
+            } else if param.ty.is_ref {
                 quote! { &#ident }
             } else {
                 quote! { #ident }
             }
         });
-        let make_value = quote! { crate::numerical::value::Value::from(
-            #pascal_name::eval_static(#(#param_idents),*)
-        ) };
+        let call = quote! { #pascal_name::eval_static(#(#param_idents),*) };
+        let call = if self.fallible {
+            quote! { #call? }
+        } else {
+            call
+        };
+        let make_value = quote! { crate::numerical::value::Value::from(#call) };
 
         if radian == Radian::Output {
             quote! {
@@ -462,8 +566,9 @@ impl Builtin {
 
     /// Generate the implementation of the `Builtin` trait for the function.
     fn impl_builtin(&self, radian: Radian) -> TokenStream2 {
-        let Self { pascal_name, params, .. } = self;
-        let arg_count = params.len();
+        let Self { pascal_name, .. } = self;
+        let arg_count = self.num_fixed_params();
+        let variadic = self.is_variadic();
         let type_checkers = self.generate_check_stmts(radian);
         let call = self.generate_call(radian);
 
@@ -471,6 +576,8 @@ impl Builtin {
             impl crate::numerical::builtin::Builtin for #pascal_name {
                 fn num_args(&self) -> usize { #arg_count }
 
+                fn variadic(&self) -> bool { #variadic }
+
                 fn eval(
                     &self,
                     ctxt: &crate::numerical::ctxt::Ctxt,
@@ -506,6 +613,7 @@ impl Parse for Builtin {
             item,
             pascal_name,
             name,
+            fallible: is_result(&eval_static_fn.sig.output),
             params: eval_static_fn.sig.inputs.into_iter().map(Param::try_from).collect::<Result<_>>()?,
         };
 
@@ -519,6 +627,16 @@ impl Parse for Builtin {
             }
         }
 
+        // a variadic parameter must be the last parameter, and cannot be combined with optional
+        // parameters
+        if let Some(param) = builtin.params.iter().rev().skip(1).find(|param| param.ty.variadic) {
+            return Err(syn::Error::new(param.ident.span(), "variadic parameters must be the last parameter"));
+        }
+        if builtin.is_variadic() && seen_optional {
+            let param = builtin.params.last().unwrap();
+            return Err(syn::Error::new(param.ident.span(), "variadic parameters cannot be combined with optional parameters"));
+        }
+
         Ok(builtin)
     }
 }
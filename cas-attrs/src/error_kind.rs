@@ -17,6 +17,7 @@ pub struct ErrorArgs {
     pub labels: Option<Expr>,
     pub help: Option<Expr>,
     pub note: Option<Expr>,
+    pub severity: Option<Expr>,
 }
 
 impl ErrorArgs {
@@ -31,6 +32,7 @@ impl ErrorArgs {
             "labels" => self.labels = Some(input.parse()?),
             "help" => self.help = Some(input.parse()?),
             "note" => self.note = Some(input.parse()?),
+            "severity" => self.severity = Some(input.parse()?),
             _ => return Err(syn::Error::new_spanned(ident, format!("unknown tag `{}`", ident_str))),
         }
 
@@ -121,22 +123,33 @@ impl Parse for ErrorKindTarget {
 
 impl ToTokens for ErrorKindTarget {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let (name, message, help, note) = (
+        let (name, message, help, note, severity) = (
             &self.name,
             self.error_args.message.as_ref(),
             self.error_args.help.as_ref().map(|e| quote! { builder.set_help(#e); }),
             self.error_args.note.as_ref().map(|e| quote! { builder.set_note(#e); }),
+            self.error_args.severity.as_ref().map(|e| quote! {
+                fn severity(&self) -> cas_error::Severity {
+                    #e
+                }
+            }),
         );
         let labels = self.generate_labels();
 
         tokens.extend(quote! {
             impl cas_error::ErrorKind for #name {
+                #severity
+
+                fn message(&self) -> String {
+                    (#message).to_string()
+                }
+
                 fn build_report(
                     &self,
                     src_id: &'static str,
                     spans: &[std::ops::Range<usize>],
                 ) -> ariadne::Report<(&'static str, std::ops::Range<usize>)> {
-                    let mut builder = ariadne::Report::build(ariadne::ReportKind::Error, src_id, spans[0].start)
+                    let mut builder = ariadne::Report::build(cas_error::ErrorKind::severity(self).report_kind(), src_id, spans[0].start)
                         .with_message(#message)
                         .with_labels(#labels);
 
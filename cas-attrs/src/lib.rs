@@ -29,14 +29,16 @@ use syn::parse_macro_input;
 /// | `message`   | The message displayed at the top of the error when it is displayed.                                                                                              |
 /// | `labels`    | A list of labels that point to the spans of the error. The first label will be associated with the first span, the second label with the second span, and so on. |
 /// | `help`      | Optional help text for the error, describing what the user can do to fix it.                                                                                     |
+/// | `severity`  | Optional [`Severity`] of the error. Errors have a severity of `Severity::Error` by default.                                                                      |
 ///
 /// The `message` and `help` tags accept an expression that can be converted to a [`String`], and
 /// the `labels` tag accepts an expression that can be converted to a [`Vec`] of [`String`]s. Each
 /// expression is actually evaluated within an associated function with access to `$self`, so the
 /// expression can use the members of the struct or determine which variant of the enum is being
-/// used in the output.
+/// used in the output. The `severity` tag accepts an expression of type [`Severity`].
 ///
 /// [`ErrorKind`]: cas_error::ErrorKind
+/// [`Severity`]: cas_error::Severity
 #[proc_macro_derive(ErrorKind, attributes(error))]
 pub fn error_kind(item: TokenStream) -> TokenStream {
     let target = parse_macro_input!(item as ErrorKindTarget);
@@ -68,6 +70,15 @@ pub fn error_kind(item: TokenStream) -> TokenStream {
 /// Optional arguments should be placed at the end of the list of parameters, though the attribute
 /// does not enforce this.
 ///
+/// The last parameter can also be wrapped in a [`Vec`] to make the function variadic. All
+/// remaining arguments are type-checked and collected into the [`Vec`], which can be empty. To
+/// require at least one argument, add a non-variadic parameter before it. Variadic parameters
+/// cannot be combined with optional parameters.
+///
+/// A parameter of type `&Ctxt` can also be added to receive the context the function is evaluated
+/// in. It does not correspond to an argument, and is not included in the function's signature, so
+/// it cannot be optional or variadic.
+///
 /// For trigonometric functions, the attribute can be used to indicate that the function takes
 /// input in radians, or returns an output in radians. This is done by adding the `radian` tag to
 /// the attribute, with the value `input` or `output`. If the user's trigonometric mode does not
@@ -108,6 +119,16 @@ pub fn error_kind(item: TokenStream) -> TokenStream {
 ///     }
 /// }
 ///
+/// /// Returns the maximum of one or more values.
+/// pub struct Max;
+///
+/// #[builtin]
+/// impl Max {
+///     pub fn eval_static(v: Float, rest: Vec<Float>) -> Float {
+///         rest.into_iter().fold(v, |max, v| max.max(&v))
+///     }
+/// }
+///
 /// /// Returns the arcsine of a value.
 /// pub struct Asin;
 ///
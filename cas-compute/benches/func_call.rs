@@ -0,0 +1,42 @@
+//! Benchmarks calling a user-defined function many times.
+//!
+//! The body of `resolved` has its parameters resolved to slots when the function is defined. The
+//! body of `unresolved` is the same expression wrapped in a block, which is not resolved, so each of
+//! its calls binds and looks up the arguments by name.
+
+use cas_compute::numerical::{ctxt::Ctxt, eval::Eval};
+use cas_parser::parser::{ast::{expr::Expr, program::Program}, Parser};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+/// The number of calls made in each iteration of a benchmark.
+const CALLS: usize = 100_000;
+
+/// Returns a context with the functions used by the benchmarks defined.
+fn ctxt() -> Ctxt {
+    let source = "resolved(x, y) = x^2 + x * y + y^2; unresolved(x, y) = { x^2 + x * y + y^2 }";
+    let program = Parser::new(source).try_parse_full::<Program>().unwrap();
+    let mut ctxt = Ctxt::default();
+    program.eval(&mut ctxt).unwrap();
+    ctxt
+}
+
+fn func_call(c: &mut Criterion) {
+    let mut group = c.benchmark_group("func_call");
+    group.sample_size(10);
+
+    for name in ["resolved", "unresolved"] {
+        let call = Parser::new(&format!("{name}(3, 4)")).try_parse_full::<Expr>().unwrap();
+        let mut ctxt = ctxt();
+        group.bench_function(name, |b| b.iter(|| {
+            for _ in 0..CALLS {
+                black_box(call.eval(&mut ctxt).unwrap());
+            }
+        }));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, func_call);
+criterion_main!(benches);
@@ -1,5 +1,9 @@
 //! Additional constants used in the library. This module consists of static constants that return
 //! [`Float`]s with the given value.
+//!
+//! Physical constants are in the [`physical`] submodule.
+
+pub mod physical;
 
 use once_cell::sync::Lazy;
 use rug::{Complex, Float};
@@ -0,0 +1,28 @@
+//! Physical constants, in SI units. Unlike the mathematical constants in the parent module, these
+//! are measured quantities and are only approximate (except where defined exactly by the SI).
+
+use once_cell::sync::Lazy;
+use rug::Float;
+use crate::primitive::{float, float_from_str};
+
+/// The speed of light in a vacuum, in meters per second. This value is exact.
+pub static SPEED_OF_LIGHT: Lazy<Float> = Lazy::new(|| float(299792458));
+
+/// The Planck constant, in joule-seconds. This value is exact.
+pub static PLANCK: Lazy<Float> = Lazy::new(|| float_from_str("6.62607015e-34"));
+
+/// The Newtonian constant of gravitation, in cubic meters per kilogram per second squared.
+pub static GRAVITATIONAL: Lazy<Float> = Lazy::new(|| float_from_str("6.67430e-11"));
+
+/// The Avogadro constant, in entities per mole. This value is exact.
+pub static AVOGADRO: Lazy<Float> = Lazy::new(|| float_from_str("6.02214076e23"));
+
+/// Returns the name and value of every physical constant.
+pub fn all() -> [(&'static str, Float); 4] {
+    [
+        ("c", SPEED_OF_LIGHT.clone()),
+        ("h", PLANCK.clone()),
+        ("G", GRAVITATIONAL.clone()),
+        ("avogadro", AVOGADRO.clone()),
+    ]
+}
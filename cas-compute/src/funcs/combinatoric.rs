@@ -13,37 +13,52 @@ use rug::Integer;
 /// - Returns the coefficient of the `x^k` term in the polynomial expansion of `(x + 1)^n`, or the
 /// coefficient of the `x^k * y^(n - k)` term in the polynomial expansion of `(x + y)^n`.
 /// - Returns the number in row `n` and column `k` of Pascal's triangle.
+///
+/// If `k` is negative or greater than `n`, there are no ways to choose the items, so `0` is
+/// returned.
 #[derive(Debug)]
 pub struct Ncr;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Ncr {
     pub fn eval_static(n: Integer, k: Integer) -> Integer {
-        if k > n {
-            // TODO: what if k > n, return an error
-            return Integer::from(0);
+        if k < 0 || k > n {
+            return int(0);
         }
 
+        // `nCk = nC(n - k)`, so use the smaller of the two to minimize the number of steps
         let sub = int(&n - &k);
-        if k > sub {
-            partial_factorial(n, k) / partial_factorial(sub, int(1))
-        } else {
-            partial_factorial(n, sub) / partial_factorial(k, int(1))
+        let k = if k > sub { sub } else { k };
+
+        // multiply and divide incrementally to keep the intermediate values small; after step `i`,
+        // `result` is `(n - k + i)C(i)`, which is always an integer
+        let base = n - &k;
+        let mut result = int(1);
+        let mut i = int(1);
+        while i <= k {
+            result *= int(&base + &i);
+            result /= &i;
+            i += 1;
         }
+        result
     }
 }
 
 /// Permutations function. Returns the number of ways to choose `k` (`r`) items from `n` items,
 /// where the order of the items does matter.
+///
+/// If `k` is negative or greater than `n`, `0` is returned.
 #[derive(Debug)]
 pub struct Npr;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Npr {
     pub fn eval_static(n: Integer, k: Integer) -> Integer {
-        // TODO: report error
+        if k < 0 || k > n {
+            return int(0);
+        }
 
-        let sub = &n - k;
+        let sub = int(&n - k);
         partial_factorial(n, sub)
     }
 }
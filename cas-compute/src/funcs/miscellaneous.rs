@@ -2,11 +2,16 @@
 
 use cas_attrs::builtin;
 use crate::consts::TAU;
-use crate::numerical::value::Value;
+use crate::numerical::{
+    builtin::func_specific::{ClampError, DivmodError, DoubleFactError, MinMaxError},
+    ctxt::Ctxt,
+    value::Value,
+};
 use crate::primitive::{complex, float_from_str, float, int};
 use once_cell::sync::Lazy;
 use rand::Rng;
 use rug::{integer::Order, ops::Pow, rand::RandState, Complex, Float, Integer};
+use std::cmp::Ordering;
 
 /// Returns the absolute value.
 #[derive(Debug)]
@@ -38,6 +43,24 @@ impl Bool {
     }
 }
 
+/// Returns the name of the type of the given value, such as `"number"` or `"complex"`.
+///
+/// Integers and floats are both reported as `"number"`. Other types are reported by their
+/// [`Value::typename`] in lowercase.
+#[derive(Debug)]
+pub struct Typeof;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Typeof {
+    pub fn eval_static(v: Value) -> Value {
+        let name = match v {
+            Value::Integer(_) | Value::Float(_) => "number".to_string(),
+            _ => v.typename().to_lowercase(),
+        };
+        Value::String(name)
+    }
+}
+
 /// Returns a random floating-point number between `0.0` and `1.0`.
 #[derive(Debug)]
 pub struct Rand;
@@ -92,6 +115,28 @@ impl Factorial {
     }
 }
 
+/// The double factorial function. Returns the product of every other integer from `n` down to `1`
+/// (i.e. `n * (n - 2) * (n - 4) * ...`).
+///
+/// This is not the same as `n!!`, which is parsed as the factorial of `n!`.
+#[derive(Debug)]
+pub struct DoubleFact;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl DoubleFact {
+    pub fn eval_static(n: Integer) -> Result<Integer, DoubleFactError> {
+        if n < 0 {
+            // negative `n` results in the empty product, `1`
+            return Ok(int(1));
+        }
+
+        // like `Factorial`, only compute the exact value for `n` that fits within `u16`
+        n.to_u16()
+            .map(|n| int(Integer::factorial_2(u32::from(n))))
+            .ok_or(DoubleFactError { max: u32::from(u16::MAX) })
+    }
+}
+
 static GAMMA_P: Lazy<[Float; 9]> = Lazy::new(|| [
     float_from_str("0.99999999999980993"),
     float_from_str("676.5203681218851"),
@@ -157,58 +202,141 @@ impl Invlerp {
     }
 }
 
-/// Returns the minimum of two values.
+/// Returns the smallest (if `order` is [`Ordering::Less`]) or largest (if `order` is
+/// [`Ordering::Greater`]) of the given values, which must not be empty.
+///
+/// If any value has a non-zero imaginary part, the values are compared by their magnitude if the
+/// context allows it; otherwise, an error is returned.
+fn extremum(
+    ctxt: &Ctxt,
+    function_name: &'static str,
+    values: Vec<Complex>,
+    order: Ordering,
+) -> Result<Value, MinMaxError> {
+    if let Some(index) = values.iter().position(|c| !c.imag().is_zero()) {
+        if !ctxt.order_complex_by_magnitude {
+            return Err(MinMaxError { function_name, index });
+        }
+
+        // of values with equal magnitude, the first is kept
+        let (_, extreme) = values.into_iter()
+            .map(|c| (float(c.abs_ref()), c))
+            .reduce(|extreme, next| {
+                if next.0.partial_cmp(&extreme.0) == Some(order) {
+                    next
+                } else {
+                    extreme
+                }
+            })
+            .unwrap();
+        return Ok(extreme.into());
+    }
+
+    let mut reals = values.into_iter().map(|c| c.into_real_imag().0);
+    let first = reals.next().unwrap();
+    let extreme = match order {
+        Ordering::Less => reals.fold(first, |min, v| min.min(&v)),
+        _ => reals.fold(first, |max, v| max.max(&v)),
+    };
+    Ok(extreme.into())
+}
+
+/// Returns the minimum of one or more values.
+///
+/// Complex numbers are only accepted if [`Ctxt::order_complex_by_magnitude`] is set, in which case
+/// the value with the smallest magnitude is returned.
 #[derive(Debug)]
 pub struct Min;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Min {
-    pub fn eval_static(v1: Float, v2: Float) -> Float {
-        v1.min(&v2)
+    pub fn eval_static(ctxt: &Ctxt, v: Complex, mut rest: Vec<Complex>) -> Result<Value, MinMaxError> {
+        rest.insert(0, v);
+        extremum(ctxt, "min", rest, Ordering::Less)
     }
 }
 
-/// Returns the maximum of two values.
+/// Returns the maximum of one or more values.
+///
+/// Complex numbers are only accepted if [`Ctxt::order_complex_by_magnitude`] is set, in which case
+/// the value with the largest magnitude is returned.
 #[derive(Debug)]
 pub struct Max;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Max {
-    pub fn eval_static(v1: Float, v2: Float) -> Float {
-        v1.max(&v2)
+    pub fn eval_static(ctxt: &Ctxt, v: Complex, mut rest: Vec<Complex>) -> Result<Value, MinMaxError> {
+        rest.insert(0, v);
+        extremum(ctxt, "max", rest, Ordering::Greater)
     }
 }
 
-/// Clamps a value between two bounds.
+/// Clamps a value between two bounds. The lower bound must be less than or equal to the upper
+/// bound.
 #[derive(Debug)]
 pub struct Clamp;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Clamp {
-    pub fn eval_static(v: Float, min: Float, max: Float) -> Float {
-        v.clamp(&min, &max)
+    pub fn eval_static(v: Float, min: Float, max: Float) -> Result<Float, ClampError> {
+        if min > max {
+            return Err(ClampError);
+        }
+        Ok(v.clamp(&min, &max))
+    }
+}
+
+/// Returns true if a value lies between two bounds. The bounds are included in the range unless the
+/// optional fourth argument is `false`.
+#[derive(Debug)]
+pub struct Between;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Between {
+    pub fn eval_static(v: Float, lo: Float, hi: Float, inclusive: Option<bool>) -> bool {
+        if inclusive.unwrap_or(true) {
+            lo <= v && v <= hi
+        } else {
+            lo < v && v < hi
+        }
     }
 }
 
-/// Returns the greatest common factor of two integers.
+/// Returns the greatest common factor of one or more integers.
 #[derive(Debug)]
 pub struct Gcf;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Gcf {
-    pub fn eval_static(a: Integer, b: Integer) -> Integer {
-        a.gcd(&b)
+    pub fn eval_static(a: Integer, rest: Vec<Integer>) -> Integer {
+        rest.into_iter().fold(a, |gcf, b| gcf.gcd(&b))
     }
 }
 
-/// Returns the least common multiple of two integers.
+/// Returns the quotient and remainder of the floored division of two integers, as the tuple
+/// `(a div b, a - b * (a div b))`.
+#[derive(Debug)]
+pub struct Divmod;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Divmod {
+    pub fn eval_static(a: Integer, b: Integer) -> Result<Value, DivmodError> {
+        if b.is_zero() {
+            return Err(DivmodError);
+        }
+        let (quotient, remainder) = a.div_rem_floor(b);
+        Ok(Value::Tuple(vec![quotient.into(), remainder.into()]))
+    }
+}
+
+/// Returns the least common multiple of one or more integers.
 #[derive(Debug)]
 pub struct Lcm;
 
 #[cfg_attr(feature = "numerical", builtin)]
 impl Lcm {
-    pub fn eval_static(a: Integer, b: Integer) -> Integer {
-        a.lcm(&b)
+    pub fn eval_static(a: Integer, rest: Vec<Integer>) -> Integer {
+        rest.into_iter().fold(a, |lcm, b| lcm.lcm(&b))
     }
 }
 
@@ -227,6 +355,17 @@ impl Sign {
     }
 }
 
+/// Returns the first value with the sign of the second value.
+#[derive(Debug)]
+pub struct Copysign;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Copysign {
+    pub fn eval_static(v: Float, sign: Float) -> Float {
+        v.copysign(&sign)
+    }
+}
+
 /// Returns the number of significant bits in the binary representation of an integer.
 #[derive(Debug)]
 pub struct Size;
@@ -117,6 +117,8 @@ pub fn all() -> HashMap<&'static str, Box<dyn Builtin>> {
         "sqrt" Sqrt,
         "cbrt" Cbrt,
         "root" Root,
+        "nthroot" Nthroot,
+        "allroots" Allroots,
         "hypot" Hypot,
         "re" Re,
         "im" Im,
@@ -124,7 +126,9 @@ pub fn all() -> HashMap<&'static str, Box<dyn Builtin>> {
         "conj" Conj,
         "fib" Fib,
         "ncr" Ncr,
+        "binom" Ncr, // intentional alias for ncr
         "npr" Npr,
+        "perm" Npr, // intentional alias for npr
         "erf" Erf,
         "erfc" Erfc,
         "inverf" Inverf,
@@ -142,19 +146,25 @@ pub fn all() -> HashMap<&'static str, Box<dyn Builtin>> {
         "ceil" Ceil,
         "floor" Floor,
         "trunc" Trunc,
+        "round_to" RoundTo,
         "abs" Abs,
         "bool" Bool,
+        "typeof" Typeof,
         "rand" Rand,
         "factorial" Factorial,
+        "doublefact" DoubleFact,
         "gamma" Gamma,
         "lerp" Lerp,
         "invlerp" Invlerp,
         "min" Min,
         "max" Max,
         "clamp" Clamp,
+        "between" Between,
+        "divmod" Divmod,
         "gcf" Gcf,
         "lcm" Lcm,
         "sign" Sign,
+        "copysign" Copysign,
         "size" Size,
     }
 }
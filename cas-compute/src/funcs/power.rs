@@ -2,8 +2,9 @@
 
 use cas_attrs::builtin;
 use crate::consts::{I, TAU, TEN};
+use crate::numerical::{builtin::func_specific::RootError, value::Value};
 use crate::primitive::{complex, float};
-use rug::{ops::Pow as _, Complex, Float};
+use rug::{ops::Pow as _, Complex, Float, Integer};
 
 /// Builds a function whose `rug` implementation has the same name as the given function.
 macro_rules! simple {
@@ -94,6 +95,50 @@ impl Root {
     }
 }
 
+/// Converts the degree of a root to a `u32`, returning an error if it is not positive.
+fn root_degree(n: &Integer, function_name: &'static str) -> Result<u32, RootError> {
+    n.to_u32()
+        .filter(|&n| n > 0)
+        .ok_or(RootError { function_name })
+}
+
+/// Returns the `k`th of the `n` complex `n`th roots of `x`, counting counterclockwise from the
+/// principal root, which is the `0`th root.
+fn kth_root(x: &Complex, n: u32, k: u32) -> Complex {
+    let (abs, arg) = (float(x.abs_ref()), float(x.arg_ref()));
+    let angle = (arg + float(k) * &*TAU) / n;
+    abs.root(n) * complex(angle * &*I).exp()
+}
+
+/// Returns the principal `n`th root of `x`, `nthroot(x, n)`.
+///
+/// The principal root is the root with argument `arg(x) / n`, where `arg(x)` is in `(-pi, pi]`.
+/// Unlike [`Cbrt`], the principal root of a negative number is complex, even if a real root
+/// exists.
+#[derive(Debug)]
+pub struct Nthroot;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Nthroot {
+    pub fn eval_static(x: Complex, n: Integer) -> Result<Complex, RootError> {
+        let n = root_degree(&n, "nthroot")?;
+        Ok(kth_root(&x, n, 0))
+    }
+}
+
+/// Returns a list of all `n` complex `n`th roots of `x`, `allroots(x, n)`, starting with the
+/// principal root.
+#[derive(Debug)]
+pub struct Allroots;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl Allroots {
+    pub fn eval_static(x: Complex, n: Integer) -> Result<Value, RootError> {
+        let n = root_degree(&n, "allroots")?;
+        Ok(Value::List((0..n).map(|k| kth_root(&x, n, k).into()).collect()))
+    }
+}
+
 /// Returns the hypothenuse of a right triangle with sides `a` and `b`.
 #[derive(Debug)]
 pub struct Hypot;
@@ -53,6 +53,44 @@ impl Siground {
     }
 }
 
+/// Rounds a number to a given number of decimal places. A negative number of places rounds to the
+/// nearest ten, hundred, and so on. Halfway cases are rounded the same way as `round`.
+#[derive(Debug)]
+pub struct RoundTo;
+
+#[cfg_attr(feature = "numerical", builtin)]
+impl RoundTo {
+    pub fn eval_static(n: Complex, d: Integer) -> Complex {
+        fn inner(n: Float, d: &Integer) -> Float {
+            // zero and non-finite values are unchanged by rounding
+            let Some(exp) = n.get_exp() else {
+                return n;
+            };
+
+            // beyond the precision of `n`, the power of ten could overflow, so handle those places
+            // directly: the fraction of `n` has fewer than `prec - exp` digits, so rounding to more
+            // places than that leaves `n` unchanged, and `|n| < 2^exp`, so rounding to more than
+            // `exp` places before the decimal point results in zero, keeping the sign of `n`
+            if *d >= i64::from(n.prec()) - i64::from(exp) {
+                return n;
+            } else if *d < -i64::from(exp.max(0)) {
+                return n * 0u8;
+            }
+
+            // scale by an exact power of ten, so that rounding to tens, hundreds, etc. is exact
+            let magnitude = float(10).pow(d.clone().abs());
+            if d.is_negative() {
+                (n / &magnitude).round() * magnitude
+            } else {
+                (n * &magnitude).round() / magnitude
+            }
+        }
+
+        let (real, imag) = n.into_real_imag();
+        complex((inner(real, &d), inner(imag, &d)))
+    }
+}
+
 build_rounding! {
     round Round; "Round a number to the nearest integer.",
     ceil Ceil; "Round a number up to the nearest integer.",
@@ -1,8 +1,39 @@
 //! General trigonometric and hyperbolic trigonometric functions.
 
 use cas_attrs::builtin;
+#[cfg(feature = "numerical")]
+use crate::{consts::{I, PI}, primitive::complex};
 use rug::{Complex, Float};
 
+/// Computes the inverse sine of the given value.
+///
+/// Real values outside of `[-1, 1]` lie on the branch cuts of the inverse sine, where the sign of
+/// the result's imaginary part would otherwise depend on the sign of the input's zero imaginary
+/// part. To give a consistent result for real input, these values use the standard formula
+/// `asin(x) = -i * ln(ix + sqrt(1 - x^2))`, so for example, `asin(2) = pi/2 - i * acosh(2)`.
+#[cfg(feature = "numerical")]
+fn asin(n: Complex) -> Complex {
+    if n.imag().is_zero() && *n.real().as_abs() > 1 {
+        let root = (complex(1) - complex(&n).square()).sqrt();
+        -complex(&*I) * (complex(&*I) * n + root).ln()
+    } else {
+        n.asin()
+    }
+}
+
+/// Computes the inverse cosine of the given value.
+///
+/// Like [`asin`], real values outside of `[-1, 1]` use the standard formula
+/// `acos(x) = pi/2 - asin(x)`.
+#[cfg(feature = "numerical")]
+fn acos(n: Complex) -> Complex {
+    if n.imag().is_zero() && *n.real().as_abs() > 1 {
+        -(asin(n) - complex(&*PI) / 2)
+    } else {
+        n.acos()
+    }
+}
+
 /// Build a trigonometric function that takes in a single radian input argument.
 macro_rules! build_radian_input {
     ($($name:ident $upname:ident; $func:expr),* $(,)?) => {
@@ -72,11 +103,11 @@ build_radian_input! {
 
 #[cfg(feature = "numerical")]
 build_radian_output! {
-    asin Asin; Complex::asin,
-    acos Acos; Complex::acos,
+    asin Asin; asin,
+    acos Acos; acos,
     atan Atan; Complex::atan,
-    acsc Acsc; |n: Complex| n.recip().asin(), // similar to csc, etc.
-    asec Asec; |n: Complex| n.recip().acos(),
+    acsc Acsc; |n: Complex| asin(n.recip()), // similar to csc, etc.
+    asec Asec; |n: Complex| acos(n.recip()),
     acot Acot; |n: Complex| n.recip().atan(),
 }
 
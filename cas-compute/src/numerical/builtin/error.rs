@@ -48,4 +48,18 @@ impl BuiltinError {
             },
         }
     }
+
+    /// Convert the [`BuiltinError`] into an [`Error`] with no spans, for when the function is
+    /// called without a function call in the source code, such as by [`Ctxt::eval_sweep`].
+    ///
+    /// [`Ctxt::eval_sweep`]: crate::numerical::ctxt::Ctxt::eval_sweep
+    pub fn into_spanless_error(self) -> Error {
+        let kind = match self {
+            BuiltinError::TooManyArguments(e) => Box::new(e) as Box<dyn ErrorKind>,
+            BuiltinError::MissingArgument(e) => Box::new(e) as Box<dyn ErrorKind>,
+            BuiltinError::TypeMismatch(e) => Box::new(e) as Box<dyn ErrorKind>,
+            BuiltinError::FunctionSpecific(e) => e.into_kind(),
+        };
+        Error { spans: Vec::new(), kind }
+    }
 }
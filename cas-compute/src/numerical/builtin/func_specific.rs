@@ -9,6 +9,21 @@ use std::ops::Range;
 pub enum FunctionSpecific {
     /// Errors for the `ncr` and `npr` builtin function.
     Ncpr(NcprError),
+
+    /// Errors for the `clamp` builtin function.
+    Clamp(ClampError),
+
+    /// Errors for the `divmod` builtin function.
+    Divmod(DivmodError),
+
+    /// Errors for the `nthroot` and `allroots` builtin functions.
+    Root(RootError),
+
+    /// Errors for the `min` and `max` builtin functions.
+    MinMax(MinMaxError),
+
+    /// Errors for the `doublefact` builtin function.
+    DoubleFact(DoubleFactError),
 }
 
 impl FunctionSpecific {
@@ -16,6 +31,11 @@ impl FunctionSpecific {
     pub fn spans(&self, call: &Call) -> Vec<Range<usize>> {
         match self {
             FunctionSpecific::Ncpr(e) => e.spans(call),
+            FunctionSpecific::Clamp(e) => e.spans(call),
+            FunctionSpecific::Divmod(e) => e.spans(call),
+            FunctionSpecific::Root(e) => e.spans(call),
+            FunctionSpecific::MinMax(e) => e.spans(call),
+            FunctionSpecific::DoubleFact(e) => e.spans(call),
         }
     }
 
@@ -23,6 +43,11 @@ impl FunctionSpecific {
     pub fn into_kind(self) -> Box<dyn ErrorKind> {
         match self {
             FunctionSpecific::Ncpr(e) => Box::new(e),
+            FunctionSpecific::Clamp(e) => Box::new(e),
+            FunctionSpecific::Divmod(e) => Box::new(e),
+            FunctionSpecific::Root(e) => Box::new(e),
+            FunctionSpecific::MinMax(e) => Box::new(e),
+            FunctionSpecific::DoubleFact(e) => Box::new(e),
         }
     }
 }
@@ -89,3 +114,148 @@ impl From<NcprError> for BuiltinError {
         BuiltinError::FunctionSpecific(FunctionSpecific::Ncpr(e))
     }
 }
+
+/// Errors for the `clamp` builtin function.
+#[derive(Debug, Clone, Copy, ErrorKind, PartialEq, Eq)]
+#[error(
+    message = "incorrect arguments for the `clamp` function",
+    labels = [
+        "this function call",
+        "",
+        "(1) the lower bound...",
+        "(2) ...must be less than or equal to the upper bound",
+    ],
+)]
+pub struct ClampError;
+
+impl ClampError {
+    fn spans(&self, call: &Call) -> Vec<Range<usize>> {
+        let mut this_function_call = call.outer_span().to_vec();
+        this_function_call.extend(call.args.iter().skip(1).map(|arg| arg.span()));
+        this_function_call
+    }
+}
+
+impl From<ClampError> for BuiltinError {
+    fn from(e: ClampError) -> Self {
+        BuiltinError::FunctionSpecific(FunctionSpecific::Clamp(e))
+    }
+}
+
+/// Errors for the `divmod` builtin function.
+#[derive(Debug, Clone, Copy, ErrorKind, PartialEq, Eq)]
+#[error(
+    message = "incorrect arguments for the `divmod` function",
+    labels = [
+        "this function call",
+        "",
+        "the divisor must not be zero",
+    ],
+)]
+pub struct DivmodError;
+
+impl DivmodError {
+    fn spans(&self, call: &Call) -> Vec<Range<usize>> {
+        let mut this_function_call = call.outer_span().to_vec();
+        this_function_call.push(call.args[1].span());
+        this_function_call
+    }
+}
+
+impl From<DivmodError> for BuiltinError {
+    fn from(e: DivmodError) -> Self {
+        BuiltinError::FunctionSpecific(FunctionSpecific::Divmod(e))
+    }
+}
+
+/// Errors for the `nthroot` and `allroots` builtin functions.
+#[derive(Debug, Clone, Copy, ErrorKind, PartialEq, Eq)]
+#[error(
+    message = format!("incorrect arguments for the `{}` function", self.function_name),
+    labels = [
+        "this function call",
+        "",
+        "the degree of the root must be a positive integer",
+    ],
+)]
+pub struct RootError {
+    /// The specific function name.
+    pub function_name: &'static str,
+}
+
+impl RootError {
+    fn spans(&self, call: &Call) -> Vec<Range<usize>> {
+        let mut this_function_call = call.outer_span().to_vec();
+        this_function_call.push(call.args[1].span());
+        this_function_call
+    }
+}
+
+impl From<RootError> for BuiltinError {
+    fn from(e: RootError) -> Self {
+        BuiltinError::FunctionSpecific(FunctionSpecific::Root(e))
+    }
+}
+
+/// Errors for the `min` and `max` builtin functions.
+#[derive(Debug, Clone, Copy, ErrorKind, PartialEq, Eq)]
+#[error(
+    message = format!("cannot compare complex numbers in the `{}` function", self.function_name),
+    labels = [
+        "this function call",
+        "",
+        "this argument has a non-zero imaginary part",
+    ],
+    help = "complex numbers have no natural ordering",
+)]
+pub struct MinMaxError {
+    /// The specific function name.
+    pub function_name: &'static str,
+
+    /// The index of the first complex argument.
+    pub index: usize,
+}
+
+impl MinMaxError {
+    fn spans(&self, call: &Call) -> Vec<Range<usize>> {
+        let mut this_function_call = call.outer_span().to_vec();
+        this_function_call.push(call.args[self.index].span());
+        this_function_call
+    }
+}
+
+impl From<MinMaxError> for BuiltinError {
+    fn from(e: MinMaxError) -> Self {
+        BuiltinError::FunctionSpecific(FunctionSpecific::MinMax(e))
+    }
+}
+
+/// Errors for the `doublefact` builtin function.
+#[derive(Debug, Clone, Copy, ErrorKind, PartialEq, Eq)]
+#[error(
+    message = "incorrect arguments for the `doublefact` function",
+    labels = [
+        "this function call",
+        "",
+        "this argument is too large",
+    ],
+    help = format!("the largest double factorial that can be computed is `doublefact({})`", self.max),
+)]
+pub struct DoubleFactError {
+    /// The largest argument allowed.
+    pub max: u32,
+}
+
+impl DoubleFactError {
+    fn spans(&self, call: &Call) -> Vec<Range<usize>> {
+        let mut this_function_call = call.outer_span().to_vec();
+        this_function_call.push(call.args[0].span());
+        this_function_call
+    }
+}
+
+impl From<DoubleFactError> for BuiltinError {
+    fn from(e: DoubleFactError) -> Self {
+        BuiltinError::FunctionSpecific(FunctionSpecific::DoubleFact(e))
+    }
+}
@@ -12,6 +12,10 @@ pub trait Builtin: std::fmt::Debug + Send + Sync {
     // NOTE: this is a `&self` method and not an associated constant to make the trait object-safe
     fn num_args(&self) -> usize;
 
+    /// Whether the function accepts any number of arguments after its fixed arguments. For
+    /// variadic functions, [`Builtin::num_args`] is the number of fixed arguments.
+    fn variadic(&self) -> bool;
+
     /// Evaluates the function.
     fn eval(&self, ctxt: &Ctxt, args: &mut dyn Iterator<Item = Value>) -> Result;
 }
@@ -0,0 +1,154 @@
+//! Common-subexpression elimination.
+//!
+//! An expression such as `sin(x)^2 + sin(x)` contains the same pure subexpression more than once.
+//! [`eval_cse`] evaluates each such subexpression only once, stores its value in a slot of the
+//! current call frame (see [`Ctxt::slots`]), and replaces each of its occurrences with that slot.
+//!
+//! [`Ctxt::slots`]: crate::numerical::ctxt::Ctxt::slots
+
+use cas_parser::parser::ast::{
+    call::CallArg,
+    expr::Expr,
+    literal::{LitSlot, Literal},
+    structural::ClearSpans,
+};
+use crate::numerical::{
+    ctxt::Ctxt,
+    error::Error,
+    eval::Eval,
+    purity::is_pure_expr,
+    value::Value,
+};
+use std::collections::HashMap;
+
+/// Returns a copy of the expression with its spans cleared, so that identical subexpressions at
+/// different offsets compare and hash equally.
+fn key(expr: &Expr) -> Expr {
+    let mut key = expr.clone();
+    key.clear_spans();
+    key
+}
+
+/// Returns true if the expression could assign a variable, which could change the value of a
+/// subexpression between two of its occurrences.
+fn assigns(expr: &Expr) -> bool {
+    expr.post_order_iter().any(|expr| match expr {
+        Expr::Assign(_) => true,
+        Expr::Block(block) => block.stmts.iter().any(|stmt| assigns(&stmt.expr)),
+        Expr::Literal(Literal::List(list)) => list.values.iter().any(assigns),
+        Expr::Literal(Literal::Tuple(tuple)) => tuple.values.iter().any(assigns),
+        _ => false,
+    })
+}
+
+/// Returns true if the expression is worth sharing: a pure computation that is more than a single
+/// literal or variable.
+fn is_shareable(expr: &Expr, ctxt: &Ctxt) -> bool {
+    matches!(expr, Expr::Call(_) | Expr::Unary(_) | Expr::Binary(_)) && is_pure_expr(expr, ctxt)
+}
+
+/// Returns the slot with the given index, replacing the expression.
+fn slot(expr: &Expr, index: usize) -> Expr {
+    Expr::Literal(Literal::Slot(LitSlot {
+        index,
+        name: expr.to_string(),
+        span: expr.span(),
+    }))
+}
+
+/// Returns the subexpressions of the expression that are always evaluated when the expression is
+/// evaluated.
+///
+/// Subexpressions that are evaluated conditionally, such as the branches of an `if` expression, are
+/// not included, since evaluating them ahead of time could report
+/// an error (or loop forever) where the expression itself would not.
+fn children_mut(expr: &mut Expr) -> Vec<&mut Expr> {
+    match expr {
+        Expr::Literal(Literal::List(list)) => list.values.iter_mut().collect(),
+        Expr::Literal(Literal::Tuple(tuple)) => tuple.values.iter_mut().collect(),
+        Expr::Paren(paren) => vec![&mut *paren.expr],
+        Expr::Unary(unary) => vec![&mut *unary.operand],
+        Expr::Binary(binary) => vec![&mut *binary.lhs, &mut *binary.rhs],
+        Expr::Call(call) => call.args.iter_mut()
+            .map(|arg| match arg {
+                CallArg::Positional(value) | CallArg::Named { value, .. } => value,
+            })
+            .collect(),
+        Expr::Conversion(conversion) => vec![&mut *conversion.value],
+        _ => Vec::new(),
+    }
+}
+
+/// State used while eliminating common subexpressions.
+struct Cse {
+    /// The number of times each shareable subexpression appears, keyed by [`key`].
+    counts: HashMap<Expr, usize>,
+
+    /// The indices of the slots that the values of shared subexpressions are stored in.
+    slots: HashMap<Expr, usize>,
+
+    /// The context the expression is evaluated in, whose call frame holds the shared values.
+    ctxt: Ctxt,
+}
+
+impl Cse {
+    /// Counts the shareable subexpressions of the expression.
+    fn count(&mut self, expr: &mut Expr) {
+        if is_shareable(expr, &self.ctxt) {
+            *self.counts.entry(key(expr)).or_default() += 1;
+        }
+        for child in children_mut(expr) {
+            self.count(child);
+        }
+    }
+
+    /// Replaces each subexpression that appears more than once with a slot holding its value,
+    /// evaluating the subexpression when it is first encountered.
+    fn rewrite(&mut self, expr: &mut Expr) -> Result<(), Error> {
+        let key = key(expr);
+        let shared = self.counts.get(&key).is_some_and(|&count| count > 1);
+        if shared {
+            if let Some(&index) = self.slots.get(&key) {
+                *expr = slot(expr, index);
+                return Ok(());
+            }
+        }
+
+        for child in children_mut(expr) {
+            self.rewrite(child)?;
+        }
+
+        if shared {
+            // the frame can already hold the arguments of an enclosing call, so the new slot is
+            // added after them
+            let value = expr.eval(&mut self.ctxt)?;
+            let index = self.ctxt.slots.len();
+            self.ctxt.slots.push(value);
+            self.slots.insert(key, index);
+            *expr = slot(expr, index);
+        }
+
+        Ok(())
+    }
+}
+
+/// Evaluates the expression in the given context, evaluating each pure subexpression that appears
+/// more than once only once.
+///
+/// The result is the same as that of [`Eval::eval`]. If the expression could assign a variable,
+/// no subexpressions are shared, and the expression is evaluated as usual.
+pub fn eval_cse(expr: &Expr, ctxt: &mut Ctxt) -> Result<Value, Error> {
+    if assigns(expr) {
+        return expr.eval(ctxt);
+    }
+
+    let mut expr = expr.clone();
+    let mut cse = Cse {
+        counts: HashMap::new(),
+        slots: HashMap::new(),
+        ctxt: ctxt.child(),
+    };
+    cse.count(&mut expr);
+    cse.rewrite(&mut expr)?;
+    expr.eval(&mut cse.ctxt)
+}
@@ -1,8 +1,16 @@
 use cas_parser::parser::ast::{assign::FuncHeader, expr::Expr};
 use crate::consts;
+use crate::primitive::float;
 use levenshtein::levenshtein;
-use std::{collections::HashMap, sync::Arc};
-use super::{builtin::Builtin, value::Value};
+use std::{collections::{HashMap, HashSet}, iter, sync::{Arc, Mutex}};
+use super::{
+    builtin::{error::BuiltinError, Builtin},
+    error::{kind::{MissingArgument, TooManyArguments, UndefinedFunction}, Error},
+    eval::call::call_user_func,
+    purity::is_pure,
+    resolve::{resolve, Resolved},
+    value::Value,
+};
 
 #[cfg(feature = "mysql")]
 use mysql_common::prelude::FromValue;
@@ -40,6 +48,47 @@ impl std::fmt::Display for TrigMode {
     }
 }
 
+/// The policy a context uses for non-finite results (`NaN` and infinity) produced while evaluating
+/// an expression.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NonFinitePolicy {
+    /// Non-finite results are returned as-is.
+    Allow,
+
+    /// `NaN` results are reported as an error, but infinite results are returned as-is.
+    #[default]
+    ErrorOnNan,
+
+    /// Both `NaN` and infinite results are reported as an error.
+    Error,
+
+    /// Non-finite results are replaced with a [`Value::NonFinite`] sentinel describing them.
+    /// Operators applied to the sentinel result in the sentinel itself, so it propagates to the
+    /// result of the expression.
+    Sentinel,
+}
+
+impl NonFinitePolicy {
+    /// Returns true if the given value is not allowed by this policy.
+    pub fn rejects(self, value: &Value) -> bool {
+        match self {
+            NonFinitePolicy::Allow | NonFinitePolicy::Sentinel => false,
+            NonFinitePolicy::ErrorOnNan => value.is_nan(),
+            NonFinitePolicy::Error => value.is_nan() || value.is_infinite(),
+        }
+    }
+
+    /// Applies this policy to a value that is allowed by it, replacing a non-finite number with a
+    /// [`Value::NonFinite`] sentinel if the policy is [`NonFinitePolicy::Sentinel`].
+    pub fn apply(self, value: Value) -> Value {
+        match (self, value.non_finite()) {
+            (NonFinitePolicy::Sentinel, Some(non_finite)) => Value::NonFinite(non_finite),
+            _ => value,
+        }
+    }
+}
+
 /// A user-defined function.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -53,6 +102,25 @@ pub struct UserFunc {
     /// Whether the function is recursive, used to report better errors if the stack overflows
     /// while evaluating the function.
     pub recursive: bool,
+
+    /// Whether the function is pure, meaning it always returns the same value for the same
+    /// arguments. See [`is_pure`] for details.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pure: bool,
+
+    /// The results of previous calls to the function, keyed by their arguments. This is only used
+    /// if the function is pure, and [`Ctxt::memoize_pure_funcs`] is enabled.
+    ///
+    /// The cache is shared between all clones of the function, so results computed in the copy of
+    /// the context used by a function call are available to later calls.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) cache: Arc<Mutex<HashMap<String, Value>>>,
+
+    /// The body of the function with its parameters resolved to slots, computed once when the
+    /// function is added to a context. This is [`None`] if the body cannot be resolved, in which
+    /// case calls to the function bind each argument by name instead.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) resolved: Option<Resolved>,
 }
 
 /// A function available for use in a context.
@@ -79,20 +147,65 @@ impl From<UserFunc> for Func {
     }
 }
 
+/// A snapshot of the variables and functions in a [`Ctxt`], created with [`Ctxt::snapshot`] and
+/// restored with [`Ctxt::restore`].
+#[derive(Debug, Clone)]
+pub struct CtxtSnapshot {
+    /// The variables in the context at the time of the snapshot.
+    vars: Arc<HashMap<String, Value>>,
+
+    /// The functions in the context at the time of the snapshot.
+    funcs: Arc<HashMap<String, Func>>,
+}
+
+/// The variables and functions of an enclosing scope of a context created with [`Ctxt::child`].
+#[derive(Debug)]
+struct Scope {
+    /// The variables defined in the scope.
+    vars: Arc<HashMap<String, Value>>,
+
+    /// The functions defined in the scope.
+    funcs: Arc<HashMap<String, Func>>,
+
+    /// The scope enclosing this one, if any.
+    parent: Option<Arc<Scope>>,
+}
+
 /// A context to use when evaluating an expression, containing variables and functions that can be
 /// used within the expression.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ctxt {
-    /// The variables in the context.
-    vars: HashMap<String, Value>,
+    /// The variables defined in the context.
+    ///
+    /// The variables and functions are shared with the children and snapshots of the context, and
+    /// are only copied if the context is changed while they are shared.
+    vars: Arc<HashMap<String, Value>>,
 
-    /// The functions in the context.
-    funcs: HashMap<String, Func>,
+    /// The functions defined in the context.
+    funcs: Arc<HashMap<String, Func>>,
+
+    /// The enclosing scope of a context created with [`Ctxt::child`]. Variables and functions that
+    /// are not defined in the context are looked up in the enclosing scopes.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    parent: Option<Arc<Scope>>,
 
     /// The trigonometric mode of the context.
     pub trig_mode: TrigMode,
 
+    /// The policy for non-finite results produced while evaluating an expression.
+    pub non_finite_policy: NonFinitePolicy,
+
+    /// When true, the `min` and `max` functions compare complex numbers by their magnitude.
+    /// Otherwise, passing a complex number with a non-zero imaginary part to them is an error, since
+    /// complex numbers have no natural ordering.
+    pub order_complex_by_magnitude: bool,
+
+    /// When true, the results of calls to pure user-defined functions are cached, so that calling
+    /// such a function again with the same arguments returns the cached result instead of
+    /// evaluating the function body.
+    pub memoize_pure_funcs: bool,
+
     /// When true, a `break` expression was evaluated in the current loop. The evaluator should
     /// stop and propogate the value of the `break` expression.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -105,6 +218,14 @@ pub struct Ctxt {
     /// TODO: Whether the maximum recursion depth was reached while evaluating an expression.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) max_depth_reached: bool,
+
+    /// The call frame of the innermost call to a user-defined function whose body was resolved to
+    /// slots: its arguments, indexed by parameter, which the body reads through
+    /// [`Literal::Slot`].
+    ///
+    /// [`Literal::Slot`]: cas_parser::parser::ast::literal::Literal::Slot
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) slots: Vec<Value>,
 }
 
 #[cfg(all(feature = "mysql", feature = "serde"))]
@@ -126,21 +247,32 @@ impl TryFrom<mysql_common::Value> for Ctxt {
 impl Default for Ctxt {
     fn default() -> Self {
         Self {
-            vars: HashMap::from([
+            vars: Arc::new([
                 ("i".to_string(), consts::I.clone().into()),
                 ("e".to_string(), consts::E.clone().into()),
                 ("phi".to_string(), consts::PHI.clone().into()),
                 ("pi".to_string(), consts::PI.clone().into()),
                 ("tau".to_string(), consts::TAU.clone().into()),
-            ]),
-            funcs: crate::funcs::all()
+            ]
+                .into_iter()
+                // physical constants are ordinary variables, so they can be shadowed by assignment
+                .chain(consts::physical::all()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value.into())))
+                .collect()),
+            funcs: Arc::new(crate::funcs::all()
                 .into_iter()
                 .map(|(name, func)| (name.to_string(), func.into()))
-                .collect(),
+                .collect()),
+            parent: None,
             trig_mode: TrigMode::default(),
+            non_finite_policy: NonFinitePolicy::default(),
+            order_complex_by_magnitude: false,
+            memoize_pure_funcs: false,
             break_loop: false,
             stack_depth: 0,
             max_depth_reached: false,
+            slots: Vec::new(),
         }
     }
 }
@@ -152,51 +284,264 @@ impl Ctxt {
     /// functions. Consider using the [`Default`] implementation instead.
     pub fn new() -> Ctxt {
         Ctxt {
-            vars: HashMap::new(),
-            funcs: HashMap::new(),
+            vars: Default::default(),
+            funcs: Default::default(),
             ..Default::default()
         }
     }
 
+    /// Returns the variables and functions defined in the context, followed by those of each of its
+    /// enclosing scopes, from the innermost to the outermost.
+    fn scopes(&self) -> impl Iterator<Item = (&HashMap<String, Value>, &HashMap<String, Func>)> {
+        let parents = iter::successors(self.parent.as_deref(), |scope| scope.parent.as_deref());
+        iter::once((&*self.vars, &*self.funcs))
+            .chain(parents.map(|scope| (&*scope.vars, &*scope.funcs)))
+    }
+
     /// Add a variable to the context.
+    ///
+    /// In a context created with [`Ctxt::child`], this shadows any variable with the same name in
+    /// the parent context, which is left unchanged.
     pub fn add_var(&mut self, name: &str, value: Value) {
-        self.vars.insert(name.to_string(), value);
+        Arc::make_mut(&mut self.vars).insert(name.to_string(), value);
     }
 
-    /// Get the value of a variable in the context.
+    /// Get the value of a variable in the context, or in its enclosing scopes.
     pub fn get_var(&self, name: &str) -> Option<Value> {
-        self.vars.get(name).cloned()
+        self.scopes().find_map(|(vars, _)| vars.get(name)).cloned()
     }
 
-    /// Returns the variables in the context.
+    /// Returns the variables defined in the context.
+    ///
+    /// The variables of the enclosing scopes of a context created with [`Ctxt::child`] are not
+    /// included. Use [`Ctxt::get_var`] to also look up a variable in the enclosing scopes.
     pub fn get_vars(&self) -> &HashMap<String, Value> {
         &self.vars
     }
 
     /// Add a function to the context.
     pub fn add_func(&mut self, header: FuncHeader, body: Expr, recursive: bool) {
-        self.funcs.insert(
+        let pure = is_pure(&header, &body, self);
+        let resolved = resolve(&header, &body);
+        let replaced = Arc::make_mut(&mut self.funcs).insert(
             header.name.name.clone(),
-            Func::UserFunc(UserFunc { header, body, recursive }),
+            Func::UserFunc(UserFunc {
+                header,
+                body,
+                recursive,
+                pure,
+                cache: Default::default(),
+                resolved,
+            }),
         );
+
+        // functions calling the shadowed builtin now call a user-defined function instead, which
+        // could change their purity
+        if let Some(Func::Builtin(_)) = replaced {
+            self.update_purity();
+        }
     }
 
-    /// Get the header and body of a function in the context.
+    /// Add a builtin function to the context, such as a function provided by the host application.
+    ///
+    /// Builtin functions other than `print` and `rand` are assumed to be pure.
+    pub fn add_builtin(&mut self, name: &str, builtin: impl Builtin + 'static) {
+        let replaced = Arc::make_mut(&mut self.funcs)
+            .insert(name.to_string(), Func::Builtin(Arc::new(builtin)));
+
+        // functions calling the shadowed user-defined function now call a builtin instead
+        if let Some(Func::UserFunc(_)) = replaced {
+            self.update_purity();
+        }
+    }
+
+    /// Re-checks the purity of every user-defined function, discarding their cached results.
+    ///
+    /// Functions inherited from an enclosing scope are copied into the context before they are
+    /// updated, so that the enclosing scope is left unchanged.
+    fn update_purity(&mut self) {
+        let mut seen = HashSet::new();
+        let purity = self.scopes()
+            .flat_map(|(_, funcs)| funcs)
+            .filter(|(name, _)| seen.insert(name.as_str()))
+            .filter_map(|(name, func)| match func {
+                Func::UserFunc(func) => Some((name.clone(), is_pure(&func.header, &func.body, self))),
+                Func::Builtin(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let funcs = Arc::make_mut(&mut self.funcs);
+        for (name, pure) in purity {
+            if !funcs.contains_key(&name) {
+                let inherited = iter::successors(self.parent.as_deref(), |scope| scope.parent.as_deref())
+                    .find_map(|scope| scope.funcs.get(&name))
+                    .cloned();
+                if let Some(func) = inherited {
+                    funcs.insert(name.clone(), func);
+                }
+            }
+            if let Some(Func::UserFunc(func)) = funcs.get_mut(&name) {
+                func.pure = pure;
+                func.cache = Default::default();
+            }
+        }
+    }
+
+    /// Get the header and body of a function in the context, or in its enclosing scopes.
     pub fn get_func(&self, name: &str) -> Option<&Func> {
-        self.funcs.get(name)
+        self.scopes().find_map(|(_, funcs)| funcs.get(name))
+    }
+
+    /// Returns the functions of the innermost scope defining the function with the given name.
+    ///
+    /// The map is shared with the context, so holding it lets the caller borrow the function while
+    /// modifying the context, such as when evaluating the function's body.
+    pub(crate) fn get_func_scope(&self, name: &str) -> Option<Arc<HashMap<String, Func>>> {
+        let parents = iter::successors(self.parent.as_deref(), |scope| scope.parent.as_deref());
+        iter::once(&self.funcs)
+            .chain(parents.map(|scope| &scope.funcs))
+            .find(|funcs| funcs.contains_key(name))
+            .cloned()
     }
 
-    /// Returns the functions in the context.
+    /// Returns the functions defined in the context.
+    ///
+    /// The functions of the enclosing scopes of a context created with [`Ctxt::child`] are not
+    /// included. Use [`Ctxt::get_func`] to also look up a function in the enclosing scopes.
     pub fn get_funcs(&self) -> &HashMap<String, Func> {
         &self.funcs
     }
 
+    /// Returns a key identifying every setting of the context that can change the result of
+    /// evaluating an expression, such as the trigonometric mode.
+    ///
+    /// Two contexts with the same key evaluate a pure expression to the same value.
+    pub(crate) fn settings_key(&self) -> String {
+        let Self {
+            trig_mode,
+            non_finite_policy,
+            order_complex_by_magnitude,
+            ..
+        } = self;
+        format!("{:?} {:?} {:?}", trig_mode, non_finite_policy, order_complex_by_magnitude)
+    }
+
+    /// Creates a child context to evaluate a nested scope in, such as the body of a function call.
+    ///
+    /// The child starts out empty, and its parent is this context: variables and functions that are
+    /// not defined in the child are looked up in this context. Anything defined or shadowed in the
+    /// child stays local to it, and is discarded when the child is dropped. The child has the same
+    /// settings as this context, but does not inherit a pending `break`.
+    ///
+    /// Nothing is copied to create the child; it shares the variables and functions of this context
+    /// until either context is changed.
+    pub fn child(&self) -> Ctxt {
+        // skip this context if it defines nothing itself, so that the chain of enclosing scopes
+        // does not grow with every nested scope
+        let parent = if self.vars.is_empty() && self.funcs.is_empty() {
+            self.parent.clone()
+        } else {
+            Some(Arc::new(Scope {
+                vars: self.vars.clone(),
+                funcs: self.funcs.clone(),
+                parent: self.parent.clone(),
+            }))
+        };
+        Ctxt {
+            vars: Default::default(),
+            funcs: Default::default(),
+            parent,
+            break_loop: false,
+            ..self.clone()
+        }
+    }
+
+    /// Evaluates the single-argument function with the given name at each of the given values, such
+    /// as when plotting the function.
+    ///
+    /// The function is looked up only once, and each value is passed to it as its first argument,
+    /// in the same way as a call to the function; any other parameters of a user-defined function
+    /// must have default values. Since there is no call in the source code, errors are reported
+    /// without spans.
+    pub fn eval_sweep(&self, func: &str, xs: &[f64]) -> Result<Vec<Value>, Error> {
+        let name = func;
+        let func = self.get_func(name)
+            .ok_or_else(|| Error::new(Vec::new(), UndefinedFunction {
+                name: name.to_string(),
+                suggestions: self.get_similar_funcs(name)
+                    .into_iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }))?;
+
+        // a resolved body that calls no other function leaves the context unchanged, so the same
+        // child context is used for every value
+        let mut ctxt = self.child();
+        xs.iter()
+            .map(|&x| {
+                let x = Value::Float(float(x));
+                match func {
+                    Func::Builtin(builtin) => builtin.eval(&ctxt, &mut Some(x).into_iter())
+                        .map_err(BuiltinError::into_spanless_error),
+                    Func::UserFunc(func) => {
+                        let header = &func.header;
+                        if header.params.is_empty() {
+                            return Err(Error::new(Vec::new(), TooManyArguments {
+                                name: name.to_string(),
+                                expected: 0,
+                                given: 1,
+                                signature: header.to_string(),
+                            }));
+                        }
+
+                        let mut args = vec![None; header.params.len()];
+                        args[0] = Some(x);
+                        call_user_func(func, &mut ctxt, args, |index| Error::new(Vec::new(), MissingArgument {
+                            name: name.to_string(),
+                            index,
+                            expected: header.params.len(),
+                            given: 1,
+                            signature: header.to_string(),
+                        }))
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Captures the variables and functions in the context, so that they can be restored later
+    /// with [`Ctxt::restore`], for example, to roll back a script that failed partway through.
+    ///
+    /// The snapshot shares the variables and functions of the context, so nothing is copied until
+    /// the context is next changed. For a context created with [`Ctxt::child`], only the variables
+    /// and functions defined in the child are captured.
+    pub fn snapshot(&self) -> CtxtSnapshot {
+        CtxtSnapshot {
+            vars: self.vars.clone(),
+            funcs: self.funcs.clone(),
+        }
+    }
+
+    /// Restores the variables and functions in the context to the state captured by
+    /// [`Ctxt::snapshot`]. Any variables or functions defined since then are discarded.
+    ///
+    /// The trigonometric mode and non-finite policy of the context are not affected.
+    pub fn restore(&mut self, snapshot: CtxtSnapshot) {
+        self.vars = snapshot.vars;
+        self.funcs = snapshot.funcs;
+    }
+
     /// Returns all functions in the context with a name similar to the given name.
     pub fn get_similar_funcs(&self, name: &str) -> Vec<&str> {
-        self.funcs
-            .iter()
-            .filter(|(n, _)| levenshtein(n, name) < 2)
-            .map(|(n, _)| n.as_str())
-            .collect()
+        let mut similar = self.scopes()
+            .flat_map(|(_, funcs)| funcs.keys())
+            .filter(|n| levenshtein(n, name) < 2)
+            .map(|n| n.as_str())
+            .collect::<Vec<_>>();
+
+        // a function can be shadowed in a child context
+        similar.sort_unstable();
+        similar.dedup();
+        similar
     }
 }
@@ -49,9 +49,75 @@ pub struct InvalidUnaryOperation {
 #[error(
     message = "maximum bitshift amount exceeded",
     labels = ["this expression", "", "too many bits to shift by"],
-    help = "the maximum number of bits you can shift an integer by is equal to: `2^64 - 1`"
+    help = format!("the maximum number of bits you can shift an integer by is {}", self.max)
 )]
-pub struct BitshiftOverflow;
+pub struct ShiftTooLarge {
+    /// The maximum number of bits that can be shifted by.
+    pub max: usize,
+}
+
+/// Attempted to convert a non-finite value (`NaN` or infinity) to an integer, such as with a
+/// bitwise operator.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "cannot convert a non-finite value to an integer",
+    help = "bitwise operators require their operands to be finite"
+)]
+pub struct NonFiniteInteger;
+
+/// Attempted to compare the order of two values of a type with no natural ordering, such as complex
+/// numbers.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("cannot order values of type `{}`", self.typename),
+    labels = [
+        format!("this operand has type `{}`", self.typename),
+        format!("the `{:?}` operator requires ordered operands", self.op),
+        format!("this operand has type `{}`", self.typename),
+    ],
+    help = format!("values of type `{}` can only be compared for equality with `==` or `!=`", self.typename)
+)]
+pub struct UnorderedComparison {
+    /// The comparison operator that was used.
+    pub op: BinOpKind,
+
+    /// The name of the type that has no ordering.
+    pub typename: &'static str,
+}
+
+/// Attempted to perform integer division by zero.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "division by zero",
+    labels = ["this expression", "", "evaluates to zero"],
+    help = "integer division requires a non-zero divisor"
+)]
+pub struct DivisionByZero;
+
+/// None of the conditions of a piecewise expression were true, and there was no `otherwise`
+/// branch to fall back to.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "no branch of this piecewise expression matched",
+    labels = ["this piecewise expression"],
+    help = "add an `otherwise` branch to handle the remaining cases"
+)]
+pub struct NoMatchingBranch;
+
+/// An expression evaluated to a non-finite value (`NaN` or infinity), which is not allowed by the
+/// context's [`NonFinitePolicy`].
+///
+/// [`NonFinitePolicy`]: crate::numerical::ctxt::NonFinitePolicy
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("expression evaluated to {}", if self.nan { "NaN" } else { "infinity" }),
+    labels = ["this expression"],
+    help = "change the context's non-finite policy to allow this result"
+)]
+pub struct NonFiniteResult {
+    /// Whether the value was `NaN`. (Otherwise, it was infinite.)
+    pub nan: bool,
+}
 
 /// The variable is undefined.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
@@ -65,6 +131,31 @@ pub struct UndefinedVariable {
     pub name: String,
 }
 
+/// The unit of a measurement is not a known unit.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("`{}` is not a known unit", self.name),
+    labels = ["this unit"],
+)]
+pub struct UnknownUnit {
+    /// The name of the unknown unit.
+    pub name: String,
+}
+
+/// The value cannot be converted to the target unit.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("cannot convert from `{}` to `{}`", self.from, self.to),
+    labels = ["this value", "this unit"],
+)]
+pub struct InvalidConversion {
+    /// The unit of the value, or its type if it is not a measurement.
+    pub from: String,
+
+    /// The name of the target unit.
+    pub to: String,
+}
+
 /// The function is undefined.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -93,6 +184,74 @@ pub struct UndefinedFunction {
     pub suggestions: Vec<String>,
 }
 
+/// Attempted to destructure a value into multiple variables, but the value is not a tuple, or has a
+/// different number of elements than there are variables.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = match self.given {
+        Some(given) => format!("cannot destructure a tuple of {} values into {} variables", given, self.expected),
+        None => format!("cannot destructure a value of type `{}`", self.typename),
+    },
+    labels = [
+        format!("{} variables", self.expected),
+        format!("this expression has type `{}`", self.typename),
+    ],
+    help = format!("the value must be a tuple with exactly {} values", self.expected)
+)]
+pub struct DestructureMismatch {
+    /// The number of variables being assigned to.
+    pub expected: usize,
+
+    /// The number of values in the tuple, or [`None`] if the value is not a tuple.
+    pub given: Option<usize>,
+
+    /// The type of the value.
+    pub typename: &'static str,
+}
+
+/// An expression was expected to be a polynomial in a variable, but it is not.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("expression is not a polynomial in `{}`", self.var),
+    labels = ["this subexpression".to_string()],
+    help = format!("polynomials can only add, subtract, and multiply `{}`, or raise it to a constant non-negative integer power", self.var)
+)]
+pub struct NotPolynomial {
+    /// The variable of the polynomial.
+    pub var: String,
+}
+
+/// The degree of a polynomial is too large to compute its coefficients.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "maximum polynomial degree exceeded",
+    labels = ["this subexpression".to_string()],
+    help = format!("the maximum degree of a polynomial is {}", self.max)
+)]
+pub struct PolynomialTooLarge {
+    /// The maximum degree of a polynomial.
+    pub max: usize,
+}
+
+/// Attempted to call a variable that is not a function.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("cannot call `{}` as a function", self.name),
+    labels = [format!("`{}` is a variable of type `{}`", self.name, self.typename)],
+    help = format!(
+        "to multiply `{}` by this value, write the multiplication explicitly, such as `{} * (...)`",
+        (&self.name).fg(EXPR),
+        self.name,
+    )
+)]
+pub struct NotCallable {
+    /// The name of the variable that was called.
+    pub name: String,
+
+    /// The type of the variable that was called.
+    pub typename: &'static str,
+}
+
 /// Too many arguments were given to a function call.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -150,6 +309,56 @@ pub struct MissingArgument {
     pub signature: String,
 }
 
+/// A named argument was given to a function that has no parameter with that name.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("the `{}` function has no parameter named `{}`", self.name, self.arg),
+    labels = ["this argument"],
+    help = "check the spelling of the argument name",
+    note = format!("function signature: {}", self.signature),
+)]
+pub struct UnknownNamedArgument {
+    /// The name of the function that was called.
+    pub name: String,
+
+    /// The name of the argument that was given.
+    pub arg: String,
+
+    /// The signature of the function.
+    pub signature: String,
+}
+
+/// More than one argument was given for the same parameter of a function call.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("the `{}` parameter of the `{}` function was given more than once", self.arg, self.name),
+    labels = ["this argument"],
+    help = "remove this argument, or pass it to a different parameter",
+    note = format!("function signature: {}", self.signature),
+)]
+pub struct DuplicateArgument {
+    /// The name of the function that was called.
+    pub name: String,
+
+    /// The name of the parameter that was given more than once.
+    pub arg: String,
+
+    /// The signature of the function.
+    pub signature: String,
+}
+
+/// A named argument was given to a builtin function.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("the `{}` function does not accept named arguments", self.name),
+    labels = ["this argument"],
+    help = "only user-defined functions accept named arguments; pass this argument positionally",
+)]
+pub struct NamedArgumentToBuiltin {
+    /// The name of the function that was called.
+    pub name: String,
+}
+
 /// An argument to a function call has the wrong type.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
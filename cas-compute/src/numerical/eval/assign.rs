@@ -5,7 +5,7 @@ use cas_parser::parser::{
 use crate::eval_break;
 use crate::numerical::{
     ctxt::Ctxt,
-    error::{kind::UndefinedVariable, Error},
+    error::{kind::{DestructureMismatch, UndefinedVariable}, Error},
     eval::{binary::eval_operands, Eval},
     value::Value,
 };
@@ -39,6 +39,35 @@ fn assign_to_symbol(
     }
 }
 
+/// Evaluate an assignment expression that destructures a tuple into multiple symbols.
+fn assign_to_destructure(
+    symbols: &[LitSym],
+    assign: &Assign,
+    ctxt: &mut Ctxt,
+) -> Result<Value, Error> {
+    let rhs = eval_break!(assign.value, ctxt);
+    let mismatch = |given| Error::new(
+        vec![assign.target.span(), assign.value.span()],
+        DestructureMismatch {
+            expected: symbols.len(),
+            given,
+            typename: rhs.typename(),
+        },
+    );
+
+    let Value::Tuple(values) = &rhs else {
+        return Err(mismatch(None));
+    };
+    if values.len() != symbols.len() {
+        return Err(mismatch(Some(values.len())));
+    }
+
+    for (symbol, value) in symbols.iter().zip(values) {
+        ctxt.add_var(&symbol.name, value.clone());
+    }
+    Ok(rhs)
+}
+
 impl Eval for Assign {
     fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
         match &self.target {
@@ -46,6 +75,10 @@ impl Eval for Assign {
                 // variable assignment
                 assign_to_symbol(symbol, self, self.op.kind, ctxt)
             },
+            AssignTarget::Destructure(symbols) => {
+                // compound assignment to multiple symbols is rejected by the parser
+                assign_to_destructure(symbols, self, ctxt)
+            },
             AssignTarget::Func(header) => {
                 // function assignment
                 ctxt.add_func(
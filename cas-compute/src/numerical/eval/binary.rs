@@ -1,14 +1,35 @@
 use cas_parser::parser::{ast::binary::Binary, token::op::BinOpKind};
-use rug::ops::Pow;
+use rug::{ops::{DivRounding, Pow}, Integer};
 use crate::eval_break;
 use crate::numerical::{
     ctxt::Ctxt,
-    error::{kind::{BitshiftOverflow, InvalidBinaryOperation}, Error},
+    error::{
+        kind::{
+            DivisionByZero,
+            InvalidBinaryOperation,
+            NonFiniteInteger,
+            ShiftTooLarge,
+            UnorderedComparison,
+        },
+        Error,
+    },
     eval::{error::EvalError, Eval},
     value::Value,
 };
 use crate::primitive::{int_from_float, float};
 
+/// The maximum number of bits an integer can be shifted by. Shifting by more than this would
+/// allocate an unreasonable amount of memory.
+const MAX_SHIFT: usize = 1024;
+
+/// Converts the right-hand side of a bitshift into a shift amount, returning an error if it is
+/// negative or greater than [`MAX_SHIFT`].
+fn shift_amount(right: &Integer) -> Result<usize, ShiftTooLarge> {
+    right.to_usize()
+        .filter(|&amount| amount <= MAX_SHIFT)
+        .ok_or(ShiftTooLarge { max: MAX_SHIFT })
+}
+
 /// Evaluates a binary expression with two integer operands.
 fn eval_integer_operands(
     op: BinOpKind,
@@ -37,11 +58,17 @@ fn eval_integer_operands(
         },
         BinOpKind::Mul => Value::Integer(left * right),
         BinOpKind::Div => Value::Float(float(left) / float(right)),
+        BinOpKind::IntDiv => {
+            if right.is_zero() {
+                return Err(DivisionByZero.into());
+            }
+            Value::Integer(left.div_floor(right))
+        },
         BinOpKind::Mod => Value::Integer(left % right),
         BinOpKind::Add => Value::Integer(left + right),
         BinOpKind::Sub => Value::Integer(left - right),
-        BinOpKind::BitRight => Value::Integer(left >> right.to_usize().ok_or(BitshiftOverflow)?),
-        BinOpKind::BitLeft => Value::Integer(left << right.to_usize().ok_or(BitshiftOverflow)?),
+        BinOpKind::BitRight => Value::Integer(left >> shift_amount(&right)?),
+        BinOpKind::BitLeft => Value::Integer(left << shift_amount(&right)?),
         BinOpKind::BitAnd => Value::Integer(left & right),
         BinOpKind::BitOr => Value::Integer(left | right),
         BinOpKind::Greater => Value::Boolean(left > right),
@@ -76,13 +103,26 @@ fn eval_real_operands(
         BinOpKind::Exp => Value::Float(left.pow(right)),
         BinOpKind::Mul => Value::Float(left * right),
         BinOpKind::Div => Value::Float(left / right),
+        BinOpKind::IntDiv => {
+            if right.is_zero() {
+                return Err(DivisionByZero.into());
+            }
+            Value::Float((left / right).floor())
+        },
         BinOpKind::Mod => Value::Float(left % right),
         BinOpKind::Add => Value::Float(left + right),
         BinOpKind::Sub => Value::Float(left - right),
-        BinOpKind::BitRight => Value::Float(float(int_from_float(left) >> int_from_float(right).to_usize().ok_or(BitshiftOverflow)?)),
-        BinOpKind::BitLeft => Value::Float(float(int_from_float(left) << int_from_float(right).to_usize().ok_or(BitshiftOverflow)?)),
-        BinOpKind::BitAnd => Value::Float(float(int_from_float(left) & int_from_float(right))),
-        BinOpKind::BitOr => Value::Float(float(int_from_float(left) | int_from_float(right))),
+        BinOpKind::BitRight | BinOpKind::BitLeft | BinOpKind::BitAnd | BinOpKind::BitOr => {
+            let (Some(left), Some(right)) = (int_from_float(left), int_from_float(right)) else {
+                return Err(NonFiniteInteger.into());
+            };
+            Value::Float(float(match op {
+                BinOpKind::BitRight => left >> shift_amount(&right)?,
+                BinOpKind::BitLeft => left << shift_amount(&right)?,
+                BinOpKind::BitAnd => left & right,
+                _ => left | right,
+            }))
+        },
         BinOpKind::Greater => Value::Boolean(left > right),
         BinOpKind::GreaterEq => Value::Boolean(left >= right),
         BinOpKind::Less => Value::Boolean(left < right),
@@ -111,12 +151,13 @@ fn eval_complex_operands(
     let (Value::Complex(left), Value::Complex(right)) = (left, right) else {
         unreachable!()
     };
+    // arithmetic results with an imaginary part of exactly zero are demoted back to real numbers
     Ok(match op {
-        BinOpKind::Exp => Value::Complex(left.pow(right)),
-        BinOpKind::Mul => Value::Complex(left * right),
-        BinOpKind::Div => Value::Complex(left / right),
-        BinOpKind::Add => Value::Complex(left + right),
-        BinOpKind::Sub => Value::Complex(left - right),
+        BinOpKind::Exp => Value::Complex(left.pow(right)).coerce_float(),
+        BinOpKind::Mul => Value::Complex(left * right).coerce_float(),
+        BinOpKind::Div => Value::Complex(left / right).coerce_float(),
+        BinOpKind::Add => Value::Complex(left + right).coerce_float(),
+        BinOpKind::Sub => Value::Complex(left - right).coerce_float(),
         BinOpKind::Eq => Value::Boolean(left == right),
         BinOpKind::NotEq => Value::Boolean(left != right),
         BinOpKind::ApproxEq => {
@@ -133,9 +174,13 @@ fn eval_complex_operands(
                 (real - real2).abs() >= 1e-6 || (float - float2).abs() >= 1e-6,
             )
         },
-        BinOpKind::And | BinOpKind::Or | BinOpKind::Mod
-            | BinOpKind::BitRight | BinOpKind::BitLeft | BinOpKind::BitAnd | BinOpKind::BitOr
-            | BinOpKind::Greater | BinOpKind::GreaterEq | BinOpKind::Less | BinOpKind::LessEq => Err(InvalidBinaryOperation {
+        // complex numbers have no natural ordering
+        BinOpKind::Greater | BinOpKind::GreaterEq | BinOpKind::Less | BinOpKind::LessEq => Err(UnorderedComparison {
+            op,
+            typename,
+        })?,
+        BinOpKind::And | BinOpKind::Or | BinOpKind::IntDiv | BinOpKind::Mod
+            | BinOpKind::BitRight | BinOpKind::BitLeft | BinOpKind::BitAnd | BinOpKind::BitOr => Err(InvalidBinaryOperation {
                 op,
                 implicit,
                 left: typename,
@@ -162,9 +207,9 @@ fn eval_bool_operands(
         BinOpKind::NotEq => Value::Boolean(left != right),
         BinOpKind::ApproxEq => Value::Boolean(left == right),
         BinOpKind::ApproxNotEq => Value::Boolean(left != right),
-        BinOpKind::Exp | BinOpKind::Mul | BinOpKind::Div | BinOpKind::Mod | BinOpKind::Add
-            | BinOpKind::Sub | BinOpKind::BitRight | BinOpKind::BitLeft | BinOpKind::BitAnd
-            | BinOpKind::BitOr | BinOpKind::Greater | BinOpKind::GreaterEq | BinOpKind::Less
+        BinOpKind::Exp | BinOpKind::Mul | BinOpKind::Div | BinOpKind::IntDiv | BinOpKind::Mod
+            | BinOpKind::Add | BinOpKind::Sub | BinOpKind::BitRight | BinOpKind::BitLeft
+            | BinOpKind::BitAnd | BinOpKind::BitOr | BinOpKind::Greater | BinOpKind::GreaterEq | BinOpKind::Less
             | BinOpKind::LessEq => Err(InvalidBinaryOperation {
                 op,
                 implicit,
@@ -188,9 +233,9 @@ fn eval_unit_operands(
     Ok(match op {
         BinOpKind::Eq | BinOpKind::ApproxEq | BinOpKind::GreaterEq | BinOpKind::LessEq => Value::Boolean(true),
         BinOpKind::NotEq | BinOpKind::ApproxNotEq | BinOpKind::Greater | BinOpKind::Less => Value::Boolean(false),
-        BinOpKind::Exp | BinOpKind::Mul | BinOpKind::Div | BinOpKind::Mod | BinOpKind::Add
-            | BinOpKind::Sub | BinOpKind::BitRight | BinOpKind::BitLeft | BinOpKind::BitAnd
-            | BinOpKind::BitOr | BinOpKind::And | BinOpKind::Or => Err(InvalidBinaryOperation {
+        BinOpKind::Exp | BinOpKind::Mul | BinOpKind::Div | BinOpKind::IntDiv | BinOpKind::Mod
+            | BinOpKind::Add | BinOpKind::Sub | BinOpKind::BitRight | BinOpKind::BitLeft
+            | BinOpKind::BitAnd | BinOpKind::BitOr | BinOpKind::And | BinOpKind::Or => Err(InvalidBinaryOperation {
                 op,
                 implicit,
                 left: typename,
@@ -206,6 +251,14 @@ pub(crate) fn eval_operands(
     left: Value,
     right: Value,
 ) -> Result<Value, EvalError> {
+    // non-finite sentinels propagate through operators, like the numbers they stand for
+    if let Value::NonFinite(_) = left {
+        return Ok(left);
+    }
+    if let Value::NonFinite(_) = right {
+        return Ok(right);
+    }
+
     if left.is_integer() && right.is_integer() {
         return eval_integer_operands(op, implicit, left.coerce_integer(), right.coerce_integer());
     }
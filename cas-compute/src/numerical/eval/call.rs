@@ -1,16 +1,20 @@
-use cas_parser::parser::ast::{assign::Param, call::Call};
+use cas_parser::parser::ast::{assign::Param, call::{Call, CallArg}};
 use crate::eval_break;
 use crate::funcs::combinatoric::Ncr;
 use crate::numerical::{
     ctxt::{MAX_RECURSION_DEPTH, Ctxt, Func, UserFunc},
     error::{
         kind::{
+            DuplicateArgument,
             InvalidDerivativeArguments,
             MissingArgument,
+            NamedArgumentToBuiltin,
             NonNumericDerivative,
+            NotCallable,
             StackOverflow,
             TooManyArguments,
             UndefinedFunction,
+            UnknownNamedArgument,
         },
         Error,
     },
@@ -60,7 +64,7 @@ fn compute_derivative(
     };
 
     let initial = get_real(initial)?;
-    let derivatives = call.derivatives;
+    let derivatives = u32::try_from(call.derivatives).unwrap_or(u32::MAX);
 
     for k in 0..=derivatives {
         // synonym for a = (-1)^(k + derivatives) to avoid overflow errors
@@ -84,23 +88,176 @@ fn compute_derivative(
     Ok(Value::Float((result_left + result_right) / 2))
 }
 
+/// Returns true if the arguments of a call to the user-defined function must be bound to variables
+/// named after their parameters.
+///
+/// This is the case unless the body was resolved to slots and calls no other function, since a
+/// function called from the body can read the parameters of the caller by name.
+fn binds_names(func: &UserFunc) -> bool {
+    func.resolved.as_ref().is_none_or(|resolved| resolved.calls)
+}
+
+/// Checks that a call to the user-defined function does not exceed the maximum recursion depth.
+///
+/// If the function is recursive, the call is counted in [`Ctxt::stack_depth`], which must be
+/// decremented once the call is complete.
+fn enter_call(func: &UserFunc, ctxt: &mut Ctxt) -> Result<(), Error> {
+    if func.recursive {
+        ctxt.stack_depth += 1;
+    }
+
+    if ctxt.stack_depth > MAX_RECURSION_DEPTH {
+        // TODO
+        ctxt.max_depth_reached = true;
+
+        // we do not include the call span
+        //
+        // this is because this call span is the span of the function call within the *function
+        // definition*, not the user's input
+        //
+        // this error will be propogated up to the top level of the call stack, where `self` is
+        // now representing the function call of the user's input
+        return Err(Error::new(vec![], StackOverflow));
+    }
+
+    Ok(())
+}
+
+/// Returns the value of each parameter of the user-defined function, given the argument passed to
+/// each parameter, if any.
+///
+/// A parameter without an argument takes its default value. If it has none, the error returned by
+/// `missing` for the index of the parameter is reported. If `bind_names` is true, each value is also
+/// added to the context as a variable named after its parameter.
+fn bind_args(
+    func: &UserFunc,
+    ctxt: &mut Ctxt,
+    args: Vec<Option<Value>>,
+    bind_names: bool,
+    missing: impl Fn(usize) -> Error,
+) -> Result<Vec<Value>, Error> {
+    let mut values = Vec::with_capacity(func.header.params.len());
+    for (index, (arg, param)) in args.into_iter().zip(&func.header.params).enumerate() {
+        let value = match (arg, param) {
+            // add the argument to the context for use in the function body
+            (Some(value), _) => value,
+
+            // no argument was given for this parameter
+            // use the default value if there is one
+            (None, Param::Default(_, expr)) => expr.eval(ctxt)?,
+
+            // if there is no default, that's an error
+            (None, Param::Symbol(_)) => return Err(missing(index)),
+        };
+        if bind_names {
+            ctxt.add_var(&param.symbol().name, value.clone());
+        }
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Evaluates the body of the user-defined function, given the value of each parameter returned by
+/// [`bind_args`].
+///
+/// If the function is pure and [`Ctxt::memoize_pure_funcs`] is enabled, the result is looked up in
+/// and added to the cache of the function.
+fn eval_body(func: &UserFunc, ctxt: &mut Ctxt, values: Vec<Value>) -> Result<Value, Error> {
+    // settings such as the trigonometric mode can change the result of the body
+    let memo_key = (ctxt.memoize_pure_funcs && func.pure)
+        .then(|| format!("{} {:?}", ctxt.settings_key(), values));
+    if let Some(key) = &memo_key {
+        if let Some(value) = func.cache.lock().unwrap().get(key) {
+            return Ok(value.clone());
+        }
+    }
+
+    // no eval_break!; cannot break out of loops from within a function
+    let result = match &func.resolved {
+        Some(resolved) => {
+            // the frame of the caller is restored once the body is evaluated
+            let frame = std::mem::replace(&mut ctxt.slots, values);
+            let result = resolved.body.eval(ctxt);
+            ctxt.slots = frame;
+            result
+        },
+        None => func.body.eval(ctxt),
+    };
+    if let (Some(key), Ok(value)) = (memo_key, &result) {
+        func.cache.lock().unwrap().insert(key, value.clone());
+    }
+    result
+}
+
+/// Calls the user-defined function, given the value of the argument passed to each parameter, if
+/// any, as a call to the function in the source code would.
+///
+/// If a parameter has neither an argument nor a default value, the error returned by `missing` for
+/// the index of the parameter is reported.
+///
+/// A body resolved to slots that calls no other function cannot read or assign any variable of its
+/// own, so it is evaluated in the given context, with its arguments as a new call frame. Otherwise,
+/// it is evaluated in a child of the given context, which the body can freely modify.
+pub(crate) fn call_user_func(
+    func: &UserFunc,
+    ctxt: &mut Ctxt,
+    args: Vec<Option<Value>>,
+    missing: impl Fn(usize) -> Error,
+) -> Result<Value, Error> {
+    let bind_names = binds_names(func);
+    let mut child;
+    let ctxt = if bind_names {
+        child = ctxt.child();
+        &mut child
+    } else {
+        ctxt
+    };
+    enter_call(func, ctxt)?;
+
+    let values = bind_args(func, ctxt, args, bind_names, missing)?;
+    let result = eval_body(func, ctxt, values);
+    if func.recursive {
+        ctxt.stack_depth -= 1;
+    }
+    result
+}
+
 impl Eval for Call {
     fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
-        let func = ctxt.get_func(&self.name.name)
-            .ok_or_else(|| Error::new(vec![self.name.span.clone()], UndefinedFunction {
-                name: self.name.name.clone(),
-                suggestions: ctxt.get_similar_funcs(&self.name.name)
-                    .into_iter()
-                    .map(|s| s.to_string())
-                    .collect(),
-            }))?;
-        let mut ctxt = ctxt.clone();
+        // hold the functions of the scope defining the function, so that the body can be evaluated
+        // in the caller's context while it is borrowed
+        let funcs = ctxt.get_func_scope(&self.name.name)
+            .ok_or_else(|| {
+                // calling a variable is likely a mistake, so report it separately from an
+                // undefined function
+                if let Some(value) = ctxt.get_var(&self.name.name) {
+                    return Error::new(vec![self.name.span.clone()], NotCallable {
+                        name: self.name.name.clone(),
+                        typename: value.typename(),
+                    });
+                }
+
+                Error::new(vec![self.name.span.clone()], UndefinedFunction {
+                    name: self.name.name.clone(),
+                    suggestions: ctxt.get_similar_funcs(&self.name.name)
+                        .into_iter()
+                        .map(|s| s.to_string())
+                        .collect(),
+                })
+            })?;
+        let func = &funcs[&self.name.name];
 
         match func {
             Func::Builtin(builtin) => {
+                let mut ctxt = ctxt.child();
                 let mut args = Vec::with_capacity(self.args.len());
                 for arg in self.args.iter() {
-                    args.push(eval_break!(arg, &mut ctxt));
+                    if let CallArg::Named { span, .. } = arg {
+                        return Err(Error::new(vec![span.clone()], NamedArgumentToBuiltin {
+                            name: self.name.name.clone(),
+                        }));
+                    }
+                    args.push(eval_break!(arg.value(), &mut ctxt));
                 }
 
                 if self.derivatives == 0 {
@@ -116,86 +273,99 @@ impl Eval for Call {
                     compute_derivative(self, func, &mut ctxt, args.swap_remove(0))
                 }
             },
-            Func::UserFunc(UserFunc { header, body, recursive }) => {
-                let mut ctxt = ctxt.clone();
-                if *recursive {
-                    ctxt.stack_depth += 1;
-                }
+            Func::UserFunc(user_func) => {
+                let header = &user_func.header;
+
+                // the body resolved to slots can only be used to evaluate the function itself, not
+                // its derivatives
+                let bind_names = self.derivatives > 0 || binds_names(user_func);
+
+                // see `call_user_func`
+                let mut child;
+                let ctxt = if bind_names {
+                    child = ctxt.child();
+                    &mut child
+                } else {
+                    ctxt
+                };
+                enter_call(user_func, ctxt)?;
+
+                // positional arguments always come before named arguments
+                let positional = self.args.iter()
+                    .take_while(|arg| matches!(arg, CallArg::Positional(_)))
+                    .count();
 
-                if ctxt.stack_depth > MAX_RECURSION_DEPTH {
-                    // TODO
-                    ctxt.max_depth_reached = true;
-
-                    // we do not include the call span
-                    //
-                    // this is because this call span is the span of the function call within the *function
-                    // definition*, not the user's input
-                    //
-                    // this error will be propogated up to the top level of the call stack, where `self` is
-                    // now representing the function call of the user's input
-                    return Err(Error::new(vec![], StackOverflow));
+                // too many arguments were given
+                if positional > header.params.len() {
+                    return Err(Error::new(self.outer_span().to_vec(), TooManyArguments {
+                        name: self.name.name.clone(),
+                        expected: header.params.len(),
+                        given: self.args.len(),
+                        signature: header.to_string(),
+                    }));
                 }
 
-                let mut index = 0;
-                let mut args = self.args.iter();
-                let mut params = header.params.iter();
-                loop {
-                    match (args.next(), params.next()) {
-                        // a positional argument
-                        // evaluate it and add it to the context for use in the function body
-                        (Some(arg), Some(param)) => {
-                            let value = eval_break!(arg, &mut ctxt);
-                            ctxt.add_var(&param.symbol().name, value);
-                        },
-
-                        // too many arguments were given
-                        (Some(_), None) => return Err(Error::new(self.outer_span().to_vec(), TooManyArguments {
+                // match named arguments to the parameters with the same name, ensuring that no
+                // parameter is given more than one argument
+                let mut named = vec![None; header.params.len()];
+                for arg in &self.args[positional..] {
+                    let Some(arg_name) = arg.name() else {
+                        continue;
+                    };
+                    let index = header.params.iter()
+                        .position(|param| param.symbol().name == arg_name.name)
+                        .ok_or_else(|| Error::new(vec![arg_name.span.clone()], UnknownNamedArgument {
                             name: self.name.name.clone(),
-                            expected: header.params.len(),
-                            given: self.args.len(),
+                            arg: arg_name.name.clone(),
                             signature: header.to_string(),
-                        })),
-
-                        // no argument was given for this parameter
-                        // use the default value if there is one
-                        (None, Some(param)) => {
-                            // if there is no default, that's an error
-                            let value = match param {
-                                Param::Symbol(_) => return Err(Error::new(
-                                    self.outer_span().to_vec(),
-                                    MissingArgument {
-                                        name: self.name.name.clone(),
-                                        index,
-                                        expected: header.params.len(),
-                                        given: self.args.len(),
-                                        signature: header.to_string(),
-                                    },
-                                )),
-                                Param::Default(_, expr) => expr.eval(&mut ctxt),
-                            }?;
-                            ctxt.add_var(&param.symbol().name, value);
-                        },
-
-                        // begin evaluation
-                        (None, None) => break,
+                        }))?;
+                    if index < positional || named[index].is_some() {
+                        return Err(Error::new(vec![arg.span()], DuplicateArgument {
+                            name: self.name.name.clone(),
+                            arg: arg_name.name.clone(),
+                            signature: header.to_string(),
+                        }));
                     }
+                    named[index] = Some(arg.value());
+                }
 
-                    index += 1;
+                // evaluate the given arguments before binding any parameter, so that an argument
+                // refers to the variables of the caller, not to a parameter bound by an earlier
+                // argument
+                let mut args = Vec::with_capacity(header.params.len());
+                for (index, named) in named.into_iter().enumerate() {
+                    let arg = if index < positional {
+                        Some(self.args[index].value())
+                    } else {
+                        named
+                    };
+                    args.push(match arg {
+                        Some(arg) => Some(eval_break!(arg, ctxt)),
+                        None => None,
+                    });
                 }
 
+                let missing = |index| Error::new(self.outer_span().to_vec(), MissingArgument {
+                    name: self.name.name.clone(),
+                    index,
+                    expected: header.params.len(),
+                    given: self.args.len(),
+                    signature: header.to_string(),
+                });
+                let values = bind_args(user_func, ctxt, args, bind_names, missing)?;
+
                 let result = if self.derivatives == 0 {
-                    // no eval_break!; cannot break out of loops from within a function
-                    body.eval(&mut ctxt)
+                    eval_body(user_func, ctxt, values)
                 } else {
                     if header.params.len() != 1 {
                         return Err(Error::new(self.outer_span().to_vec(), InvalidDerivativeArguments {
                             name: self.name.name.clone(),
                         }));
                     }
-                    let initial = ctxt.get_var(&header.params[0].symbol().name).unwrap();
-                    compute_derivative(self, func, &mut ctxt, initial)
+                    let initial = values.into_iter().next().unwrap();
+                    compute_derivative(self, func, ctxt, initial)
                 };
-                if *recursive {
+                if user_func.recursive {
                     ctxt.stack_depth -= 1;
                 }
 
@@ -0,0 +1,29 @@
+use cas_math::unit_conversion::Quantity;
+use cas_parser::parser::ast::conversion::Conversion;
+use crate::numerical::{
+    ctxt::Ctxt,
+    error::{kind::{InvalidConversion, UnknownUnit}, Error},
+    eval::Eval,
+    value::Value,
+};
+
+impl Eval for Conversion {
+    fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
+        let value = self.value.eval(ctxt)?;
+        let target = Quantity::try_from(self.target.name.as_str())
+            .map_err(|_| Error::new(vec![self.target.span.clone()], UnknownUnit {
+                name: self.target.name.clone(),
+            }))?;
+        let invalid_conversion = |from: String| Error::new(
+            vec![self.value.span(), self.target.span.clone()],
+            InvalidConversion { from, to: self.target.name.clone() },
+        );
+
+        match value {
+            Value::Measurement(measurement) => measurement.convert_absolute_exact(target)
+                .map(Value::Measurement)
+                .map_err(|_| invalid_conversion(measurement.unit().to_string())),
+            value => Err(invalid_conversion(value.typename().to_string())),
+        }
+    }
+}
@@ -1,6 +1,15 @@
 use cas_error::ErrorKind;
 use cas_parser::parser::ast::{assign::Assign, binary::Binary};
-use crate::numerical::error::{kind::{BitshiftOverflow, InvalidBinaryOperation}, Error};
+use crate::numerical::error::{
+    kind::{
+        DivisionByZero,
+        InvalidBinaryOperation,
+        NonFiniteInteger,
+        ShiftTooLarge,
+        UnorderedComparison,
+    },
+    Error,
+};
 use std::ops::Range;
 
 /// Trait implemented on [`Binary`] and [`Assign`] to extract the spans of the operands and the
@@ -29,7 +38,16 @@ pub enum EvalError {
     InvalidBinaryOperation(InvalidBinaryOperation),
 
     /// Attempted to bitshift by a value that is too large.
-    BitshiftOverflow(BitshiftOverflow),
+    ShiftTooLarge(ShiftTooLarge),
+
+    /// Attempted to apply a bitwise operator to a non-finite value.
+    NonFiniteInteger(NonFiniteInteger),
+
+    /// Attempted to perform integer division by zero.
+    DivisionByZero(DivisionByZero),
+
+    /// Attempted to compare the order of values that have no natural ordering.
+    UnorderedComparison(UnorderedComparison),
 }
 
 impl From<InvalidBinaryOperation> for EvalError {
@@ -38,9 +56,27 @@ impl From<InvalidBinaryOperation> for EvalError {
     }
 }
 
-impl From<BitshiftOverflow> for EvalError {
-    fn from(e: BitshiftOverflow) -> Self {
-        EvalError::BitshiftOverflow(e)
+impl From<ShiftTooLarge> for EvalError {
+    fn from(e: ShiftTooLarge) -> Self {
+        EvalError::ShiftTooLarge(e)
+    }
+}
+
+impl From<NonFiniteInteger> for EvalError {
+    fn from(e: NonFiniteInteger) -> Self {
+        EvalError::NonFiniteInteger(e)
+    }
+}
+
+impl From<DivisionByZero> for EvalError {
+    fn from(e: DivisionByZero) -> Self {
+        EvalError::DivisionByZero(e)
+    }
+}
+
+impl From<UnorderedComparison> for EvalError {
+    fn from(e: UnorderedComparison) -> Self {
+        EvalError::UnorderedComparison(e)
     }
 }
 
@@ -53,7 +89,19 @@ impl EvalError {
                 spans,
                 kind: Box::new(e) as Box<dyn ErrorKind>,
             },
-            EvalError::BitshiftOverflow(e) => Error {
+            EvalError::ShiftTooLarge(e) => Error {
+                spans,
+                kind: Box::new(e) as Box<dyn ErrorKind>,
+            },
+            EvalError::NonFiniteInteger(e) => Error {
+                spans,
+                kind: Box::new(e) as Box<dyn ErrorKind>,
+            },
+            EvalError::DivisionByZero(e) => Error {
+                spans,
+                kind: Box::new(e) as Box<dyn ErrorKind>,
+            },
+            EvalError::UnorderedComparison(e) => Error {
                 spans,
                 kind: Box::new(e) as Box<dyn ErrorKind>,
             },
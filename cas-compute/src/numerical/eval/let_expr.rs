@@ -0,0 +1,17 @@
+use cas_parser::parser::ast::let_expr::Let;
+use crate::eval_break;
+use crate::numerical::{ctxt::Ctxt, error::Error, eval::Eval, value::Value};
+
+impl Eval for Let {
+    fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
+        let value = eval_break!(self.value, ctxt);
+
+        // the binding is local to the body, so it is made in a child of the context that is
+        // discarded afterwards
+        let mut scope = ctxt.child();
+        scope.add_var(&self.name.name, value);
+        let result = self.body.eval(&mut scope);
+        ctxt.break_loop = scope.break_loop;
+        result
+    }
+}
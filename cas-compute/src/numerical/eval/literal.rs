@@ -24,6 +24,16 @@ impl Eval for Literal {
                 }
                 Ok(Value::List(values))
             },
+            Literal::Tuple(tuple) => {
+                let mut values = Vec::with_capacity(tuple.values.len());
+                for value in &tuple.values {
+                    values.push(value.eval(ctxt)?);
+                }
+                Ok(Value::Tuple(values))
+            },
+            Literal::Slot(slot) => ctxt.slots.get(slot.index)
+                .cloned()
+                .ok_or_else(|| Error::new(vec![slot.span.clone()], UndefinedVariable { name: slot.name.clone() })),
         }
     }
 }
@@ -12,11 +12,15 @@ trait ConditionalLoop {
         while self.condition(ctxt)? {
             let value = match self.body() {
                 Expr::Literal(literal) => literal.eval(ctxt),
+                Expr::Measurement(measurement) => measurement.eval(ctxt),
+                Expr::Conversion(conversion) => conversion.eval(ctxt),
                 Expr::Paren(paren) => paren.expr.eval(ctxt),
                 Expr::Block(block) => block.eval(ctxt),
+                Expr::Piecewise(piecewise) => piecewise.eval(ctxt),
                 Expr::If(if_expr) => if_expr.eval(ctxt),
                 Expr::Loop(loop_expr) => loop_expr.eval(ctxt),
                 Expr::While(while_expr) => while_expr.eval(ctxt),
+                Expr::Let(let_expr) => let_expr.eval(ctxt),
                 Expr::Break(break_expr) => break_expr.eval(ctxt),
                 Expr::Continue(_) => continue,
                 Expr::Call(call) => call.eval(ctxt),
@@ -0,0 +1,22 @@
+use cas_math::unit_conversion::{Measurement as UnitMeasurement, Quantity};
+use cas_parser::parser::ast::measurement::Measurement;
+use crate::numerical::{
+    ctxt::Ctxt,
+    error::{kind::UnknownUnit, Error},
+    eval::Eval,
+    value::Value,
+};
+
+impl Eval for Measurement {
+    fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
+        // the value is always an integer or float literal
+        let Value::Float(value) = self.value.eval(ctxt)?.coerce_float() else {
+            unreachable!()
+        };
+        let quantity = Quantity::try_from(self.unit.name.as_str())
+            .map_err(|_| Error::new(vec![self.unit.span.clone()], UnknownUnit {
+                name: self.unit.name.clone(),
+            }))?;
+        Ok(Value::Measurement(UnitMeasurement::new(value, quantity)))
+    }
+}
@@ -1,15 +1,20 @@
 mod assign;
-mod binary;
+pub(crate) mod binary;
 mod block;
 mod break_expr;
-mod call;
+pub(crate) mod call;
 mod continue_expr;
+mod conversion;
 mod error;
 mod expr;
 mod if_expr;
+mod let_expr;
 mod literal;
 mod loops;
+mod measurement;
+mod piecewise;
 mod primary;
+mod program;
 mod stmt;
 mod unary;
 
@@ -33,14 +38,15 @@ pub trait Eval {
 /// Eval tests depend on the parser, so ensure that parser tests pass before running these.
 #[cfg(test)]
 mod tests {
+    use cas_math::unit_conversion::{Length, Measurement, Quantity, Unit};
     use crate::consts;
     use crate::funcs::miscellaneous::{Abs, Factorial};
-    use crate::numerical::builtin::Builtin;
-    use crate::primitive::float;
+    use crate::numerical::{builtin::Builtin, ctxt::{Func, NonFinitePolicy, TrigMode}, error::kind::{DestructureMismatch, InvalidConversion, NoMatchingBranch, NotCallable, UnorderedComparison}, value::NonFinite};
+    use crate::primitive::{complex, float, int};
     use rug::ops::Pow;
     use super::*;
 
-    use cas_parser::parser::{ast::expr::Expr, Parser};
+    use cas_parser::parser::{ast::{expr::Expr, program::Program}, token::op::BinOpKind, ParseOptions, Parser};
 
     #[test]
     fn binary_expr() {
@@ -56,6 +62,231 @@ mod tests {
         assert_eq!(expr.eval_default().unwrap(), 7.into());
     }
 
+    #[test]
+    fn binary_mod_word() {
+        let mut parser = Parser::new("7 mod 3");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 1.into());
+    }
+
+    #[test]
+    fn superscript_exponent() {
+        let mut parser = Parser::new("2²");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 4.into());
+
+        let mut parser = Parser::new("2³");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 8.into());
+    }
+
+    #[test]
+    fn radix_literal_exact() {
+        // radix literals evaluate to arbitrary-precision integers, so there is no loss of
+        // precision past `2^53`
+        let source = format!("2'1{}1", "0".repeat(63));
+        let mut parser = Parser::new(&source);
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(2).pow(64u32) + 1));
+    }
+
+    #[test]
+    fn binary_exp_double_star() {
+        let mut parser = Parser::new("2 ** 3 ** 2");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 512.into());
+    }
+
+    #[test]
+    fn binary_int_div() {
+        let mut parser = Parser::new("7 div 2");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 3.into());
+    }
+
+    #[test]
+    fn binary_int_div_floored() {
+        let mut parser = Parser::new("-7 div 2");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), (-4).into());
+    }
+
+    #[test]
+    fn binary_int_div_by_zero() {
+        let mut parser = Parser::new("7 div 0");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+    }
+
+    #[test]
+    fn bitshift_too_large() {
+        let mut parser = Parser::new("1 << 1024");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(1) << 1024u32));
+
+        for source in ["1 << 1025", "1 >> 100000", "1.5 << 2000"] {
+            let mut parser = Parser::new(source);
+            let expr = parser.try_parse_full::<Expr>().unwrap();
+            assert!(expr.eval_default().is_err());
+        }
+    }
+
+    #[test]
+    fn bitwise_non_finite() {
+        let mut parser = Parser::new("(1 / 0) | 1");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let mut ctxt = Ctxt::default();
+        ctxt.non_finite_policy = NonFinitePolicy::Allow;
+        assert!(expr.eval(&mut ctxt).is_err());
+    }
+
+    #[test]
+    fn bool_literal() {
+        let mut parser = Parser::new("not false");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), true.into());
+
+        let mut parser = Parser::new("true && false");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), false.into());
+    }
+
+    #[test]
+    fn percent_of() {
+        let mut parser = Parser::new("20% of 50");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().approx_eq(&Value::Float(float(10))));
+    }
+
+    #[test]
+    fn measurement() {
+        let mut parser = Parser::with_options("5 km", ParseOptions {
+            unit_names: Some(Quantity::is_abbreviation),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(
+            expr.eval_default().unwrap(),
+            Value::Measurement(Measurement::new(float(5), Length::Kilometer)),
+        );
+    }
+
+    #[test]
+    fn measurement_conversion() {
+        let mut parser = Parser::with_options("(2 mi) to dm", ParseOptions {
+            unit_names: Some(Quantity::is_abbreviation),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Value::Measurement(m) = expr.eval_default().unwrap() else {
+            panic!("expected a measurement");
+        };
+        assert_eq!(*m.unit(), Unit::new(Length::Decimeter));
+        assert!((m.value().to_f64() - 32186.88).abs() < 1e-9);
+    }
+
+    #[test]
+    fn measurement_invalid_conversion() {
+        let mut parser = Parser::with_options("2 mi to hr", ParseOptions {
+            unit_names: Some(Quantity::is_abbreviation),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let err = expr.eval_default().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err.kind),
+            format!("{:?}", InvalidConversion { from: "mi".to_string(), to: "hr".to_string() }),
+        );
+    }
+
+    #[test]
+    fn complex_comparison() {
+        let mut parser = Parser::new("(1i) < (2i)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let err = expr.eval_default().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err.kind),
+            format!("{:?}", UnorderedComparison { op: BinOpKind::Less, typename: "Complex" }),
+        );
+
+        let mut parser = Parser::new("(1i) == (1i)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), true.into());
+    }
+
+    #[test]
+    fn round_to() {
+        let mut parser = Parser::new("round_to(3.14159, 2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().coerce_float().approx_eq(&Value::Float(float(314) / 100)));
+
+        let mut parser = Parser::new("round_to(12345, -2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(12300)));
+
+        // halfway cases are rounded the same way as `round`
+        let mut parser = Parser::new("round_to(0.125, 2) == round(12.5) / 100");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), true.into());
+
+        let mut parser = Parser::new("round_to(-25, -1) == round(-2.5) * 10");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), true.into());
+
+        // places beyond the precision of the number
+        let mut parser = Parser::new("round_to(1.5, 10^20)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(1.5)));
+
+        let mut parser = Parser::new("round_to(12345, -10^20)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(0)));
+    }
+
+    #[test]
+    fn between() {
+        let mut parser = Parser::new("between(5, 1, 10)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), true.into());
+
+        let mut parser = Parser::new("between(11, 1, 10)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), false.into());
+
+        let mut parser = Parser::new("between(10, 1, 10, false)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), false.into());
+    }
+
+    #[test]
+    fn let_binding() {
+        let mut ctxt = Ctxt::default();
+        let mut parser = Parser::new("let x = 3 in x * x");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 9.into());
+
+        // the binding does not leak outside of the body
+        assert_eq!(ctxt.get_var("x"), None);
+        let mut parser = Parser::new("x");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).is_err());
+    }
+
+    #[test]
+    fn unary_plus() {
+        let mut parser = Parser::new("+3");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 3.into());
+
+        let mut parser = Parser::new("+(2 + 3i)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Complex(complex((2, 3))));
+
+        let mut parser = Parser::new("+true");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+    }
+
     #[test]
     fn binary_and_unary() {
         let mut parser = Parser::new("3 * -5 / 5! + 6");
@@ -81,6 +312,70 @@ mod tests {
         assert!(val1.approx_eq(&val2));
     }
 
+    #[test]
+    fn complex_exp() {
+        // principal value of i^i = e^(-pi / 2)
+        let mut parser = Parser::new("i^i");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let val1 = expr.eval_default().unwrap();
+        let val2 = complex((0.20788, 0)).into();
+        assert!(val1.approx_eq(&val2));
+
+        let mut parser = Parser::new("(1+1i)^2");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let val1 = expr.eval_default().unwrap();
+        let val2 = complex((0, 2)).into();
+        assert!(val1.approx_eq(&val2));
+    }
+
+    #[test]
+    fn non_finite_policy() {
+        let mut ctxt = Ctxt::default();
+        let mut parser = Parser::new("log(0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        // infinite results are allowed by default
+        let val = expr.eval(&mut ctxt).unwrap();
+        assert!(val.is_infinite());
+
+        ctxt.non_finite_policy = NonFinitePolicy::Error;
+        assert!(expr.eval(&mut ctxt).is_err());
+
+        ctxt.non_finite_policy = NonFinitePolicy::Allow;
+        assert_eq!(
+            expr.eval(&mut ctxt).unwrap().coerce_float(),
+            Value::Float(float(rug::float::Special::NegInfinity)),
+        );
+
+        ctxt.non_finite_policy = NonFinitePolicy::Sentinel;
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::NonFinite(NonFinite::NegInfinity));
+
+        // the sentinel propagates through operators
+        let mut parser = Parser::new("2 * log(0) + 1");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::NonFinite(NonFinite::NegInfinity));
+    }
+
+    #[test]
+    fn special_float_literal() {
+        let mut ctxt = Ctxt::default();
+        let mut parser = Parser::new("inf");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(
+            expr.eval(&mut ctxt).unwrap().coerce_float(),
+            Value::Float(float(rug::float::Special::Infinity)),
+        );
+
+        let mut parser = Parser::new("nan");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        // NaN results are rejected by default
+        assert!(expr.eval(&mut ctxt).is_err());
+
+        ctxt.non_finite_policy = NonFinitePolicy::Allow;
+        assert!(expr.eval(&mut ctxt).unwrap().is_nan());
+    }
+
     #[test]
     fn precision() {
         let mut parser = Parser::new("e^2 - tau");
@@ -88,6 +383,161 @@ mod tests {
         assert_eq!(expr.eval_default().unwrap(), Value::Float(consts::E.clone().pow(2) - &*consts::TAU));
     }
 
+    #[test]
+    fn physical_constant() {
+        let mut parser = Parser::new("c");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Float(float(299792458)));
+
+        let mut parser = Parser::new("c = 1; c");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert_eq!(program.eval_default().unwrap(), Value::Integer(int(1)));
+    }
+
+    #[test]
+    fn degree_mode_trig() {
+        let mut ctxt = Ctxt::default();
+        ctxt.trig_mode = TrigMode::Degrees;
+
+        let mut parser = Parser::new("sin(90)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).unwrap().coerce_float().approx_eq(&Value::Float(float(1))));
+
+        let mut parser = Parser::new("atan(1)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).unwrap().coerce_float().approx_eq(&Value::Float(float(45))));
+    }
+
+    #[test]
+    fn hyperbolic_trig() {
+        let mut parser = Parser::new("sinh(0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(0)));
+
+        let mut parser = Parser::new("cosh(0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(1)));
+
+        let mut parser = Parser::new("tanh(20)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().coerce_float().approx_eq(&Value::Float(float(1))));
+
+        // like `sqrt`, `acosh` of a real value below 1 returns a complex value
+        let mut parser = Parser::new("acosh(0.5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Value::Complex(c) = expr.eval_default().unwrap() else {
+            panic!("expected a complex value");
+        };
+        assert!(!c.imag().is_zero());
+    }
+
+    #[test]
+    fn inverse_trig_out_of_domain() {
+        let mut parser = Parser::new("asin(2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let acosh_2 = float(2).acosh();
+        let expected = complex((float(&*consts::PI) / 2, -acosh_2.clone()));
+        assert!(expr.eval_default().unwrap().approx_eq(&Value::Complex(expected)));
+
+        let mut parser = Parser::new("acos(2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().approx_eq(&Value::Complex(complex((0, acosh_2)))));
+
+        // inside the domain, the result is real
+        let mut parser = Parser::new("asin(0.5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let value = expr.eval_default().unwrap().coerce_float();
+        assert!(value.approx_eq(&Value::Float(float(&*consts::PI) / 6)));
+    }
+
+    #[test]
+    fn nth_roots() {
+        let mut parser = Parser::new("nthroot(8, 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(2)));
+
+        let mut parser = Parser::new("allroots(1, 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Value::List(roots) = expr.eval_default().unwrap() else {
+            panic!("expected a list");
+        };
+        let half_sqrt3 = 3f64.sqrt() / 2.0;
+        let expected = [(1.0, 0.0), (-0.5, half_sqrt3), (-0.5, -half_sqrt3)];
+        assert_eq!(roots.len(), expected.len());
+        for (root, (re, im)) in roots.into_iter().zip(expected) {
+            let Value::Complex(c) = root.coerce_complex() else {
+                panic!("expected a complex value");
+            };
+            assert!((c.real().to_f64() - re).abs() < 1e-9);
+            assert!((c.imag().to_f64() - im).abs() < 1e-9);
+        }
+
+        let mut parser = Parser::new("nthroot(8, 0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+    }
+
+    #[test]
+    fn complex_factorial() {
+        // the factorial of a complex number is `gamma(z + 1)`
+        let mut parser = Parser::new("(2+3i)!");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Value::Complex(c) = expr.eval_default().unwrap() else {
+            panic!("expected a complex value");
+        };
+        assert!((c.real().to_f64() - -0.440113407637002).abs() < 1e-9);
+        assert!((c.imag().to_f64() - -0.063637243126317).abs() < 1e-9);
+    }
+
+    #[test]
+    fn clamp_sign_copysign() {
+        let mut parser = Parser::new("clamp(5, 0, 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Float(float(3)));
+
+        let mut parser = Parser::new("clamp(5, 3, 0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+
+        let mut parser = Parser::new("sign(-2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Float(float(-1)));
+
+        let mut parser = Parser::new("copysign(3, -1)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Float(float(-3)));
+    }
+
+    #[test]
+    fn binomial_and_permutation() {
+        let mut parser = Parser::new("binom(5, 2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(10)));
+
+        let mut parser = Parser::new("binom(5, 6)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(0)));
+
+        let mut parser = Parser::new("perm(5, 2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(20)));
+    }
+
+    #[test]
+    fn double_factorial() {
+        let mut parser = Parser::new("doublefact(5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(15)));
+
+        let mut parser = Parser::new("doublefact(6)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Integer(int(48)));
+
+        let mut parser = Parser::new("doublefact(10^10)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+    }
+
     #[test]
     fn precision_2() {
         let mut parser = Parser::new("pi^2 * 17! / -4.9 + e");
@@ -103,6 +553,13 @@ mod tests {
         assert!(val1.approx_eq(&val2));
     }
 
+    #[test]
+    fn program() {
+        let mut parser = Parser::new("f(x) = x^2; f(3)");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert_eq!(program.eval_default().unwrap(), 9.into());
+    }
+
     #[test]
     fn func_call() {
         let mut ctxt = Ctxt::default();
@@ -118,6 +575,230 @@ mod tests {
         assert_eq!(expr.eval(&mut ctxt).unwrap(), 90.into());
     }
 
+    #[test]
+    fn piecewise_func() {
+        let mut ctxt = Ctxt::default();
+
+        let mut parser = Parser::new("f(x) = { x^2 if x > 0; -x^2 otherwise }");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::Unit);
+
+        let cases = [("f(3)", 9), ("f(-3)", -9), ("f(0)", 0)];
+        for (source, expected) in cases {
+            let mut parser = Parser::new(source);
+            let expr = parser.try_parse_full::<Expr>().unwrap();
+            assert_eq!(expr.eval(&mut ctxt).unwrap(), expected.into());
+        }
+    }
+
+    #[test]
+    fn piecewise_no_matching_branch() {
+        let mut parser = Parser::new("{ 1 if false; 2 if 1 > 2 }");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let err = expr.eval_default().unwrap_err();
+        assert_eq!(format!("{:?}", err.kind), format!("{:?}", NoMatchingBranch));
+    }
+
+    #[test]
+    fn ctxt_snapshot_restore() {
+        let mut ctxt = Ctxt::default();
+
+        let mut parser = Parser::new("x = 2; f(n) = n + 1");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        program.eval(&mut ctxt).unwrap();
+
+        let snapshot = ctxt.snapshot();
+
+        // redefine both, and add a new variable
+        let mut parser = Parser::new("x = 5; f(n) = n * 10; y = 3");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        program.eval(&mut ctxt).unwrap();
+        assert_eq!(ctxt.get_var("x"), Some(5.into()));
+
+        ctxt.restore(snapshot);
+        assert_eq!(ctxt.get_var("x"), Some(2.into()));
+        assert_eq!(ctxt.get_var("y"), None);
+
+        let mut parser = Parser::new("f(x)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 3.into());
+    }
+
+    #[test]
+    fn ctxt_child() {
+        let mut parent = Ctxt::default();
+        parent.add_var("x", 2.into());
+
+        let mut scope = parent.child();
+        assert_eq!(scope.get_var("x"), Some(2.into()));
+        assert!(scope.get_var("pi").is_some());
+        assert!(scope.get_vars().is_empty());
+
+        let mut parser = Parser::new("y = x + 1; x = 10");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        program.eval(&mut scope).unwrap();
+        assert_eq!(scope.get_var("x"), Some(10.into()));
+        assert_eq!(scope.get_var("y"), Some(3.into()));
+
+        // neither the shadowed nor the new variable leaks into the parent
+        assert_eq!(parent.get_var("x"), Some(2.into()));
+        assert_eq!(parent.get_var("y"), None);
+
+        // lookups fall through every enclosing scope
+        let mut grandchild = scope.child();
+        grandchild.add_var("z", 4.into());
+        assert_eq!(grandchild.get_var("x"), Some(10.into()));
+        assert_eq!(grandchild.get_var("y"), Some(3.into()));
+        assert_eq!(grandchild.get_var("z"), Some(4.into()));
+        assert_eq!(scope.get_var("z"), None);
+    }
+
+    #[test]
+    fn call_non_function() {
+        let mut ctxt = Ctxt::default();
+        ctxt.add_var("x", 2.into());
+
+        let mut parser = Parser::with_options("x(3)", ParseOptions {
+            implicit_multiplication: false,
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let err = expr.eval(&mut ctxt).unwrap_err();
+        assert_eq!(
+            format!("{:?}", err.kind),
+            format!("{:?}", NotCallable { name: "x".to_string(), typename: "Integer" }),
+        );
+    }
+
+    #[test]
+    fn tuple_literal() {
+        let mut parser = Parser::new("(1, 2.5, true)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(
+            expr.eval_default().unwrap(),
+            Value::Tuple(vec![1.into(), 2.5.into(), true.into()]),
+        );
+    }
+
+    #[test]
+    fn divmod_destructure() {
+        let mut parser = Parser::new("divmod(-7, 2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Tuple(vec![(-4).into(), 1.into()]));
+
+        let mut parser = Parser::new("divmod(7, 0)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+
+        let mut ctxt = Ctxt::default();
+        let mut parser = Parser::new("q, r = divmod(7, 2); q * 10 + r");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert_eq!(program.eval(&mut ctxt).unwrap(), 31.into());
+        assert_eq!(ctxt.get_var("q"), Some(3.into()));
+        assert_eq!(ctxt.get_var("r"), Some(1.into()));
+    }
+
+    #[test]
+    fn chained_assignment() {
+        let mut ctxt = Ctxt::default();
+        let mut parser = Parser::new("a = b = c = 5");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 5.into());
+
+        // every target is bound, not just the outermost one
+        for name in ["a", "b", "c"] {
+            assert_eq!(ctxt.get_var(name), Some(5.into()));
+        }
+    }
+
+    #[test]
+    fn destructure_arity_mismatch() {
+        let cases = [
+            ("a, b, c = (1, 2)", DestructureMismatch { expected: 3, given: Some(2), typename: "Tuple" }),
+            ("a, b = 5", DestructureMismatch { expected: 2, given: None, typename: "Integer" }),
+        ];
+        for (source, expected) in cases {
+            let mut parser = Parser::new(source);
+            let expr = parser.try_parse_full::<Expr>().unwrap();
+            let err = expr.eval_default().unwrap_err();
+            assert_eq!(format!("{:?}", err.kind), format!("{:?}", expected));
+        }
+    }
+
+    #[test]
+    fn func_call_named_args() {
+        let mut ctxt = Ctxt::default();
+
+        // assign function
+        let mut parser = Parser::new("f(x, y) = x - 2y");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::Unit);
+
+        // call function with named arguments in reverse order
+        let mut parser = Parser::new("f(y: 3, x: 10)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 4.into());
+
+        // mix positional and named arguments
+        let mut parser = Parser::new("f(10, y: 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 4.into());
+    }
+
+    #[test]
+    fn func_call_bad_named_args() {
+        let mut ctxt = Ctxt::default();
+
+        let mut parser = Parser::new("f(x, y) = x - 2y");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::Unit);
+
+        // unknown parameter name
+        let mut parser = Parser::new("f(1, z: 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).is_err());
+
+        // parameter given twice
+        let mut parser = Parser::new("f(1, x: 3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).is_err());
+    }
+
+    #[test]
+    fn repeated_func_call() {
+        let mut parser = Parser::new("f(x) = x + 1; n = 0; s = 0; while n < 1000 then { s += f(n); n += 1 }; s");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert_eq!(program.eval_default().unwrap(), 500500.into());
+    }
+
+    #[test]
+    fn default_params() {
+        let mut ctxt = Ctxt::default();
+
+        let mut parser = Parser::new("f(x, y = 1) = x + y");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::Unit);
+
+        // omitted
+        let mut parser = Parser::new("f(3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 4.into());
+
+        // provided
+        let mut parser = Parser::new("f(3, 5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 8.into());
+
+        // only the second argument, by name
+        let mut parser = Parser::new("g(n = 3, k = 6) = n * k");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), Value::Unit);
+
+        let mut parser = Parser::new("g(k: 4)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval(&mut ctxt).unwrap(), 12.into());
+    }
+
     #[test]
     fn complicated_func_call() {
         let mut ctxt = Ctxt::default();
@@ -130,15 +811,19 @@ mod tests {
         // call function
         let tries = [
             (None, None, 18),
-            // (None, Some(4.0), 12.0), // TODO: there is currently no way to pass only the second argument
+            (None, Some(4), 12),
             (Some(9), None, 54),
             (Some(8), Some(14), 112),
         ];
         for (n, k, expected_result) in tries {
+            // the second argument is passed by name if the first is omitted
             let source = format!(
                 "f({}{})",
                 n.map_or("".to_string(), |n| n.to_string()),
-                k.map_or("".to_string(), |k| format!(", {}", k)),
+                k.map_or("".to_string(), |k| match n {
+                    Some(_) => format!(", {}", k),
+                    None => format!("k: {}", k),
+                }),
             );
             let mut parser = Parser::new(&source);
             let expr = parser.try_parse_full::<Expr>().unwrap();
@@ -151,9 +836,234 @@ mod tests {
         }
     }
 
+    #[test]
+    fn variadic_builtin() {
+        let mut parser = Parser::new("max(3, 1, 4, 1, 5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 5.0.into());
+
+        let mut parser = Parser::new("gcf(12, 18, 27)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), 3.into());
+
+        let mut parser = Parser::new("max()");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().is_err());
+    }
+
+    #[test]
+    fn min_max_complex() {
+        let mut parser = Parser::new("max(3, 4i, -5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let err = expr.eval_default().unwrap_err();
+        assert_eq!(err.spans[2], 7..9);
+
+        let mut ctxt = Ctxt::default();
+        ctxt.order_complex_by_magnitude = true;
+        assert_eq!(expr.eval(&mut ctxt).unwrap().coerce_float(), Value::Float(float(-5)));
+
+        let mut parser = Parser::new("min(3, 2i, -5)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).unwrap().approx_eq(&Value::Complex(complex((0, 2)))));
+    }
+
+    #[test]
+    fn mixed_real_complex() {
+        let mut parser = Parser::new("3 + 2i");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().approx_eq(&Value::Complex(complex((3, 2)))));
+
+        let mut parser = Parser::new("(2 + 0i) + 1");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap().coerce_float(), Value::Float(float(3)));
+
+        let mut parser = Parser::new("2 * (1 + 1i)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval_default().unwrap().approx_eq(&Value::Complex(complex((2, 2)))));
+
+        let mut parser = Parser::new("(1 + i) * (1 - i)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.eval_default().unwrap(), Value::Float(float(2)));
+    }
+
+    #[test]
+    fn common_subexpressions() {
+        use crate::numerical::{builtin::error::BuiltinError, cse::eval_cse};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        /// `sin`, counting the number of times it is called.
+        #[derive(Debug)]
+        struct CountingSin;
+
+        impl Builtin for CountingSin {
+            fn num_args(&self) -> usize {
+                1
+            }
+
+            fn variadic(&self) -> bool {
+                false
+            }
+
+            fn eval(&self, _: &Ctxt, args: &mut dyn Iterator<Item = Value>) -> Result<Value, BuiltinError> {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                let Value::Float(x) = args.next().unwrap().coerce_float() else {
+                    unreachable!()
+                };
+                Ok(Value::Float(x.sin()))
+            }
+        }
+
+        let mut ctxt = Ctxt::default();
+        ctxt.add_builtin("sin", CountingSin);
+        ctxt.add_var("x", Value::from(0.5));
+
+        let mut parser = Parser::new("sin(x)^2 + sin(x)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let vars = ctxt.get_vars().len();
+        let value = eval_cse(&expr, &mut ctxt).unwrap();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        assert_eq!(value, expr.eval(&mut ctxt).unwrap());
+        assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+
+        // the shared value is not visible outside of the expression
+        assert_eq!(ctxt.get_vars().len(), vars);
+        assert!(ctxt.slots.is_empty());
+    }
+
+    #[test]
+    fn eval_sweep() {
+        let mut parser = Parser::new("f(x) = x^2; g(x) = { x^2 }; h(x, y = 2) = x * y; s(x, y) = x + y; t() = 1");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        let mut ctxt = Ctxt::default();
+        program.eval(&mut ctxt).unwrap();
+
+        // `f` is resolved to slots, while `g` binds its parameter by name
+        let expected = [0, 1, 4, 9].map(|n| Value::Float(float(n)));
+        for func in ["f", "g"] {
+            let values = ctxt.eval_sweep(func, &[0.0, 1.0, 2.0, 3.0]).unwrap();
+            assert_eq!(values.into_iter().map(Value::coerce_float).collect::<Vec<_>>(), expected);
+        }
+
+        // other parameters take their default values
+        let values = ctxt.eval_sweep("h", &[3.0]).unwrap();
+        assert_eq!(values[0].clone().coerce_float(), Value::Float(float(6)));
+
+        // calls are checked in the same way as a call in the source code
+        assert!(ctxt.eval_sweep("s", &[1.0]).is_err());
+        assert!(ctxt.eval_sweep("t", &[1.0]).is_err());
+
+        let values = ctxt.eval_sweep("abs", &[-2.0]).unwrap();
+        assert_eq!(values[0].clone().coerce_float(), Value::Float(float(2)));
+        assert!(ctxt.eval_sweep("undefined", &[1.0]).is_err());
+    }
+
+    #[test]
+    fn memoize_pure_funcs() {
+        let fib = "fib(n) = if n < 2 then n else fib(n - 1) + fib(n - 2);";
+        let eval_with = |source: &str, memoize: bool| {
+            let mut parser = Parser::new(source);
+            let program = parser.try_parse_full::<Program>().unwrap();
+            let mut ctxt = Ctxt::default();
+            ctxt.memoize_pure_funcs = memoize;
+            program.eval(&mut ctxt).unwrap()
+        };
+
+        let source = format!("{fib} fib(15)");
+        assert_eq!(eval_with(&source, false), eval_with(&source, true));
+
+        // exponential without memoization
+        assert_eq!(eval_with(&format!("{fib} fib(30)"), true), 832040.into());
+
+        // reads a variable outside of its scope, so must not be cached
+        assert_eq!(eval_with("a = 1; g(x) = x + a; g(1); a = 5; g(1)", true), 6.into());
+
+        // results cached under one setting must not be reused under another
+        let mut ctxt = Ctxt::default();
+        ctxt.memoize_pure_funcs = true;
+        ctxt.order_complex_by_magnitude = true;
+        let mut parser = Parser::new("f(x) = min(x, 2i); f(3)");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert!(program.eval(&mut ctxt).is_ok());
+
+        ctxt.order_complex_by_magnitude = false;
+        let mut parser = Parser::new("f(3)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(expr.eval(&mut ctxt).is_err());
+    }
+
+    #[test]
+    fn resolved_func_call() {
+        let eval = |source: &str| {
+            let mut parser = Parser::new(source);
+            let program = parser.try_parse_full::<Program>().unwrap();
+            let mut ctxt = Ctxt::default();
+            let result = program.eval(&mut ctxt).unwrap();
+            (result, ctxt)
+        };
+
+        let (result, ctxt) = eval("x = 10; f(x, y) = [x + y, if x > y then x else y]; f(3, y: 4)");
+        assert_eq!(result, Value::List(vec![7.into(), 4.into()]));
+        assert!(matches!(ctxt.get_func("f"), Some(Func::UserFunc(func)) if func.resolved.is_some()));
+        assert!(ctxt.slots.is_empty());
+
+        // the frame of the caller is restored after each call
+        let (result, _) = eval("sq(x) = x^2; f(x, y) = sq(y) + x; f(2, 3)");
+        assert_eq!(result, 11.into());
+
+        // parameters are still visible by name to called functions
+        let (result, _) = eval("g() = n * 2; f(n) = g() + n; f(5)");
+        assert_eq!(result, 15.into());
+
+        // arguments are evaluated with the slots of the caller
+        let (result, _) = eval("sq(x) = x^2; f(x, y) = sq(y) + sq(x + 1); f(1, 3)");
+        assert_eq!(result, 13.into());
+
+        let (result, _) = eval("fib(n) = if n < 2 then n else fib(n - 1) + fib(n - 2); fib(15)");
+        assert_eq!(result, 610.into());
+
+        // bodies that assign variables are not resolved
+        let (result, ctxt) = eval("f(x) = { x = x + 1; x * 2 }; f(3)");
+        assert_eq!(result, 8.into());
+        assert!(matches!(ctxt.get_func("f"), Some(Func::UserFunc(func)) if func.resolved.is_none()));
+
+        // arguments cannot see the parameters bound by earlier arguments, with or without slots
+        let (resolved, _) = eval("x = 10; f(x, y) = x + y; f(1, x)");
+        let (unresolved, _) = eval("x = 10; f(x, y) = { x + y }; f(1, x)");
+        assert_eq!(resolved, 11.into());
+        assert_eq!(unresolved, 11.into());
+
+        let (result, _) = eval("f(x, y = x + 1) = x * y; f(3)");
+        assert_eq!(result, 12.into());
+
+        // derivatives are computed from the unresolved body
+        let (resolved, _) = eval("f(x) = 3 * x^2; f'(2)");
+        let (unresolved, _) = eval("f(x) = { 3 * x^2 }; f'(2)");
+        assert_eq!(resolved, unresolved);
+    }
+
     #[test]
     fn builtin_func_arg_check() {
         assert_eq!(Abs.eval(&Ctxt::default(), &mut [Value::from(4.0)].into_iter()).unwrap().coerce_float(), 4.0.into());
         assert!(Abs.eval(&Ctxt::default(), &mut [Value::Unit].into_iter()).is_err());
     }
+
+    #[test]
+    fn typeof_builtin() {
+        let cases = [
+            ("typeof(3)", "number"),
+            ("typeof(3.5)", "number"),
+            ("typeof(3i)", "complex"),
+            ("typeof(true)", "boolean"),
+            ("typeof(typeof(1))", "string"),
+        ];
+
+        for (source, expected) in cases {
+            let mut parser = Parser::new(source);
+            let expr = parser.try_parse_full::<Expr>().unwrap();
+            assert_eq!(expr.eval_default().unwrap(), Value::String(expected.to_string()));
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use cas_parser::parser::ast::piecewise::Piecewise;
+use crate::eval_break;
+use crate::numerical::{
+    ctxt::Ctxt,
+    error::{kind::NoMatchingBranch, Error},
+    eval::Eval,
+    value::Value,
+};
+
+impl Eval for Piecewise {
+    fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
+        for branch in &self.branches {
+            let condition = eval_break!(branch.condition, ctxt);
+            if condition.is_truthy() {
+                return branch.value.eval(ctxt);
+            }
+        }
+
+        match &self.otherwise {
+            Some(otherwise) => otherwise.eval(ctxt),
+            None => Err(Error::new(vec![self.span()], NoMatchingBranch)),
+        }
+    }
+}
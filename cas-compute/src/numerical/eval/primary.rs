@@ -5,11 +5,14 @@ impl Eval for Primary {
     fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
         match self {
             Primary::Literal(literal) => literal.eval(ctxt),
+            Primary::Measurement(measurement) => measurement.eval(ctxt),
             Primary::Paren(paren) => paren.expr.eval(ctxt),
             Primary::Block(block) => block.eval(ctxt),
+            Primary::Piecewise(piecewise) => piecewise.eval(ctxt),
             Primary::If(if_expr) => if_expr.eval(ctxt),
             Primary::Loop(loop_expr) => loop_expr.eval(ctxt),
             Primary::While(while_expr) => while_expr.eval(ctxt),
+            Primary::Let(let_expr) => let_expr.eval(ctxt),
             Primary::Break(break_expr) => break_expr.eval(ctxt),
             Primary::Continue(continue_expr) => continue_expr.eval(ctxt),
             Primary::Call(call) => call.eval(ctxt),
@@ -0,0 +1,8 @@
+use cas_parser::parser::ast::program::Program;
+use crate::numerical::{ctxt::Ctxt, error::Error, eval::{eval_stmts, Eval}, value::Value};
+
+impl Eval for Program {
+    fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
+        eval_stmts(&self.stmts, ctxt)
+    }
+}
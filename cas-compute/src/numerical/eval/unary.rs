@@ -1,9 +1,9 @@
 use cas_parser::parser::{ast::unary::Unary, token::op::UnaryOpKind};
 use crate::eval_break;
-use crate::funcs::miscellaneous::Factorial;
+use crate::funcs::miscellaneous::{Factorial, Gamma};
 use crate::numerical::{
     ctxt::Ctxt,
-    error::{kind::InvalidUnaryOperation, Error},
+    error::{kind::{InvalidUnaryOperation, NonFiniteInteger}, Error},
     eval::Eval,
     value::Value,
 };
@@ -13,21 +13,33 @@ impl Eval for Unary {
     fn eval(&self, ctxt: &mut Ctxt) -> Result<Value, Error> {
         let operand = eval_break!(self.operand, ctxt).coerce_number();
         match operand {
+            // non-finite sentinels propagate through operators, like the numbers they stand for
+            Value::NonFinite(_) => Ok(operand),
             Value::Float(num) => Ok(match self.op.kind {
                 UnaryOpKind::Not => Value::Boolean(num.is_zero()),
-                UnaryOpKind::BitNot => Value::Float(float(!int_from_float(num))),
+                UnaryOpKind::BitNot => match int_from_float(num) {
+                    Some(int) => Value::Float(float(!int)),
+                    None => return Err(Error::new(
+                        vec![self.operand.span(), self.op.span.clone()],
+                        NonFiniteInteger,
+                    )),
+                },
                 UnaryOpKind::Factorial => Factorial::eval_static(num),
                 UnaryOpKind::Neg => Value::Float(-num),
+                UnaryOpKind::Pos => Value::Float(num),
             }),
             Value::Integer(num) => Ok(match self.op.kind {
                 UnaryOpKind::Not => Value::Boolean(num.is_zero()),
                 UnaryOpKind::BitNot => Value::Integer(!num),
                 UnaryOpKind::Factorial => Factorial::eval_static(float(num)),
                 UnaryOpKind::Neg => Value::Integer(-num),
+                UnaryOpKind::Pos => Value::Integer(num),
             }),
             Value::Complex(ref comp) => Ok(match self.op.kind {
                 UnaryOpKind::Not => Value::Boolean(comp.is_zero()),
+                UnaryOpKind::Factorial => Value::Complex(Gamma::eval_static(complex(comp) + 1)),
                 UnaryOpKind::Neg => Value::Complex(complex(&*comp.as_neg())),
+                UnaryOpKind::Pos => Value::Complex(complex(comp)),
                 _ => return Err(Error::new(vec![self.operand.span(), self.op.span.clone()], InvalidUnaryOperation {
                     op: self.op.kind,
                     expr_type: operand.typename(),
@@ -47,7 +59,7 @@ impl Eval for Unary {
                 op: self.op.kind,
                 expr_type: operand.typename(),
             })),
-            Value::List(_) => Err(Error::new(vec![self.operand.span(), self.op.span.clone()], InvalidUnaryOperation {
+            Value::String(_) | Value::List(_) | Value::Tuple(_) | Value::Measurement(_) => Err(Error::new(vec![self.operand.span(), self.op.span.clone()], InvalidUnaryOperation {
                 op: self.op.kind,
                 expr_type: operand.typename(),
             })),
@@ -225,6 +225,40 @@ fn fmt_word(f: &mut Formatter<'_>, n: &Float, options: FormatOptions) -> std::fm
     Ok(())
 }
 
+/// Formats an [`f64`] in hexadecimal floating-point notation, such as `0x1.921fb54442d18p+1`.
+pub fn fmt_hex_float(f: &mut Formatter<'_>, n: f64) -> std::fmt::Result {
+    if n.is_nan() {
+        return write!(f, "NaN");
+    }
+    if n.is_infinite() {
+        return write!(f, "{}∞", if n.is_sign_negative() { "-" } else { "" });
+    }
+
+    let bits = n.to_bits();
+    let sign = if n.is_sign_negative() { "-" } else { "" };
+    let biased_exp = ((bits >> 52) & 0x7ff) as i64;
+    let mantissa = bits & ((1 << 52) - 1);
+
+    if biased_exp == 0 && mantissa == 0 {
+        return write!(f, "{}0x0p+0", sign);
+    }
+
+    // subnormal numbers have no implicit leading bit, and the minimum exponent
+    let (leading, exp) = if biased_exp == 0 {
+        (0, -1022)
+    } else {
+        (1, biased_exp - 1023)
+    };
+
+    write!(f, "{}0x{}", sign, leading)?;
+    let digits = format!("{:013x}", mantissa);
+    let digits = digits.trim_end_matches('0');
+    if !digits.is_empty() {
+        write!(f, ".{}", digits)?;
+    }
+    write!(f, "p{:+}", exp)
+}
+
 /// Format a floating-point number using the given formatting options.
 pub fn fmt(f: &mut Formatter<'_>, n: &Float, options: FormatOptions) -> std::fmt::Result {
     match options.number {
@@ -239,5 +273,6 @@ pub fn fmt(f: &mut Formatter<'_>, n: &Float, options: FormatOptions) -> std::fmt
         NumberFormat::Scientific => fmt_scientific(f, n, options),
         NumberFormat::Fraction => fmt_fraction(f, n, options),
         NumberFormat::Word => fmt_word(f, n, options),
+        NumberFormat::HexFloat => fmt_hex_float(f, n.to_f64()),
     }
 }
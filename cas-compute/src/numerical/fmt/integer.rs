@@ -2,7 +2,7 @@
 
 use rug::Integer;
 use std::fmt::{Formatter, Write};
-use super::{FormatOptions, NumberFormat, Scientific, Separator};
+use super::{float, FormatOptions, NumberFormat, Scientific, Separator};
 
 /// Returns true if the given integer is large enough that it should be formatted in scientific
 /// notation.
@@ -238,5 +238,6 @@ pub fn fmt(f: &mut Formatter<'_>, n: &Integer, options: FormatOptions) -> std::f
         NumberFormat::Decimal | NumberFormat::Fraction => fmt_decimal(f, n, options),
         NumberFormat::Scientific => fmt_scientific(f, n, options),
         NumberFormat::Word => fmt_word(f, n, options),
+        NumberFormat::HexFloat => float::fmt_hex_float(f, n.to_f64()),
     }
 }
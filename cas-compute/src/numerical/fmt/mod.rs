@@ -4,7 +4,7 @@ mod integer;
 
 use crate::primitive::float;
 use std::fmt::{Display, Formatter};
-use super::value::Value;
+use super::value::{NonFinite, Value};
 
 /// Formatting options for values.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -82,6 +82,16 @@ pub enum NumberFormat {
 
     /// Formats the number in word form (e.g. "one", "two", "three").
     Word,
+
+    /// Formats the number in hexadecimal floating-point notation (e.g. `0x1.921fb54442d18p+1` for
+    /// `pi`), after rounding it to the nearest [`f64`].
+    ///
+    /// The output shows the exact bits of the rounded number, which is useful when diagnosing
+    /// rounding issues. The [`precision`] and [`separators`] options are ignored.
+    ///
+    /// [`precision`]: FormatOptions::precision
+    /// [`separators`]: FormatOptions::separators
+    HexFloat,
 }
 
 impl NumberFormat {
@@ -209,6 +219,7 @@ impl Display for ValueFormatter<'_> {
             Value::Complex(c) => complex::fmt(f, c, self.options),
             Value::Boolean(b) => write!(f, "{}", b),
             Value::Unit => write!(f, "()"),
+            Value::String(s) => write!(f, "{:?}", s),
             Value::List(l) => {
                 write!(f, "[")?;
                 for (i, item) in l.iter().enumerate() {
@@ -222,6 +233,29 @@ impl Display for ValueFormatter<'_> {
                 }
                 write!(f, "]")
             },
+            Value::Tuple(t) => {
+                write!(f, "(")?;
+                for (i, item) in t.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ValueFormatter {
+                        value: item,
+                        options: self.options,
+                    })?;
+                }
+                if t.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            },
+            Value::Measurement(m) => {
+                float::fmt(f, m.value(), self.options)?;
+                write!(f, " {}", m.unit())
+            },
+            Value::NonFinite(NonFinite::Nan) => write!(f, "NaN"),
+            Value::NonFinite(NonFinite::Infinity) => write!(f, "∞"),
+            Value::NonFinite(NonFinite::NegInfinity) => write!(f, "-∞"),
         }
     }
 }
@@ -404,4 +438,18 @@ mod tests {
 
         assert_eq!(formatted, "1,400.001");
     }
+
+    #[test]
+    fn hex_float() {
+        let opts = FormatOptionsBuilder::new()
+            .number(NumberFormat::HexFloat)
+            .build();
+
+        let inputs = ["pi", "-1", "0.5", "0", "2^-1074"];
+        let outputs = ["0x1.921fb54442d18p+1", "-0x1p+0", "0x1p-1", "0x0p+0", "0x0.0000000000001p-1022"];
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let formatted = format!("{}", eval(input).fmt(opts));
+            assert_eq!(formatted, *output);
+        }
+    }
 }
@@ -6,9 +6,14 @@
 
 pub mod builtin;
 pub mod ctxt;
+pub mod cse;
 pub mod error;
 pub mod eval;
 pub mod fmt;
+pub mod poly;
+pub mod purity;
+mod resolve;
+pub mod session;
 pub mod value;
 
 #[cfg(test)]
@@ -0,0 +1,350 @@
+//! Analysis of expressions that are polynomials in a single variable.
+
+use cas_parser::parser::{
+    ast::{
+        binary::Binary,
+        call::CallArg,
+        expr::Expr,
+        literal::{LitFloat, LitInt, LitSym, Literal},
+        paren::Paren,
+        unary::Unary,
+    },
+    token::op::{BinOp, BinOpKind, UnaryOp, UnaryOpKind},
+};
+use crate::numerical::{
+    ctxt::Ctxt,
+    error::{kind::{NotPolynomial, PolynomialTooLarge}, Error},
+    eval::{binary::eval_operands, Eval},
+    value::Value,
+};
+use std::ops::Range;
+
+/// A polynomial, represented by its coefficients from the lowest to the highest degree.
+type Coeffs = Vec<Value>;
+
+/// The maximum degree of a polynomial whose coefficients can be computed.
+const MAX_DEGREE: usize = 1024;
+
+/// Returns true if the expression refers to the given variable.
+fn contains_var(expr: &Expr, var: &str) -> bool {
+    expr.post_order_iter()
+        .any(|expr| matches!(expr, Expr::Literal(Literal::Symbol(sym)) if sym.name == var))
+}
+
+/// Creates a [`NotPolynomial`] error pointing at the given expression.
+fn not_polynomial(expr: &Expr, var: &str) -> Error {
+    Error::new(vec![expr.span()], NotPolynomial { var: var.to_string() })
+}
+
+/// Negates a numeric value.
+fn negate(value: Value) -> Value {
+    match value {
+        Value::Float(n) => Value::Float(-n),
+        Value::Integer(n) => Value::Integer(-n),
+        Value::Complex(c) => Value::Complex(-c),
+        value => value,
+    }
+}
+
+/// Adds (or subtracts, if `op` is [`BinOpKind::Sub`]) two polynomials.
+fn add(binary: &Binary, op: BinOpKind, lhs: Coeffs, rhs: Coeffs) -> Result<Coeffs, Error> {
+    let len = lhs.len().max(rhs.len());
+    let (mut lhs, mut rhs) = (lhs.into_iter(), rhs.into_iter());
+    (0..len)
+        .map(|_| {
+            let a = lhs.next().unwrap_or_else(|| 0.into());
+            let b = rhs.next().unwrap_or_else(|| 0.into());
+            eval_operands(op, false, a, b).map_err(|e| e.into_error(binary))
+        })
+        .collect()
+}
+
+/// Creates a [`PolynomialTooLarge`] error pointing at the given span.
+fn too_large(span: Range<usize>) -> Error {
+    Error::new(vec![span], PolynomialTooLarge { max: MAX_DEGREE })
+}
+
+/// Multiplies two polynomials, returning an error if the degree of the product would exceed
+/// [`MAX_DEGREE`].
+fn mul(binary: &Binary, lhs: &[Value], rhs: &[Value]) -> Result<Coeffs, Error> {
+    if (lhs.len() - 1) + (rhs.len() - 1) > MAX_DEGREE {
+        return Err(too_large(binary.span()));
+    }
+
+    let mut product = vec![Value::from(0); lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            let term = eval_operands(BinOpKind::Mul, false, a.clone(), b.clone())
+                .map_err(|e| e.into_error(binary))?;
+            product[i + j] = eval_operands(BinOpKind::Add, false, product[i + j].clone(), term)
+                .map_err(|e| e.into_error(binary))?;
+        }
+    }
+    Ok(product)
+}
+
+/// Evaluates an expression that does not contain the variable, which must result in a number.
+fn constant(expr: &Expr, var: &str, ctxt: &mut Ctxt) -> Result<Value, Error> {
+    let value = expr.eval(ctxt)?.coerce_number();
+    if value.is_complex() {
+        Ok(value)
+    } else {
+        Err(not_polynomial(expr, var))
+    }
+}
+
+/// Recursively computes the coefficients of the polynomial.
+fn inner_coeffs(expr: &Expr, var: &str, ctxt: &mut Ctxt) -> Result<Coeffs, Error> {
+    if !contains_var(expr, var) {
+        return Ok(vec![constant(expr, var, ctxt)?]);
+    }
+
+    match expr {
+        Expr::Literal(Literal::Symbol(_)) => Ok(vec![0.into(), 1.into()]),
+        Expr::Paren(paren) => inner_coeffs(&paren.expr, var, ctxt),
+        Expr::Unary(unary) if unary.op.kind == UnaryOpKind::Neg => {
+            Ok(inner_coeffs(&unary.operand, var, ctxt)?.into_iter().map(negate).collect())
+        },
+        Expr::Binary(binary) => match binary.op.kind {
+            BinOpKind::Add | BinOpKind::Sub => {
+                let lhs = inner_coeffs(&binary.lhs, var, ctxt)?;
+                let rhs = inner_coeffs(&binary.rhs, var, ctxt)?;
+                add(binary, binary.op.kind, lhs, rhs)
+            },
+            BinOpKind::Mul => {
+                let lhs = inner_coeffs(&binary.lhs, var, ctxt)?;
+                let rhs = inner_coeffs(&binary.rhs, var, ctxt)?;
+                mul(binary, &lhs, &rhs)
+            },
+            BinOpKind::Div if !contains_var(&binary.rhs, var) => {
+                let divisor = constant(&binary.rhs, var, ctxt)?;
+                inner_coeffs(&binary.lhs, var, ctxt)?
+                    .into_iter()
+                    .map(|coeff| {
+                        eval_operands(BinOpKind::Div, false, coeff, divisor.clone())
+                            .map_err(|e| e.into_error(binary))
+                    })
+                    .collect()
+            },
+            BinOpKind::Exp if !contains_var(&binary.rhs, var) => {
+                let exponent = constant(&binary.rhs, var, ctxt)?.coerce_integer();
+                let Some(exponent) = (match exponent {
+                    Value::Integer(n) => n.to_u32(),
+                    _ => None,
+                }) else {
+                    return Err(not_polynomial(expr, var));
+                };
+
+                let base = inner_coeffs(&binary.lhs, var, ctxt)?;
+                let degree = (base.len() - 1).checked_mul(exponent as usize);
+                if degree.is_none_or(|degree| degree > MAX_DEGREE) {
+                    return Err(too_large(expr.span()));
+                }
+
+                let mut power = vec![Value::from(1)];
+                for _ in 0..exponent {
+                    power = mul(binary, &power, &base)?;
+                }
+                Ok(power)
+            },
+            _ => Err(not_polynomial(expr, var)),
+        },
+        _ => Err(not_polynomial(expr, var)),
+    }
+}
+
+/// Returns the coefficients of an expression that is a polynomial in the variable `var`, from the
+/// lowest to the highest degree.
+///
+/// The expression must be built from the variable and constant subexpressions using only addition,
+/// subtraction, multiplication, negation, division by a constant, and exponentiation to a constant
+/// non-negative integer power. Constant subexpressions are evaluated in the given context, and must
+/// evaluate to numbers. Trailing zero coefficients are removed, though the constant coefficient is
+/// always returned.
+///
+/// If the expression is not a polynomial in `var`, a [`NotPolynomial`] error is returned, pointing
+/// at the offending subexpression. If the degree of the polynomial, or of any of its
+/// subexpressions, exceeds [`MAX_DEGREE`], a [`PolynomialTooLarge`] error is returned instead.
+pub fn coeffs(expr: &Expr, var: &str, ctxt: &mut Ctxt) -> Result<Vec<Value>, Error> {
+    let mut coeffs = inner_coeffs(expr, var, ctxt)?;
+    while coeffs.len() > 1 && coeffs.last().is_some_and(|coeff| !coeff.is_truthy()) {
+        coeffs.pop();
+    }
+    Ok(coeffs)
+}
+
+/// Creates an expression evaluating to the given real number, or returns [`None`] if the value is
+/// not a real number.
+fn real_literal(value: &Value) -> Option<Expr> {
+    let (literal, negative) = match value.clone().coerce_number() {
+        Value::Integer(n) => (
+            Literal::Integer(LitInt { value: n.clone().abs().to_string(), span: 0..0 }),
+            n.is_negative(),
+        ),
+        Value::Float(n) => (
+            Literal::Float(LitFloat { value: n.clone().abs().to_string(), span: 0..0 }),
+            n.is_sign_negative(),
+        ),
+        _ => return None,
+    };
+
+    let literal = Expr::Literal(literal);
+    if negative {
+        Some(Expr::Unary(Unary {
+            operand: Box::new(literal),
+            op: UnaryOp { kind: UnaryOpKind::Neg, span: 0..0 },
+            span: 0..0,
+        }))
+    } else {
+        Some(literal)
+    }
+}
+
+/// Creates a binary expression with the given operator.
+fn binary(lhs: Expr, kind: BinOpKind, rhs: Expr) -> Expr {
+    Expr::Binary(Binary {
+        lhs: Box::new(lhs),
+        op: BinOp { kind, implicit: false, span: 0..0 },
+        rhs: Box::new(rhs),
+        span: 0..0,
+    })
+}
+
+/// Rewrites the polynomial with the given coefficients into Horner form, or returns [`None`] if
+/// any coefficient is not a real number.
+fn horner_form(coeffs: &[Value], var: &str) -> Option<Expr> {
+    let (leading, rest) = coeffs.split_last()?;
+    let mut output = real_literal(leading)?;
+    for coeff in rest.iter().rev() {
+        let var = Expr::Literal(Literal::Symbol(LitSym { name: var.to_string(), span: 0..0 }));
+        let product = binary(var, BinOpKind::Mul, Expr::Paren(Paren {
+            expr: Box::new(output),
+            span: 0..0,
+        }));
+        output = if coeff.is_truthy() {
+            binary(real_literal(coeff)?, BinOpKind::Add, product)
+        } else {
+            product
+        };
+    }
+    Some(output)
+}
+
+/// Rewrites every polynomial in the variable `var` within the expression into Horner form, such
+/// that `1 + 2x + 3x^2` becomes `1 + x * (2 + x * 3)`.
+///
+/// The Horner form of a polynomial of degree `n` needs exactly `n` multiplications and no
+/// exponentiation to evaluate, which is both faster and accumulates less rounding error than the
+/// expanded form. This makes it worthwhile when the expression will be evaluated for many values of
+/// `var`.
+///
+/// Polynomials are found with [`coeffs`], so their constant subexpressions are evaluated once, in
+/// the given context, while rewriting. Subexpressions that are not polynomials are searched for
+/// nested polynomials instead. Polynomials of degree less than two, and polynomials with
+/// non-real coefficients, are left unchanged.
+pub fn horner(expr: &Expr, var: &str, ctxt: &mut Ctxt) -> Expr {
+    if !contains_var(expr, var) {
+        return expr.clone();
+    }
+
+    if let Ok(coeffs) = coeffs(expr, var, ctxt) {
+        if coeffs.len() > 2 {
+            if let Some(output) = horner_form(&coeffs, var) {
+                return output;
+            }
+        }
+        return expr.clone();
+    }
+
+    let mut output = expr.clone();
+    match &mut output {
+        Expr::Paren(paren) => *paren.expr = horner(&paren.expr, var, ctxt),
+        Expr::Unary(unary) => *unary.operand = horner(&unary.operand, var, ctxt),
+        Expr::Binary(binary) => {
+            *binary.lhs = horner(&binary.lhs, var, ctxt);
+            *binary.rhs = horner(&binary.rhs, var, ctxt);
+        },
+        Expr::Call(call) => {
+            for arg in &mut call.args {
+                let (CallArg::Positional(value) | CallArg::Named { value, .. }) = arg;
+                *value = horner(value, var, ctxt);
+            }
+        },
+        _ => (),
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cas_parser::parser::Parser;
+
+    /// Returns the coefficients of the polynomial in `x` given by `source`.
+    fn coeffs_str(source: &str) -> Result<Vec<Value>, Error> {
+        let expr = Parser::new(source).try_parse_full::<Expr>().unwrap();
+        coeffs(&expr, "x", &mut Ctxt::default())
+    }
+
+    #[test]
+    fn polynomial_coeffs() {
+        assert_eq!(coeffs_str("2 + 3*x + x^2").unwrap(), vec![2.into(), 3.into(), 1.into()]);
+        assert_eq!(coeffs_str("(x - 1)^2").unwrap(), vec![1.into(), (-2).into(), 1.into()]);
+        assert_eq!(coeffs_str("5").unwrap(), vec![5.into()]);
+        assert_eq!(coeffs_str("x^2 - x^2 + 4x").unwrap(), vec![0.into(), 4.into()]);
+    }
+
+    #[test]
+    fn horner_rewrite() {
+        /// Counts the multiplications and exponentiations in the expression.
+        fn count_muls(expr: &Expr) -> usize {
+            expr.post_order_iter()
+                .filter(|expr| matches!(expr, Expr::Binary(binary) if matches!(binary.op.kind, BinOpKind::Mul | BinOpKind::Exp)))
+                .count()
+        }
+
+        let mut ctxt = Ctxt::default();
+        let expr = Parser::new("1 + 2x + 3x^2").try_parse_full::<Expr>().unwrap();
+        let rewritten = horner(&expr, "x", &mut ctxt);
+        assert_eq!(rewritten.to_string(), "1+x*(2+x*3)");
+        assert!(count_muls(&rewritten) < count_muls(&expr));
+
+        for x in [Value::from(-2), 0.into(), 0.5.into(), 3.into()] {
+            ctxt.add_var("x", x);
+            assert_eq!(rewritten.eval(&mut ctxt).unwrap(), expr.eval(&mut ctxt).unwrap());
+        }
+
+        // nested polynomials are rewritten too
+        let expr = Parser::new("sin(x^2 - x^3)").try_parse_full::<Expr>().unwrap();
+        let rewritten = horner(&expr, "x", &mut ctxt);
+        assert_eq!(rewritten.to_string(), "sin(x*(x*(1+x*(-1))))");
+    }
+
+    #[test]
+    fn polynomial_too_large() {
+        for source in ["x^4294967295", "(x^1000)^1000", "x^1000 * x^1000"] {
+            let err = coeffs_str(source).unwrap_err();
+            assert_eq!(
+                format!("{:?}", err.kind),
+                format!("{:?}", PolynomialTooLarge { max: MAX_DEGREE }),
+                "{}",
+                source,
+            );
+        }
+        assert_eq!(coeffs_str("x^100").unwrap().len(), 101);
+    }
+
+    #[test]
+    fn not_a_polynomial() {
+        for source in ["sin(x)", "x^x", "1 / x", "x^-1", "x^0.5"] {
+            let err = coeffs_str(source).unwrap_err();
+            assert_eq!(
+                format!("{:?}", err.kind),
+                format!("{:?}", NotPolynomial { var: "x".to_string() }),
+                "{}",
+                source,
+            );
+        }
+    }
+}
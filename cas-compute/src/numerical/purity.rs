@@ -0,0 +1,161 @@
+//! Conservative analysis of whether a user-defined function or an expression is pure.
+//!
+//! A pure function always returns the same value when called with the same arguments, and has no
+//! side effects, so its results can be safely cached (see [`Ctxt::memoize_pure_funcs`]). Likewise,
+//! a pure expression can be evaluated once and its value reused (see [`eval_cse`]).
+//!
+//! [`eval_cse`]: crate::numerical::cse::eval_cse
+
+use cas_parser::parser::ast::{
+    assign::{AssignTarget, FuncHeader},
+    call::CallArg,
+    expr::Expr,
+    literal::Literal,
+};
+use crate::numerical::ctxt::{Ctxt, Func};
+use std::collections::HashSet;
+
+/// Builtin functions that have side effects or return different values on each call.
+pub(crate) const IMPURE_BUILTINS: &[&str] = &["print", "rand"];
+
+/// State used while checking the purity of a function body or an expression.
+struct Checker<'a> {
+    /// The name of the function being checked, which may call itself, or [`None`] if an expression
+    /// is being checked. An expression may read any variable of the context, but not assign one.
+    name: Option<&'a str>,
+
+    /// The context the function is defined in, used to determine which functions are builtins.
+    ctxt: &'a Ctxt,
+
+    /// The variables that are local to the function: its parameters, and any variables assigned in
+    /// its body.
+    locals: HashSet<&'a str>,
+}
+
+impl<'a> Checker<'a> {
+    /// Returns true if the call arguments are all pure.
+    fn args(&mut self, args: &'a [CallArg]) -> bool {
+        args.iter().all(|arg| self.expr(arg.value()))
+    }
+
+    /// Returns true if the expressions are all pure.
+    fn exprs(&mut self, exprs: &'a [Expr]) -> bool {
+        exprs.iter().all(|expr| self.expr(expr))
+    }
+
+    /// Returns true if the expression is pure.
+    fn expr(&mut self, expr: &'a Expr) -> bool {
+        match expr {
+            Expr::Literal(literal) => match literal {
+                Literal::Symbol(sym) => self.name.is_none() || self.locals.contains(sym.name.as_str()),
+                Literal::List(list) => self.exprs(&list.values),
+                Literal::Tuple(tuple) => self.exprs(&tuple.values),
+                Literal::Integer(_)
+                    | Literal::Float(_)
+                    | Literal::Radix(_)
+                    | Literal::Boolean(_)
+                    | Literal::Unit(_)
+                    | Literal::Slot(_) => true,
+            },
+            Expr::Measurement(_) => true,
+
+            // breaking out of a loop that encloses the expression is a side effect of the
+            // expression, but that of a function body is caught by the call
+            Expr::Continue(_) => self.name.is_some(),
+            Expr::Paren(paren) => self.expr(&paren.expr),
+            Expr::Block(block) => block.stmts.iter().all(|stmt| self.expr(&stmt.expr)),
+            Expr::Piecewise(piecewise) => {
+                piecewise.branches.iter()
+                    .all(|branch| self.expr(&branch.value) && self.expr(&branch.condition))
+                    && piecewise.otherwise.as_deref().is_none_or(|otherwise| self.expr(otherwise))
+            },
+            Expr::If(if_expr) => {
+                self.expr(&if_expr.condition)
+                    && self.expr(&if_expr.then_expr)
+                    && if_expr.else_expr.as_deref().is_none_or(|else_expr| self.expr(else_expr))
+            },
+            Expr::Loop(loop_expr) => self.expr(&loop_expr.body),
+            Expr::While(while_expr) => self.expr(&while_expr.condition) && self.expr(&while_expr.body),
+            Expr::Let(let_expr) => {
+                if !self.expr(&let_expr.value) {
+                    return false;
+                }
+
+                // the body is evaluated in a child of the context, so neither the binding nor any
+                // variables assigned in the body are visible afterwards
+                let locals = self.locals.clone();
+                self.locals.insert(&let_expr.name.name);
+                let pure = self.expr(&let_expr.body);
+                self.locals = locals;
+                pure
+            },
+            Expr::Break(break_expr) => {
+                self.name.is_some()
+                    && break_expr.value.as_deref().is_none_or(|value| self.expr(value))
+            },
+            Expr::Call(call) => {
+                let name = call.name.name.as_str();
+
+                // other user-defined functions could read variables outside of the scope of a
+                // function body, or be redefined later; neither can happen while an expression is
+                // evaluated
+                let pure_callee = Some(name) == self.name || match self.ctxt.get_func(name) {
+                    Some(Func::Builtin(_)) => !IMPURE_BUILTINS.contains(&name),
+                    Some(Func::UserFunc(func)) => self.name.is_none() && func.pure,
+                    None => false,
+                };
+                pure_callee && self.args(&call.args)
+            },
+            Expr::Unary(unary) => self.expr(&unary.operand),
+            Expr::Binary(binary) => self.expr(&binary.lhs) && self.expr(&binary.rhs),
+            // assigning a variable of the context is a side effect of the expression
+            Expr::Assign(_) if self.name.is_none() => false,
+            Expr::Assign(assign) => match &assign.target {
+                AssignTarget::Symbol(symbol) => {
+                    // compound assignment reads the variable before writing it
+                    let reads_outer = assign.op.is_compound()
+                        && !self.locals.contains(symbol.name.as_str());
+                    let pure = !reads_outer && self.expr(&assign.value);
+                    self.locals.insert(&symbol.name);
+                    pure
+                },
+                AssignTarget::Destructure(symbols) => {
+                    let pure = self.expr(&assign.value);
+                    self.locals.extend(symbols.iter().map(|symbol| symbol.name.as_str()));
+                    pure
+                },
+                AssignTarget::Func(_) => false,
+            },
+            Expr::Conversion(conversion) => self.expr(&conversion.value),
+        }
+    }
+}
+
+/// Returns true if the user-defined function with the given header and body is pure, when defined
+/// in the given context.
+///
+/// The analysis is conservative: a function is considered pure only if its body refers to no
+/// variables other than its parameters and variables it assigns itself, calls only itself and
+/// builtin functions without side effects, and does not define functions.
+pub fn is_pure(header: &FuncHeader, body: &Expr, ctxt: &Ctxt) -> bool {
+    let mut checker = Checker {
+        name: Some(&header.name.name),
+        ctxt,
+        locals: header.params.iter().map(|param| param.symbol().name.as_str()).collect(),
+    };
+    checker.expr(body)
+}
+
+/// Returns true if the expression is pure when evaluated in the given context: it always evaluates
+/// to the same value, and has no side effects.
+///
+/// Unlike a function body, the expression may read any variable of the context, and call
+/// user-defined functions that are themselves pure.
+pub(crate) fn is_pure_expr(expr: &Expr, ctxt: &Ctxt) -> bool {
+    let mut checker = Checker {
+        name: None,
+        ctxt,
+        locals: HashSet::new(),
+    };
+    checker.expr(expr)
+}
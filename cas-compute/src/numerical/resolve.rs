@@ -0,0 +1,120 @@
+//! Resolution of the parameters of user-defined functions to slots.
+//!
+//! Calling a user-defined function normally binds each argument to a variable named after its
+//! parameter, and each reference to a parameter in the body looks the variable up by name. To
+//! avoid this, [`resolve`] rewrites the body once, when the function is defined, replacing each
+//! reference to a parameter with a [`LitSlot`] holding the index of its argument. The arguments of
+//! a call are then stored in [`Ctxt::slots`], and read from there directly.
+//!
+//! [`Ctxt::slots`]: crate::numerical::ctxt::Ctxt::slots
+
+use cas_parser::parser::ast::{
+    assign::{FuncHeader, Param},
+    call::CallArg,
+    expr::Expr,
+    literal::{LitSlot, Literal},
+};
+
+/// A function body whose parameters have been resolved to slots.
+#[derive(Debug, Clone)]
+pub(crate) struct Resolved {
+    /// The function body, with each reference to a parameter replaced by a reference to its slot.
+    pub(crate) body: Expr,
+
+    /// Whether the body calls any function. Functions called from the body can read the
+    /// parameters of the caller by name, so the arguments must still be bound to variables.
+    pub(crate) calls: bool,
+}
+
+/// State used while resolving a function body.
+struct Resolver<'a> {
+    /// The names of the parameters of the function, in order.
+    params: Vec<&'a str>,
+
+    /// Whether the body calls any function.
+    calls: bool,
+}
+
+impl Resolver<'_> {
+    /// Resolves the references to parameters in the expressions, returning false if any of them
+    /// cannot be resolved.
+    fn exprs(&mut self, exprs: &mut [Expr]) -> bool {
+        exprs.iter_mut().all(|expr| self.expr(expr))
+    }
+
+    /// Resolves the references to parameters in the expression, returning false if it cannot be
+    /// resolved.
+    ///
+    /// Expressions that can introduce or assign variables, such as assignments and `let`
+    /// expressions, cannot be resolved, since they could shadow or change a parameter.
+    fn expr(&mut self, expr: &mut Expr) -> bool {
+        match expr {
+            Expr::Literal(literal) => match literal {
+                Literal::Symbol(sym) => {
+                    if let Some(index) = self.params.iter().position(|&param| param == sym.name) {
+                        *literal = Literal::Slot(LitSlot {
+                            index,
+                            name: sym.name.clone(),
+                            span: sym.span.clone(),
+                        });
+                    }
+                    true
+                },
+                Literal::List(list) => self.exprs(&mut list.values),
+                Literal::Tuple(tuple) => self.exprs(&mut tuple.values),
+                Literal::Integer(_)
+                    | Literal::Float(_)
+                    | Literal::Radix(_)
+                    | Literal::Boolean(_)
+                    | Literal::Unit(_)
+                    | Literal::Slot(_) => true,
+            },
+            Expr::Measurement(_) => true,
+            Expr::Paren(paren) => self.expr(&mut paren.expr),
+            Expr::Piecewise(piecewise) => {
+                piecewise.branches.iter_mut()
+                    .all(|branch| self.expr(&mut branch.value) && self.expr(&mut branch.condition))
+                    && piecewise.otherwise.as_mut().is_none_or(|otherwise| self.expr(otherwise))
+            },
+            Expr::If(if_expr) => {
+                self.expr(&mut if_expr.condition)
+                    && self.expr(&mut if_expr.then_expr)
+                    && if_expr.else_expr.as_mut().is_none_or(|else_expr| self.expr(else_expr))
+            },
+            Expr::Call(call) => {
+                self.calls = true;
+                call.args.iter_mut().all(|arg| match arg {
+                    CallArg::Positional(value) | CallArg::Named { value, .. } => self.expr(value),
+                })
+            },
+            Expr::Unary(unary) => self.expr(&mut unary.operand),
+            Expr::Binary(binary) => self.expr(&mut binary.lhs) && self.expr(&mut binary.rhs),
+            Expr::Conversion(conversion) => self.expr(&mut conversion.value),
+            Expr::Block(_)
+                | Expr::Loop(_)
+                | Expr::While(_)
+                | Expr::Let(_)
+                | Expr::Break(_)
+                | Expr::Continue(_)
+                | Expr::Assign(_) => false,
+        }
+    }
+}
+
+/// Resolves the parameters of the function to slots, returning [`None`] if the function cannot be
+/// resolved.
+///
+/// Functions with default parameter values are not resolved, since a default value can refer to
+/// an earlier parameter by name.
+pub(crate) fn resolve(header: &FuncHeader, body: &Expr) -> Option<Resolved> {
+    if header.params.iter().any(|param| matches!(param, Param::Default(..))) {
+        return None;
+    }
+
+    let mut resolver = Resolver {
+        params: header.params.iter().map(|param| param.symbol().name.as_str()).collect(),
+        calls: false,
+    };
+    let mut body = body.clone();
+    resolver.expr(&mut body).then_some(Resolved { body, calls: resolver.calls })
+}
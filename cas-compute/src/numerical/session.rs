@@ -0,0 +1,91 @@
+//! A persistent evaluation session, useful for building REPLs.
+
+use cas_parser::parser::{ast::Program, error::Error as ParseError, Parser};
+use super::{ctxt::Ctxt, error::Error as EvalError, eval::Eval, value::Value};
+
+/// An error that can occur while parsing or evaluating a line of input in a [`Session`].
+#[derive(Debug)]
+pub enum SessionError {
+    /// Errors that occurred while parsing.
+    ParseError(Vec<ParseError>),
+
+    /// An error that occurred while evaluating.
+    EvalError(EvalError),
+}
+
+impl From<Vec<ParseError>> for SessionError {
+    fn from(errs: Vec<ParseError>) -> Self {
+        Self::ParseError(errs)
+    }
+}
+
+impl From<EvalError> for SessionError {
+    fn from(err: EvalError) -> Self {
+        Self::EvalError(err)
+    }
+}
+
+/// A session holds a [`Ctxt`] that persists across multiple inputs, so that variables and
+/// functions defined in one input can be used in later inputs.
+///
+/// ```
+/// use cas_compute::numerical::session::Session;
+///
+/// let mut session = Session::new();
+/// session.eval_line("x = 5").unwrap();
+/// assert_eq!(session.eval_line("x + 1").unwrap(), 6.into());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    /// The context that input is evaluated in.
+    ctxt: Ctxt,
+}
+
+impl Session {
+    /// Creates a new session with the default context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new session that evaluates input in the given context.
+    pub fn with_ctxt(ctxt: Ctxt) -> Self {
+        Self { ctxt }
+    }
+
+    /// Returns an immutable reference to the session's context.
+    pub fn ctxt(&self) -> &Ctxt {
+        &self.ctxt
+    }
+
+    /// Returns a mutable reference to the session's context.
+    pub fn ctxt_mut(&mut self) -> &mut Ctxt {
+        &mut self.ctxt
+    }
+
+    /// Parses and evaluates the given input against the session's context, returning the value of
+    /// the last statement in the input. Any definitions made by the input persist in the session.
+    pub fn eval_line(&mut self, line: &str) -> Result<Value, SessionError> {
+        let program = Parser::try_new(line)
+            .map_err(|err| vec![err])?
+            .try_parse_full::<Program>()?;
+        Ok(program.eval(&mut self.ctxt)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definitions_persist() {
+        let mut session = Session::new();
+        assert_eq!(session.eval_line("x = 5").unwrap(), Value::Unit);
+        assert_eq!(session.eval_line("x + 1").unwrap(), 6.into());
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut session = Session::new();
+        assert!(matches!(session.eval_line("1 +"), Err(SessionError::ParseError(_))));
+    }
+}
@@ -1,7 +1,8 @@
+use cas_math::unit_conversion::Measurement;
 use crate::consts::PI;
 use crate::primitive::{complex, float};
 use rug::{Complex, Float, Integer};
-use std::fmt::{Display, Formatter};
+use std::{error::Error, fmt::{Display, Formatter}};
 use super::fmt::{FormatOptions, ValueFormatter};
 
 #[cfg(feature = "serde")]
@@ -26,8 +27,37 @@ pub enum Value {
     /// The unit type, analogous to `()` in Rust.
     Unit,
 
+    /// A string of text.
+    String(String),
+
     /// A list of values.
     List(Vec<Value>),
+
+    /// A fixed-size group of values, such as the quotient and remainder returned by `divmod`.
+    Tuple(Vec<Value>),
+
+    /// A number with a unit of measurement, such as `5 km`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Measurement(Measurement<Float>),
+
+    /// A sentinel standing in for a non-finite number. See [`NonFinitePolicy::Sentinel`].
+    ///
+    /// [`NonFinitePolicy::Sentinel`]: crate::numerical::ctxt::NonFinitePolicy::Sentinel
+    NonFinite(NonFinite),
+}
+
+/// The kinds of non-finite numbers represented by a [`Value::NonFinite`] sentinel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NonFinite {
+    /// A number with a `NaN` component.
+    Nan,
+
+    /// A number with an infinite component, other than negative infinity.
+    Infinity,
+
+    /// Negative real infinity.
+    NegInfinity,
 }
 
 #[cfg(test)]
@@ -36,6 +66,15 @@ impl Value {
     pub fn approx_eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::Float(a), Value::Float(b)) => float(a - b) / float(a) < float(1e-3),
+            (Value::Complex(a), Value::Complex(b)) => {
+                // compare each part separately, since either part may be zero
+                let (real, imag) = complex(a - b).into_real_imag();
+                real.abs() < float(1e-3) && imag.abs() < float(1e-3)
+            },
+            // arithmetic demotes complex results with a zero imaginary part to floats
+            (Value::Float(a), Value::Complex(b)) | (Value::Complex(b), Value::Float(a)) => {
+                Value::Complex(complex(a)).approx_eq(&Value::Complex(b.clone()))
+            },
             _ => false,
         }
     }
@@ -50,7 +89,11 @@ impl Value {
             Value::Complex(_) => "Complex",
             Value::Boolean(_) => "Boolean",
             Value::Unit => "Unit",
+            Value::String(_) => "String",
             Value::List(_) => "List",
+            Value::Tuple(_) => "Tuple",
+            Value::Measurement(_) => "Measurement",
+            Value::NonFinite(_) => "NonFinite",
         }
     }
 
@@ -192,6 +235,38 @@ impl Value {
         matches!(self, Value::Unit)
     }
 
+    /// Returns true if this value is a number with a `NaN` component.
+    pub fn is_nan(&self) -> bool {
+        match self {
+            Value::Float(n) => n.is_nan(),
+            Value::Complex(c) => c.real().is_nan() || c.imag().is_nan(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if this value is a number with an infinite component.
+    pub fn is_infinite(&self) -> bool {
+        match self {
+            Value::Float(n) => n.is_infinite(),
+            Value::Complex(c) => c.real().is_infinite() || c.imag().is_infinite(),
+            _ => false,
+        }
+    }
+
+    /// Returns the kind of non-finite number this value is, or [`None`] if it is not a non-finite
+    /// number.
+    pub fn non_finite(&self) -> Option<NonFinite> {
+        if self.is_nan() {
+            Some(NonFinite::Nan)
+        } else if !self.is_infinite() {
+            None
+        } else if matches!(self, Value::Float(n) if n.is_sign_negative()) {
+            Some(NonFinite::NegInfinity)
+        } else {
+            Some(NonFinite::Infinity)
+        }
+    }
+
     /// Returns true if this value is truthy.
     pub fn is_truthy(&self) -> bool {
         match self {
@@ -200,7 +275,11 @@ impl Value {
             Value::Complex(c) => !c.is_zero(),
             Value::Boolean(b) => *b,
             Value::Unit => false,
+            Value::String(s) => !s.is_empty(),
             Value::List(l) => !l.is_empty(),
+            Value::Tuple(t) => !t.is_empty(),
+            Value::Measurement(m) => !m.value().is_zero(),
+            Value::NonFinite(_) => true,
         }
     }
 
@@ -255,8 +334,120 @@ impl From<()> for Value {
     }
 }
 
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Self {
+        Value::List(values)
+    }
+}
+
+/// The error returned when a [`Value`] cannot be converted into a Rust type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryFromValueError {
+    /// The name of the Rust type that the value was being converted into.
+    pub expected: &'static str,
+
+    /// The type of the value (see [`Value::typename`]).
+    pub found: &'static str,
+}
+
+impl Display for TryFromValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot convert a value of type `{}` to `{}`", self.found, self.expected)
+    }
+}
+
+impl Error for TryFromValueError {}
+
+impl TryFrom<Value> for f64 {
+    type Error = TryFromValueError;
+
+    /// Converts a [`Value::Float`] or [`Value::Integer`] into an [`f64`], rounding to the nearest
+    /// representable value.
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(n) => Ok(n.to_f64()),
+            Value::Integer(n) => Ok(n.to_f64()),
+            value => Err(TryFromValueError { expected: "f64", found: value.typename() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = TryFromValueError;
+
+    /// Converts a [`Value::Integer`] into an [`i64`]. Integers that do not fit in an [`i64`] are
+    /// rejected.
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(ref n) => n.to_i64()
+                .ok_or(TryFromValueError { expected: "i64", found: value.typename() }),
+            value => Err(TryFromValueError { expected: "i64", found: value.typename() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Boolean(b) => Ok(b),
+            value => Err(TryFromValueError { expected: "bool", found: value.typename() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for Vec<Value> {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::List(values) => Ok(values),
+            value => Err(TryFromValueError { expected: "Vec<Value>", found: value.typename() }),
+        }
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.fmt(Default::default()).fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f64_round_trip() {
+        let value = Value::from(2.5);
+        assert_eq!(f64::try_from(value), Ok(2.5));
+    }
+
+    #[test]
+    fn try_from_type_mismatch() {
+        assert_eq!(
+            bool::try_from(Value::from(2.5)),
+            Err(TryFromValueError { expected: "bool", found: "Float" }),
+        );
+        assert_eq!(
+            i64::try_from(Value::from(true)),
+            Err(TryFromValueError { expected: "i64", found: "Boolean" }),
+        );
+    }
+
+    #[test]
+    fn list_round_trip() {
+        let value = Value::from(vec![Value::from(true), Value::from(1i64)]);
+        assert_eq!(
+            Vec::<Value>::try_from(value),
+            Ok(vec![Value::Boolean(true), Value::Integer(Integer::from(1))]),
+        );
+    }
+}
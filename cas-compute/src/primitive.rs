@@ -15,9 +15,10 @@ where
 }
 
 /// Creates an [`Integer`] from a [`Float`] by truncating the fractional part.
-pub fn int_from_float(f: Float) -> Integer {
-    // TODO: can panic if NaN
-    f.trunc().to_integer().unwrap()
+///
+/// Returns [`None`] if the [`Float`] is `NaN` or infinite.
+pub fn int_from_float(f: Float) -> Option<Integer> {
+    f.trunc().to_integer()
 }
 
 /// Creates an [`Integer`] from a string slice.
@@ -82,4 +83,12 @@ mod tests {
             assert_eq!(from_str_radix(number, *radix), expected);
         }
     }
+
+    #[test]
+    fn int_from_non_finite_float() {
+        assert_eq!(int_from_float(float(2.75)), Some(int(2)));
+        assert_eq!(int_from_float(float(f64::INFINITY)), None);
+        assert_eq!(int_from_float(float(f64::NEG_INFINITY)), None);
+        assert_eq!(int_from_float(float(f64::NAN)), None);
+    }
 }
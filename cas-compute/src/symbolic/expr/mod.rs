@@ -495,16 +495,22 @@ impl From<AstExpr> for Expr {
                 Literal::Symbol(sym) => Self::Primary(Primary::Symbol(sym.name)),
                 Literal::Unit(_) => todo!(),
                 Literal::List(_) => todo!(),
+                Literal::Tuple(_) => todo!(),
+                Literal::Slot(slot) => Self::Primary(Primary::Symbol(slot.name)),
             },
             AstExpr::Paren(paren) => Self::from(paren.into_innermost()),
             AstExpr::Block(_) => todo!(),
+            AstExpr::Piecewise(_) => todo!(),
             AstExpr::If(_) => todo!(),
             AstExpr::Loop(_) => todo!(),
             AstExpr::While(_) => todo!(),
+            AstExpr::Let(_) => todo!(),
             AstExpr::Break(_) => todo!(),
             AstExpr::Continue(_) => todo!(),
+            AstExpr::Measurement(_) => todo!(),
+            AstExpr::Conversion(_) => todo!(),
             AstExpr::Call(call) => {
-                let args = call.args.into_iter().map(Self::from).collect();
+                let args = call.args.into_iter().map(|arg| Self::from(arg.into_value())).collect();
                 Self::Primary(Primary::Call(call.name.name, args))
             },
             AstExpr::Unary(unary) => {
@@ -513,6 +519,7 @@ impl From<AstExpr> for Expr {
                         // treat this as -1 * rhs
                         Self::from(*unary.operand).neg()
                     },
+                    UnaryOpKind::Pos => Self::from(*unary.operand),
                     _ => todo!(),
                 }
             },
@@ -556,7 +563,7 @@ impl From<AstExpr> for Expr {
                             Self::from(*bin.rhs),
                         )
                     },
-                    BinOpKind::Mod => todo!(),
+                    BinOpKind::IntDiv | BinOpKind::Mod => todo!(),
                     BinOpKind::Add => {
                         // iteratively flatten binary expressions into terms
                         // because the AST obviously exists, `terms` will never end up as a
@@ -614,7 +621,7 @@ impl From<AstExpr> for Expr {
 impl From<Expr> for AstExpr {
     fn from(expr: Expr) -> Self {
         use cas_parser::parser::{
-            ast::{Binary, Call, LitFloat, LitInt, LitSym},
+            ast::{Binary, Call, CallArg, LitFloat, LitInt, LitSym},
             token::op::BinOp,
         };
 
@@ -635,7 +642,7 @@ impl From<Expr> for AstExpr {
                 Primary::Call(name, args) => AstExpr::Call(Call {
                     name: LitSym { name, span: 0..0 },
                     derivatives: 0,
-                    args: args.into_iter().map(Self::from).collect(),
+                    args: args.into_iter().map(|arg| CallArg::Positional(Self::from(arg))).collect(),
                     span: 0..0,
                     paren_span: 0..0,
                 }),
@@ -197,6 +197,23 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn trivial_identities() {
+        let x = Expr::Primary(Primary::Symbol(String::from("x")));
+        for input in ["x + 0", "x * 1", "x^1", "0 + x * 1"] {
+            assert_eq!(simplify_str(input), x, "{}", input);
+        }
+        assert_eq!(simplify_str("x * 0"), Expr::Primary(Primary::Integer(int(0))));
+        assert_eq!(simplify_str("x^0"), Expr::Primary(Primary::Integer(int(1))));
+
+        // `0 * inf` is undefined, so it must not be simplified to `0`
+        let expr = Expr::Mul(vec![
+            Expr::Primary(Primary::Integer(int(0))),
+            Expr::Primary(Primary::Float(float(f64::INFINITY))),
+        ]);
+        assert_eq!(rules::multiply::multiply_zero(&expr, &mut ()), None);
+    }
+
     #[test]
     fn add_fractions() {
         let simplified_expr = simplify_str("1/2 + 1/3 - 2 + 5/6");
@@ -206,6 +223,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn exact_fraction_literal() {
+        // integer divisions are kept as exact fractions, so no rounding error is introduced
+        let simplified_expr = simplify_str("1/3");
+        assert_eq!(simplified_expr, make_fraction(
+            Expr::Primary(Primary::Integer(int(1))),
+            Expr::Primary(Primary::Integer(int(3))),
+        ));
+
+        let simplified_expr = simplify_str("1/3 * 3");
+        assert_eq!(simplified_expr, Expr::Primary(Primary::Integer(int(1))));
+    }
+
     #[test]
     fn add_fractions_with_factors() {
         let simplified_expr = simplify_str("pi/2 + 2 - 1/3 - 5pi/6");
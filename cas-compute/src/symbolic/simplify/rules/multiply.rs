@@ -10,8 +10,17 @@ use crate::symbolic::{
 
 /// `0*a = 0`
 /// `a*0 = 0`
+///
+/// Symbols are assumed to be finite, but this rule is not applied if any factor is a non-finite
+/// floating-point number, since `0*inf` is undefined.
 pub fn multiply_zero(expr: &Expr, step_collector: &mut dyn StepCollector<Step>) -> Option<Expr> {
     let opt = do_multiply(expr, |factors| {
+        let non_finite = factors.iter()
+            .any(|factor| matches!(factor, Expr::Primary(Primary::Float(n)) if !n.is_finite()));
+        if non_finite {
+            return None;
+        }
+
         if factors.iter().any(|factor| factor.as_integer().map(|n| n.is_zero()).unwrap_or(false)) {
             Some(Expr::Primary(Primary::Integer(int(0))))
         } else {
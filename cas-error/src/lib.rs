@@ -1,14 +1,45 @@
 //! Contains the common [`ErrorKind`] trait used by all parsing and evaluation errors to display
 //! user-facing error messages.
 
-use ariadne::{Color, Report};
-use std::{fmt::Debug, ops::Range};
+use ariadne::{Color, Report, ReportKind};
+use std::{any::Any, fmt::Debug, ops::Range};
 
 /// The color to use to highlight expressions.
 pub const EXPR: Color = Color::RGB(52, 235, 152);
 
+/// The severity of an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Severity {
+    /// The error prevents the operation from completing.
+    #[default]
+    Error,
+
+    /// The error indicates a likely problem, but the operation can still complete.
+    Warning,
+}
+
+impl Severity {
+    /// Returns the [`ReportKind`] used to display errors of this severity.
+    pub fn report_kind(self) -> ReportKind<'static> {
+        match self {
+            Self::Error => ReportKind::Error,
+            Self::Warning => ReportKind::Warning,
+        }
+    }
+}
+
 /// Represents any kind of error that can occur during some operation.
-pub trait ErrorKind: Debug + Send {
+///
+/// The [`Any`] supertrait allows a `dyn ErrorKind` to be downcast to its concrete type.
+pub trait ErrorKind: Any + Debug + Send {
+    /// Returns the severity of this error. By default, this is [`Severity::Error`].
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Returns the main message of this error, without any labels, help, or notes.
+    fn message(&self) -> String;
+
     /// Builds the report for this error.
     fn build_report(
         &self,
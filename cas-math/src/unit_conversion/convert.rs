@@ -1,7 +1,19 @@
+use rug::{ops::Pow, Integer, Rational};
 use super::unit::Unit;
 
+/// Returns the exact rational number `mantissa * 10^exponent`. This is used to define conversion
+/// factors exactly as they are written in decimal notation.
+pub(crate) fn decimal(mantissa: i64, exponent: i32) -> Rational {
+    let scale = Integer::from(10).pow(exponent.unsigned_abs());
+    if exponent >= 0 {
+        Rational::from(scale * mantissa)
+    } else {
+        Rational::from((mantissa, scale))
+    }
+}
+
 /// A trait implemented by all units, providing the information needed to convert between them.
-pub trait Convert {
+pub trait Convert: Sized + 'static {
     /// The base unit of this unit.
     ///
     /// The base unit is the unit in which conversions to other units of the same quantity are
@@ -10,21 +22,47 @@ pub trait Convert {
     /// long as conversions between each unit and the base unit are correctly defined.
     const BASE: Self;
 
-    /// Returns the conversion factor from this unit to the base unit, i.e. the value to multiply a
-    /// quantity in this unit by, in order to get a quantity in the base unit. If the base unit is
-    /// the same as this unit, then this function should return `1.0`.
+    /// Every unit of this quantity, in declaration order. This is useful for listing the available
+    /// units, such as for autocompletion.
+    const UNITS: &'static [Self];
+
+    /// Returns every unit of this quantity. See [`Convert::UNITS`].
+    fn units() -> &'static [Self] {
+        Self::UNITS
+    }
+
+    /// Returns the exact conversion factor from this unit to the base unit, i.e. the value to
+    /// multiply a quantity in this unit by, in order to get a quantity in the base unit. If the
+    /// base unit is the same as this unit, then this function should return `1`.
     ///
     /// For example, if the base unit is the meter, the conversion factor for a centimeter would be
-    /// `0.01`.
-    fn conversion_factor(&self) -> f64;
+    /// `1/100`.
+    fn conversion_factor_exact(&self) -> Rational;
+
+    /// Returns the conversion factor from this unit to the base unit as an [`f64`]. See
+    /// [`Convert::conversion_factor_exact`] for more information.
+    fn conversion_factor(&self) -> f64 {
+        self.conversion_factor_exact().to_f64()
+    }
+
+    /// Returns the exact offset from this unit to the base unit, i.e. the value to add to a
+    /// quantity in this unit, after multiplying by the conversion factor, in order to get a
+    /// quantity in the base unit.
+    ///
+    /// This is zero for most units, but is non-zero for units with a different zero point than the
+    /// base unit. For example, if the base unit is the kelvin, the offset for degrees Celsius would
+    /// be `273.15`.
+    fn conversion_offset_exact(&self) -> Rational {
+        Rational::new()
+    }
 
-    /// Defines the conversion factor from the base unit of this unit, to a specific unit in a
-    /// derived quantity kind.
+    /// Defines the exact conversion factor from the base unit of this unit, to a specific unit in
+    /// a derived quantity kind.
     ///
     /// For example, area is derived from length units squared, and volume is derived from length
     /// units cubed. This function would define the conversion factor from the base area / volume
     /// unit, to the given length unit squared / cubed.
-    fn conversion_factor_to(&self, _: impl Into<Unit>) -> Option<f64> {
+    fn conversion_factor_to(&self, _: impl Into<Unit>) -> Option<Rational> {
         None
     }
 }
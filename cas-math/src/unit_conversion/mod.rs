@@ -27,12 +27,28 @@
 pub mod convert;
 pub mod unit;
 
-use std::ops::Mul;
-pub use unit::{Area, ConversionError, Length, Quantity, Time, Unit};
+use rug::Rational;
+use std::{fmt::Display, ops::{Add, Div, Mul, MulAssign}};
+pub use unit::{
+    Area,
+    ConversionError,
+    Energy,
+    Force,
+    Length,
+    Power,
+    Pressure,
+    Quantity,
+    Speed,
+    Temperature,
+    Time,
+    Unit,
+    UnknownDerivedUnit,
+};
 
 /// A value and the unit it represents.
 ///
 /// This value can be converted to other units within the same quantity kind.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Measurement<T> {
     value: T,
     unit: Unit,
@@ -49,6 +65,18 @@ impl<T> Measurement<T> {
         &self.value
     }
 
+    /// Get a mutable reference to the value of this measurement.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Multiply the value of this measurement by the given factor in place. The unit is unchanged.
+    pub fn scale(&mut self, factor: T)
+        where T: MulAssign,
+    {
+        self.value *= factor;
+    }
+
     /// Get the unit of this measurement.
     pub fn unit(&self) -> &Unit {
         &self.unit
@@ -59,8 +87,67 @@ impl<T> Measurement<T> {
     /// In general, target units must be the same kind as the source unit, and with the same
     /// power. However, some conversions are allowed between different kinds of units, such as
     /// between cubed length units and volume units.
+    ///
+    /// Returns an error if the units have different zero points, such as temperature units, since
+    /// only the conversion factor is applied. Use [`Measurement::convert_absolute`] to convert an
+    /// absolute value between such units, or [`Measurement::convert_delta`] to convert a
+    /// difference between two values.
     pub fn convert(&self, target: impl Into<Unit>) -> Result<Self, ConversionError>
         where T: Copy + Mul<f64, Output = T>,
+    {
+        let target = target.into();
+        Ok(Self {
+            value: self.value * self.unit.conversion_factor_without_offset_exact(target)?.to_f64(),
+            unit: target,
+        })
+    }
+
+    /// Convert this measurement, representing an absolute value, to another unit.
+    ///
+    /// Unlike [`Measurement::convert`], units with different zero points, such as temperature
+    /// units, are offset accordingly. For example, an absolute temperature of 10 degrees Celsius
+    /// is 50 degrees Fahrenheit.
+    pub fn convert_absolute(&self, target: impl Into<Unit>) -> Result<Self, ConversionError>
+        where T: Copy + Mul<f64, Output = T> + Add<f64, Output = T>,
+    {
+        let target = target.into();
+        Ok(Self {
+            value: self.value * self.unit.conversion_factor(target)?
+                + self.unit.conversion_offset(target)?,
+            unit: target,
+        })
+    }
+
+    /// Returns a human-readable explanation of the conversion of this measurement to another unit,
+    /// showing the conversion factor used, such as `2 mi × 16093.44 = 32186.88 dm`.
+    ///
+    /// If the units have different zero points, the offset is shown as well, such as
+    /// `10 °C × 1.8 + 32 = 50 °F`.
+    pub fn explain_conversion(&self, target: impl Into<Unit>) -> Result<String, ConversionError>
+        where T: Copy + Display + Mul<f64, Output = T> + Add<f64, Output = T>,
+    {
+        let target = target.into();
+        let factor = self.unit.conversion_factor(target)?;
+        let offset = self.unit.conversion_offset(target)?;
+        let converted = self.convert_absolute(target)?;
+
+        let mut explanation = format!("{} {} × {}", self.value, self.unit, factor);
+        if offset > 0.0 {
+            explanation += &format!(" + {}", offset);
+        } else if offset < 0.0 {
+            explanation += &format!(" - {}", -offset);
+        }
+        explanation += &format!(" = {} {}", converted.value, converted.unit);
+        Ok(explanation)
+    }
+
+    /// Convert this measurement, representing a difference between two values, to another unit.
+    ///
+    /// Only the conversion factor between the units is applied. For example, a difference of 10
+    /// degrees Celsius is a difference of 18 degrees Fahrenheit, even though an absolute
+    /// temperature of 10 degrees Celsius is 50 degrees Fahrenheit.
+    pub fn convert_delta(&self, target: impl Into<Unit>) -> Result<Self, ConversionError>
+        where T: Copy + Mul<f64, Output = T>,
     {
         let target = target.into();
         Ok(Self {
@@ -68,6 +155,80 @@ impl<T> Measurement<T> {
             unit: target,
         })
     }
+
+    /// Convert this measurement to another unit, using the exact conversion factor between the
+    /// units.
+    ///
+    /// Unlike [`Measurement::convert`], no precision is lost when converting measurements of exact
+    /// types, such as [`Rational`] and [`rug::Integer`]. Converting an [`rug::Integer`] measurement
+    /// results in a [`Rational`] measurement.
+    ///
+    /// Like [`Measurement::convert`], this returns an error if the units have different zero
+    /// points. Use [`Measurement::convert_absolute_exact`] to convert between such units.
+    pub fn convert_exact(&self, target: impl Into<Unit>) -> Result<Measurement<T::Output>, ConversionError>
+        where T: Clone + Mul<Rational>,
+    {
+        let target = target.into();
+        Ok(Measurement {
+            value: self.value.clone() * self.unit.conversion_factor_without_offset_exact(target)?,
+            unit: target,
+        })
+    }
+
+    /// Convert this measurement, representing an absolute value, to another unit, using the exact
+    /// conversion factor and offset between the units.
+    ///
+    /// This is the exact counterpart of [`Measurement::convert_absolute`].
+    pub fn convert_absolute_exact(&self, target: impl Into<Unit>) -> Result<Measurement<T::Output>, ConversionError>
+        where
+            T: Clone + Mul<Rational>,
+            T::Output: Add<Rational, Output = T::Output>,
+    {
+        let target = target.into();
+        Ok(Measurement {
+            value: self.value.clone() * self.unit.conversion_factor_exact(target)?
+                + self.unit.conversion_offset_exact(target)?,
+            unit: target,
+        })
+    }
+
+    /// Multiply this measurement by another, producing a measurement of the derived quantity in
+    /// its base unit. For example, multiplying a force by a length produces an energy in joules.
+    pub fn product(&self, other: &Self) -> Result<Self, UnknownDerivedUnit>
+        where T: Copy + Mul<f64, Output = T> + Mul<Output = T>,
+    {
+        let (lhs, rhs, unit) = self.unit.product(other.unit)?;
+
+        // the derived unit is only found if both operands can be converted
+        let lhs = self.value * self.unit.conversion_factor(lhs).unwrap();
+        let rhs = other.value * other.unit.conversion_factor(rhs).unwrap();
+        Ok(Self { value: lhs * rhs, unit })
+    }
+
+    /// Divide this measurement by another, producing a measurement of the derived quantity in its
+    /// base unit. For example, dividing an energy by a time produces a power in watts.
+    pub fn quotient(&self, other: &Self) -> Result<Self, UnknownDerivedUnit>
+        where T: Copy + Mul<f64, Output = T> + Div<Output = T>,
+    {
+        let (lhs, rhs, unit) = self.unit.quotient(other.unit)?;
+
+        // the derived unit is only found if both operands can be converted
+        let lhs = self.value * self.unit.conversion_factor(lhs).unwrap();
+        let rhs = other.value * other.unit.conversion_factor(rhs).unwrap();
+        Ok(Self { value: lhs / rhs, unit })
+    }
+}
+
+impl Measurement<f64> {
+    /// Returns true if this measurement is within `epsilon` of the other measurement, after
+    /// converting the other measurement to the unit of this measurement.
+    ///
+    /// Returns an error if the other measurement cannot be converted to the unit of this
+    /// measurement.
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> Result<bool, ConversionError> {
+        let other = other.convert_absolute(self.unit)?;
+        Ok((self.value - other.value).abs() <= epsilon)
+    }
 }
 
 #[cfg(test)]
@@ -78,8 +239,10 @@ mod tests {
         afe_is_relative_eq,
         assert_float_relative_eq,
     };
+    use rug::Integer;
     use super::*;
-    use unit::{Area, Length, Time};
+    use convert::Convert;
+    use unit::{Area, Energy, Force, Length, Power, Pressure, Speed, Temperature, Time};
 
     #[test]
     fn identity_length() {
@@ -95,6 +258,33 @@ mod tests {
         assert_float_relative_eq!(*m2.value(), 32186.88);
     }
 
+    #[test]
+    fn exact_conversion_factor() {
+        let factor = Unit::new(Length::Mile).conversion_factor_exact(Length::Inch.into()).unwrap();
+        assert_eq!(factor, 63360);
+    }
+
+    #[test]
+    fn base_factor() {
+        assert_float_relative_eq!(Quantity::from(Length::Kilometer).base_factor(), 1000.0);
+        assert_float_relative_eq!(Quantity::from(Length::Centimeter).base_factor(), 0.01);
+        assert_float_relative_eq!(Quantity::from(Time::Hour).base_factor(), 3600.0);
+    }
+
+    #[test]
+    fn convert_exact_rational() {
+        let m = Measurement::new(Rational::from(1), Length::Mile);
+        let m2 = m.convert_exact(Length::Foot).unwrap();
+        assert_eq!(*m2.value(), 5280);
+    }
+
+    #[test]
+    fn convert_exact_integer() {
+        let m = Measurement::new(Integer::from(3), Length::Foot);
+        let m2 = m.convert_exact(Length::Inch).unwrap();
+        assert_eq!(*m2.value(), 36);
+    }
+
     #[test]
     fn convert_area_as_length() {
         let m = Measurement::new(2.0, Unit::with_power(Length::Meter, 2));
@@ -136,4 +326,187 @@ mod tests {
         let m2 = m.convert(Time::Decisecond).unwrap();
         assert_float_relative_eq!(*m2.value(), 1.220016816e11);
     }
+
+    #[test]
+    fn convert_temperature() {
+        let m = Measurement::new(10.0, Temperature::Celsius);
+        let m2 = m.convert_absolute(Temperature::Fahrenheit).unwrap();
+        assert_float_relative_eq!(*m2.value(), 50.0);
+
+        let m3 = m.convert_absolute(Temperature::Kelvin).unwrap();
+        assert_float_relative_eq!(*m3.value(), 283.15);
+
+        // converting by the conversion factor alone would give the wrong result
+        assert!(m.convert(Temperature::Fahrenheit).is_err());
+        assert!(m.convert_exact(Temperature::Fahrenheit).is_err());
+    }
+
+    #[test]
+    fn convert_temperature_delta() {
+        let m = Measurement::new(10.0, Temperature::Celsius);
+        let m2 = m.convert_delta(Temperature::Fahrenheit).unwrap();
+        assert_float_relative_eq!(*m2.value(), 18.0);
+    }
+
+    #[test]
+    fn convert_temperature_exact() {
+        let m = Measurement::new(Rational::from(-40), Temperature::Fahrenheit);
+        let m2 = m.convert_absolute_exact(Temperature::Celsius).unwrap();
+        assert_eq!(*m2.value(), -40);
+    }
+
+    #[test]
+    fn explain_conversion() {
+        let m = Measurement::new(2.0, Length::Mile);
+        assert_eq!(m.explain_conversion(Length::Decimeter).unwrap(), "2 mi × 16093.44 = 32186.88 dm");
+
+        let m = Measurement::new(10.0, Temperature::Celsius);
+        assert_eq!(m.explain_conversion(Temperature::Fahrenheit).unwrap(), "10 °C × 1.8 + 32 = 50 °F");
+
+        assert!(m.explain_conversion(Length::Meter).is_err());
+    }
+
+    #[test]
+    fn convert_energy() {
+        let m = Measurement::new(1.0, Energy::KilowattHour);
+        let m2 = m.convert(Energy::Joule).unwrap();
+        assert_float_relative_eq!(*m2.value(), 3_600_000.0);
+
+        let m = Measurement::new(Rational::from(1), Energy::KilowattHour);
+        let m2 = m.convert_exact(Energy::Joule).unwrap();
+        assert_eq!(*m2.value(), 3_600_000);
+    }
+
+    #[test]
+    fn convert_power() {
+        let m = Measurement::new(1.0, Power::Horsepower);
+        let m2 = m.convert(Power::Watt).unwrap();
+        assert_float_relative_eq!(*m2.value(), 745.7, 1e-4);
+    }
+
+    #[test]
+    fn derived_product() {
+        let force = Measurement::new(2.0, Force::Kilonewton);
+        let length = Measurement::new(3.0, Length::Meter);
+        let energy = force.product(&length).unwrap();
+        assert_eq!(*energy.unit(), Unit::new(Energy::Joule));
+        assert_float_relative_eq!(*energy.value(), 6000.0);
+
+        // multiplication is commutative
+        let energy = length.product(&force).unwrap();
+        assert_float_relative_eq!(*energy.value(), 6000.0);
+
+        let time = Measurement::new(1.0, Time::Second);
+        assert!(length.product(&time).is_err());
+    }
+
+    #[test]
+    fn derived_quotient() {
+        let energy = Measurement::new(1.0, Energy::KilowattHour);
+        let time = Measurement::new(1.0, Time::Hour);
+        let power = energy.quotient(&time).unwrap();
+        assert_eq!(*power.unit(), Unit::new(Power::Watt));
+        assert_float_relative_eq!(*power.value(), 1000.0);
+
+        let length = Measurement::new(2.0, Length::Meter);
+        let force = energy.quotient(&length).unwrap();
+        assert_eq!(*force.unit(), Unit::new(Force::Newton));
+        assert_float_relative_eq!(*force.value(), 1_800_000.0);
+
+        let time = energy.quotient(&Measurement::new(1.0, Power::Kilowatt)).unwrap();
+        assert_eq!(*time.unit(), Unit::new(Time::Second));
+        assert_float_relative_eq!(*time.value(), 3600.0);
+    }
+
+    #[test]
+    fn convert_pressure() {
+        let m = Measurement::new(1.0, Pressure::Bar);
+        let m2 = m.convert(Pressure::Pascal).unwrap();
+        assert_float_relative_eq!(*m2.value(), 100_000.0);
+
+        let m = Measurement::new(Rational::from(1), Pressure::Atmosphere);
+        let m2 = m.convert_exact(Pressure::Pascal).unwrap();
+        assert_eq!(*m2.value(), 101325);
+
+        let m = Measurement::new(1.0, Pressure::Atmosphere);
+        let m2 = m.convert(Pressure::MillimeterOfMercury).unwrap();
+        assert_float_relative_eq!(*m2.value(), 760.0, 1e-6);
+    }
+
+    #[test]
+    fn convert_psi() {
+        let m = Measurement::new(1.0, Pressure::PoundPerSquareInch);
+        let m2 = m.convert(Pressure::Pascal).unwrap();
+        assert_float_relative_eq!(*m2.value(), 6894.757293168361);
+    }
+
+    #[test]
+    fn derived_pressure() {
+        let force = Measurement::new(1.0, Force::PoundForce);
+        let area = Measurement::new(1.0, Unit::with_power(Length::Inch, 2));
+        let pressure = force.quotient(&area).unwrap();
+        assert_eq!(*pressure.unit(), Unit::new(Pressure::Pascal));
+        assert_float_relative_eq!(*pressure.value(), 6894.757293168361);
+
+        // area units are accepted as well as squared length units
+        let area = Measurement::new(1.0, Area::Centiare);
+        let pressure = Measurement::new(5.0, Force::Newton).quotient(&area).unwrap();
+        assert_float_relative_eq!(*pressure.value(), 5.0);
+    }
+
+    #[test]
+    fn convert_speed() {
+        let m = Measurement::new(100.0, Speed::KilometerPerHour);
+        let m2 = m.convert(Speed::MeterPerSecond).unwrap();
+        assert_float_relative_eq!(*m2.value(), 27.777777777777778);
+
+        let m = Measurement::new(60.0, Speed::MilePerHour);
+        let m2 = m.convert(Speed::KilometerPerHour).unwrap();
+        assert_float_relative_eq!(*m2.value(), 96.56064);
+
+        assert!(m.convert(Length::Meter).is_err());
+    }
+
+    #[test]
+    fn derived_speed() {
+        let length = Measurement::new(100.0, Length::Kilometer);
+        let time = Measurement::new(2.0, Time::Hour);
+        let speed = length.quotient(&time).unwrap();
+        assert_eq!(*speed.unit(), Unit::new(Speed::MeterPerSecond));
+        assert_float_relative_eq!(*speed.value(), 13.88888888888889);
+
+        let length = speed.product(&time).unwrap();
+        assert_eq!(*length.unit(), Unit::new(Length::Meter));
+        assert_float_relative_eq!(*length.value(), 100_000.0);
+    }
+
+    #[test]
+    fn mutate_value() {
+        let mut m = Measurement::new(2.0, Length::Meter);
+        *m.value_mut() += 3.0;
+        assert_float_relative_eq!(*m.value(), 5.0);
+
+        m.scale(4.0);
+        assert_float_relative_eq!(*m.value(), 20.0);
+        assert_eq!(*m.unit(), Unit::new(Length::Meter));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let m = Measurement::new(2.0, Length::Meter);
+        assert!(m.approx_eq(&Measurement::new(200.0, Length::Centimeter), 1e-9).unwrap());
+        assert!(!m.approx_eq(&Measurement::new(201.0, Length::Centimeter), 1e-9).unwrap());
+        assert!(m.approx_eq(&Measurement::new(2.0, Time::Second), 1e-9).is_err());
+    }
+
+    #[test]
+    fn list_units() {
+        let units = Length::units();
+        assert!(units.contains(&Length::Mile));
+        assert!(units.contains(&Length::Meter));
+        assert!(units.contains(&Length::Decimeter));
+
+        assert!(Quantity::units().any(|unit| unit == Quantity::Time(Time::Hour)));
+        assert!(Quantity::units().all(|unit| Quantity::is_abbreviation(&unit.to_string())));
+    }
 }
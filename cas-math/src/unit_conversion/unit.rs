@@ -1,5 +1,6 @@
+use rug::{ops::Pow, Rational};
 use std::{error::Error, fmt::{self, Display, Formatter}};
-use super::convert::Convert;
+use super::convert::{decimal, Convert};
 
 /// A unit of measurement.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -36,29 +37,137 @@ impl Unit {
     /// If this unit can be converted to the target unit, returns the conversion factor between
     /// them.
     pub fn conversion_factor(&self, target: Unit) -> Result<f64, ConversionError> {
+        self.conversion_factor_exact(target).map(|factor| factor.to_f64())
+    }
+
+    /// If this unit can be converted to the target unit, returns the exact conversion factor
+    /// between them as a [`Rational`].
+    pub fn conversion_factor_exact(&self, target: Unit) -> Result<Rational, ConversionError> {
         if self.power != target.power {
             return self.quantity.conversion_factor_to(target)
-                .or_else(|| target.quantity.conversion_factor_to(*self).map(|f| 1.0 / f))
+                .or_else(|| target.quantity.conversion_factor_to(*self).map(Rational::recip))
                 .ok_or(ConversionError { unit: *self, target });
         }
 
         let power = self.power as i32;
         match (self.quantity, target.quantity) {
             (Quantity::Length(l1), Quantity::Length(l2)) => {
-                Ok(l1.conversion_factor().powi(power)
-                    / l2.conversion_factor().powi(power))
+                Ok(l1.conversion_factor_exact().pow(power)
+                    / l2.conversion_factor_exact().pow(power))
             },
             (Quantity::Area(a1), Quantity::Area(a2)) => {
-                Ok(a1.conversion_factor().powi(power)
-                    / a2.conversion_factor().powi(power))
+                Ok(a1.conversion_factor_exact().pow(power)
+                    / a2.conversion_factor_exact().pow(power))
             },
             (Quantity::Time(t1), Quantity::Time(t2)) => {
-                Ok(t1.conversion_factor().powi(power)
-                    / t2.conversion_factor().powi(power))
+                Ok(t1.conversion_factor_exact().pow(power)
+                    / t2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Temperature(t1), Quantity::Temperature(t2)) => {
+                Ok(t1.conversion_factor_exact().pow(power)
+                    / t2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Force(f1), Quantity::Force(f2)) => {
+                Ok(f1.conversion_factor_exact().pow(power)
+                    / f2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Energy(e1), Quantity::Energy(e2)) => {
+                Ok(e1.conversion_factor_exact().pow(power)
+                    / e2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Power(p1), Quantity::Power(p2)) => {
+                Ok(p1.conversion_factor_exact().pow(power)
+                    / p2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Pressure(p1), Quantity::Pressure(p2)) => {
+                Ok(p1.conversion_factor_exact().pow(power)
+                    / p2.conversion_factor_exact().pow(power))
+            },
+            (Quantity::Speed(s1), Quantity::Speed(s2)) => {
+                Ok(s1.conversion_factor_exact().pow(power)
+                    / s2.conversion_factor_exact().pow(power))
             },
             _ => Err(ConversionError { unit: *self, target }),
         }
     }
+
+    /// Returns true if a measurement in this unit can be converted to the target unit.
+    pub fn can_convert(&self, target: Unit) -> bool {
+        self.conversion_factor_exact(target).is_ok()
+    }
+
+    /// Finds the derived unit of the product of a measurement in this unit and a measurement in
+    /// the `rhs` unit.
+    ///
+    /// Returns the units that each operand must be converted to before multiplying, followed by
+    /// the unit of the product.
+    pub fn product(&self, rhs: Unit) -> Result<(Unit, Unit, Unit), UnknownDerivedUnit> {
+        derived_units()
+            .into_iter()
+            .find_map(|(a, b, c)| {
+                if self.can_convert(a) && rhs.can_convert(b) {
+                    Some((a, b, c))
+                } else if self.can_convert(b) && rhs.can_convert(a) {
+                    Some((b, a, c))
+                } else {
+                    None
+                }
+            })
+            .ok_or(UnknownDerivedUnit { lhs: *self, rhs, op: '×' })
+    }
+
+    /// Finds the derived unit of the quotient of a measurement in this unit and a measurement in
+    /// the `rhs` unit.
+    ///
+    /// Returns the units that each operand must be converted to before dividing, followed by the
+    /// unit of the quotient.
+    pub fn quotient(&self, rhs: Unit) -> Result<(Unit, Unit, Unit), UnknownDerivedUnit> {
+        derived_units()
+            .into_iter()
+            .find_map(|(a, b, c)| {
+                if self.can_convert(c) && rhs.can_convert(b) {
+                    Some((c, b, a))
+                } else if self.can_convert(c) && rhs.can_convert(a) {
+                    Some((c, a, b))
+                } else {
+                    None
+                }
+            })
+            .ok_or(UnknownDerivedUnit { lhs: *self, rhs, op: '/' })
+    }
+
+    /// If this unit can be converted to the target unit by the conversion factor between them
+    /// alone, returns the exact conversion factor. Returns an error if the conversion also needs
+    /// an offset, i.e. the units have different zero points.
+    pub(crate) fn conversion_factor_without_offset_exact(&self, target: Unit) -> Result<Rational, ConversionError> {
+        if self.conversion_offset_exact(target)? == 0 {
+            self.conversion_factor_exact(target)
+        } else {
+            Err(ConversionError { unit: *self, target })
+        }
+    }
+
+    /// If this unit can be converted to the target unit, returns the offset to add after
+    /// multiplying by the conversion factor between them.
+    pub fn conversion_offset(&self, target: Unit) -> Result<f64, ConversionError> {
+        self.conversion_offset_exact(target).map(|offset| offset.to_f64())
+    }
+
+    /// If this unit can be converted to the target unit, returns the exact offset to add after
+    /// multiplying by the conversion factor between them.
+    ///
+    /// The offset is zero unless the units have different zero points, such as degrees Celsius and
+    /// kelvin. Since an offset is only meaningful for absolute values, it is always zero for units
+    /// with a power other than 1.
+    pub fn conversion_offset_exact(&self, target: Unit) -> Result<Rational, ConversionError> {
+        match (self.quantity, target.quantity) {
+            (Quantity::Temperature(t1), Quantity::Temperature(t2)) if self.power == 1 && target.power == 1 => {
+                Ok((t1.conversion_offset_exact() - t2.conversion_offset_exact())
+                    / t2.conversion_factor_exact())
+            },
+            _ => self.conversion_factor_exact(target).map(|_| Rational::new()),
+        }
+    }
 }
 
 /// Error returned if a unit cannot be converted to another.
@@ -97,6 +206,46 @@ impl Display for InvalidUnit {
 
 impl Error for InvalidUnit {}
 
+/// Error returned if two units cannot be multiplied or divided to produce a known derived unit.
+#[derive(Debug)]
+pub struct UnknownDerivedUnit {
+    /// The left-hand side unit.
+    lhs: Unit,
+
+    /// The right-hand side unit.
+    rhs: Unit,
+
+    /// The operation that was attempted, either `×` or `/`.
+    op: char,
+}
+
+impl Display for UnknownDerivedUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f,
+            "no known unit for `{} {} {}`",
+            self.lhs, self.op, self.rhs
+        )
+    }
+}
+
+impl Error for UnknownDerivedUnit {}
+
+/// Relations between the base units of different quantities, used to multiply and divide
+/// measurements. Each entry `(a, b, c)` states that `a × b = c`, which also implies `c / b = a`
+/// and `c / a = b`.
+fn derived_units() -> [(Unit, Unit, Unit); 4] {
+    [
+        // N × m = J
+        (Force::BASE.into(), Length::BASE.into(), Energy::BASE.into()),
+        // W × s = J
+        (Power::BASE.into(), Time::BASE.into(), Energy::BASE.into()),
+        // Pa × m² = N
+        (Pressure::BASE.into(), Unit::with_power(Length::BASE, 2), Force::BASE.into()),
+        // m/s × s = m
+        (Speed::BASE.into(), Time::BASE.into(), Length::BASE.into()),
+    ]
+}
+
 /// A type of quantity, such as length, mass, volume, etc.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -104,6 +253,12 @@ pub enum Quantity {
     Length(Length),
     Area(Area),
     Time(Time),
+    Temperature(Temperature),
+    Force(Force),
+    Energy(Energy),
+    Power(Power),
+    Pressure(Pressure),
+    Speed(Speed),
 }
 
 impl Display for Quantity {
@@ -112,6 +267,12 @@ impl Display for Quantity {
             Quantity::Length(l) => write!(f, "{}", l),
             Quantity::Area(a) => write!(f, "{}", a),
             Quantity::Time(t) => write!(f, "{}", t),
+            Quantity::Temperature(t) => write!(f, "{}", t),
+            Quantity::Force(force) => write!(f, "{}", force),
+            Quantity::Energy(e) => write!(f, "{}", e),
+            Quantity::Power(p) => write!(f, "{}", p),
+            Quantity::Pressure(p) => write!(f, "{}", p),
+            Quantity::Speed(s) => write!(f, "{}", s),
         }
     }
 }
@@ -123,6 +284,12 @@ impl TryFrom<&str> for Quantity {
         Length::try_from(value).map(Quantity::Length)
             .or_else(|_| Area::try_from(value).map(Quantity::Area))
             .or_else(|_| Time::try_from(value).map(Quantity::Time))
+            .or_else(|_| Temperature::try_from(value).map(Quantity::Temperature))
+            .or_else(|_| Force::try_from(value).map(Quantity::Force))
+            .or_else(|_| Energy::try_from(value).map(Quantity::Energy))
+            .or_else(|_| Power::try_from(value).map(Quantity::Power))
+            .or_else(|_| Pressure::try_from(value).map(Quantity::Pressure))
+            .or_else(|_| Speed::try_from(value).map(Quantity::Speed))
     }
 }
 
@@ -133,12 +300,54 @@ impl From<Quantity> for Unit {
 }
 
 impl Quantity {
-    fn conversion_factor_to(&self, target: impl Into<Unit>) -> Option<f64> {
+    /// Returns an iterator over every unit of every kind of quantity, such as [`Length::Meter`]
+    /// and [`Time::Hour`].
+    pub fn units() -> impl Iterator<Item = Quantity> {
+        Length::UNITS.iter().copied().map(Quantity::Length)
+            .chain(Area::UNITS.iter().copied().map(Quantity::Area))
+            .chain(Time::UNITS.iter().copied().map(Quantity::Time))
+            .chain(Temperature::UNITS.iter().copied().map(Quantity::Temperature))
+            .chain(Force::UNITS.iter().copied().map(Quantity::Force))
+            .chain(Energy::UNITS.iter().copied().map(Quantity::Energy))
+            .chain(Power::UNITS.iter().copied().map(Quantity::Power))
+            .chain(Pressure::UNITS.iter().copied().map(Quantity::Pressure))
+            .chain(Speed::UNITS.iter().copied().map(Quantity::Speed))
+    }
+
+    /// Returns true if the given string is the abbreviation of a unit, such as `km` or `hr`.
+    pub fn is_abbreviation(value: &str) -> bool {
+        Self::try_from(value).is_ok()
+    }
+
+    /// Returns the factor to multiply a measurement in this unit by, in order to get a measurement
+    /// in the base unit of the quantity kind (see [`Convert::BASE`]). For example, the base unit of
+    /// length is the meter, so the base factor of a kilometer is `1000.0`.
+    pub fn base_factor(&self) -> f64 {
+        match self {
+            Quantity::Length(l) => l.conversion_factor(),
+            Quantity::Area(a) => a.conversion_factor(),
+            Quantity::Time(t) => t.conversion_factor(),
+            Quantity::Temperature(t) => t.conversion_factor(),
+            Quantity::Force(f) => f.conversion_factor(),
+            Quantity::Energy(e) => e.conversion_factor(),
+            Quantity::Power(p) => p.conversion_factor(),
+            Quantity::Pressure(p) => p.conversion_factor(),
+            Quantity::Speed(s) => s.conversion_factor(),
+        }
+    }
+
+    fn conversion_factor_to(&self, target: impl Into<Unit>) -> Option<Rational> {
         let target = target.into();
         match self {
             Quantity::Length(l) => l.conversion_factor_to(target),
             Quantity::Area(a) => a.conversion_factor_to(target),
             Quantity::Time(t) => t.conversion_factor_to(target),
+            Quantity::Temperature(t) => t.conversion_factor_to(target),
+            Quantity::Force(f) => f.conversion_factor_to(target),
+            Quantity::Energy(e) => e.conversion_factor_to(target),
+            Quantity::Power(p) => p.conversion_factor_to(target),
+            Quantity::Pressure(p) => p.conversion_factor_to(target),
+            Quantity::Speed(s) => s.conversion_factor_to(target),
         }
     }
 }
@@ -235,26 +444,46 @@ impl Display for Length {
 impl Convert for Length {
     const BASE: Self = Length::Meter;
 
-    fn conversion_factor(&self) -> f64 {
+    const UNITS: &'static [Self] = &[
+        Length::Parsec,
+        Length::LightYear,
+        Length::AstronomicalUnit,
+        Length::NauticalMile,
+        Length::Kilometer,
+        Length::Meter,
+        Length::Decimeter,
+        Length::Centimeter,
+        Length::Millimeter,
+        Length::Micrometer,
+        Length::Nanometer,
+        Length::Angstrom,
+        Length::Picometer,
+        Length::Mile,
+        Length::Yard,
+        Length::Foot,
+        Length::Inch,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
         match self {
-            Length::Parsec => 3.085677581e16,
-            Length::LightYear => 9.4607304725808e15,
-            Length::AstronomicalUnit => 1.495978707e11,
-            Length::NauticalMile => 1852.0,
-            Length::Kilometer => 1000.0,
-            Length::Meter => 1.0,
-            Length::Decimeter => 0.1,
-            Length::Centimeter => 0.01,
-            Length::Millimeter => 0.001,
-            Length::Micrometer => 1e-6,
-            Length::Nanometer => 1e-9,
-            Length::Angstrom => 1e-10,
-            Length::Picometer => 1e-12,
+            Length::Parsec => decimal(3085677581, 7),
+            Length::LightYear => decimal(94607304725808, 2),
+            Length::AstronomicalUnit => decimal(1495978707, 2),
+            Length::NauticalMile => decimal(1852, 0),
+            Length::Kilometer => decimal(1, 3),
+            Length::Meter => decimal(1, 0),
+            Length::Decimeter => decimal(1, -1),
+            Length::Centimeter => decimal(1, -2),
+            Length::Millimeter => decimal(1, -3),
+            Length::Micrometer => decimal(1, -6),
+            Length::Nanometer => decimal(1, -9),
+            Length::Angstrom => decimal(1, -10),
+            Length::Picometer => decimal(1, -12),
 
-            Length::Mile => 1609.344,
-            Length::Yard => 0.9144,
-            Length::Foot => 0.3048,
-            Length::Inch => 0.0254,
+            Length::Mile => decimal(1609344, -3),
+            Length::Yard => decimal(9144, -4),
+            Length::Foot => decimal(3048, -4),
+            Length::Inch => decimal(254, -4),
         }
     }
 }
@@ -325,28 +554,38 @@ impl Display for Area {
 impl Convert for Area {
     const BASE: Self = Area::Are;
 
-    fn conversion_factor(&self) -> f64 {
+    const UNITS: &'static [Self] = &[
+        Area::Hectare,
+        Area::Decare,
+        Area::Are,
+        Area::Deciare,
+        Area::Centiare,
+        Area::Barn,
+        Area::Acre,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
         match self {
-            Area::Hectare => 100.0,
-            Area::Decare => 10.0,
-            Area::Are => 1.0,
-            Area::Deciare => 0.1,
-            Area::Centiare => 0.01,
-            Area::Barn => 1e-30,
+            Area::Hectare => decimal(1, 2),
+            Area::Decare => decimal(1, 1),
+            Area::Are => decimal(1, 0),
+            Area::Deciare => decimal(1, -1),
+            Area::Centiare => decimal(1, -2),
+            Area::Barn => decimal(1, -30),
 
-            Area::Acre => 40.468564224,
+            Area::Acre => decimal(40468564224, -9),
         }
     }
 
-    fn conversion_factor_to(&self, target: impl Into<Unit>) -> Option<f64> {
+    fn conversion_factor_to(&self, target: impl Into<Unit>) -> Option<Rational> {
         let target = target.into();
         if matches!(target.quantity, Quantity::Length(_)) && target.power == 2 {
             Some(
                 // convert from self to base length unit squared
-                self.conversion_factor() * 100.0
+                self.conversion_factor_exact() * 100
 
                 // convert from base length unit squared to target
-                * Unit::with_power(Length::BASE, 2).conversion_factor(target).unwrap()
+                * Unit::with_power(Length::BASE, 2).conversion_factor_exact(target).unwrap()
             )
         } else {
             None
@@ -437,22 +676,522 @@ impl Display for Time {
 impl Convert for Time {
     const BASE: Self = Time::Second;
 
-    fn conversion_factor(&self) -> f64 {
+    const UNITS: &'static [Self] = &[
+        Time::Century,
+        Time::Decade,
+        Time::Year,
+        Time::Week,
+        Time::Day,
+        Time::Hour,
+        Time::Minute,
+        Time::Second,
+        Time::Decisecond,
+        Time::Centisecond,
+        Time::Millisecond,
+        Time::Microsecond,
+        Time::Nanosecond,
+        Time::Picosecond,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Time::Century => decimal(315576, 4),
+            Time::Decade => decimal(315576, 3),
+            Time::Year => decimal(315576, 2),
+            Time::Week => decimal(604800, 0),
+            Time::Day => decimal(86400, 0),
+            Time::Hour => decimal(3600, 0),
+            Time::Minute => decimal(60, 0),
+            Time::Second => decimal(1, 0),
+            Time::Decisecond => decimal(1, -1),
+            Time::Centisecond => decimal(1, -2),
+            Time::Millisecond => decimal(1, -3),
+            Time::Microsecond => decimal(1, -6),
+            Time::Nanosecond => decimal(1, -9),
+            Time::Picosecond => decimal(1, -12),
+        }
+    }
+}
+
+/// A unit of temperature.
+///
+/// Unlike other units, temperature units can have different zero points, so converting an
+/// absolute temperature involves an offset in addition to a conversion factor. Use
+/// [`Measurement::convert_absolute`] to convert an absolute temperature, and
+/// [`Measurement::convert_delta`] to convert a temperature difference, which ignores the offset.
+///
+/// [`Measurement::convert_absolute`]: super::Measurement::convert_absolute
+/// [`Measurement::convert_delta`]: super::Measurement::convert_delta
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Temperature {
+    Kelvin,
+    Celsius,
+    Fahrenheit,
+}
+
+impl From<Temperature> for Unit {
+    fn from(t: Temperature) -> Self {
+        Self::new(Quantity::Temperature(t))
+    }
+}
+
+impl From<Temperature> for Quantity {
+    fn from(t: Temperature) -> Self {
+        Self::Temperature(t)
+    }
+}
+
+impl TryFrom<&str> for Temperature {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "K" => Ok(Temperature::Kelvin),
+            "°C" | "C" => Ok(Temperature::Celsius),
+            "°F" | "F" => Ok(Temperature::Fahrenheit),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Temperature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Temperature::Kelvin => write!(f, "K"),
+            Temperature::Celsius => write!(f, "°C"),
+            Temperature::Fahrenheit => write!(f, "°F"),
+        }
+    }
+}
+
+impl Convert for Temperature {
+    const BASE: Self = Temperature::Kelvin;
+
+    const UNITS: &'static [Self] = &[
+        Temperature::Kelvin,
+        Temperature::Celsius,
+        Temperature::Fahrenheit,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Temperature::Kelvin => decimal(1, 0),
+            Temperature::Celsius => decimal(1, 0),
+            Temperature::Fahrenheit => Rational::from((5, 9)),
+        }
+    }
+
+    fn conversion_offset_exact(&self) -> Rational {
+        match self {
+            Temperature::Kelvin => Rational::new(),
+            Temperature::Celsius => decimal(27315, -2),
+            // 459.67 * 5 / 9
+            Temperature::Fahrenheit => Rational::from((45967, 180)),
+        }
+    }
+}
+
+/// A unit of force.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Force {
+    Kilonewton,
+    Newton,
+    Dyne,
+    PoundForce,
+}
+
+impl From<Force> for Unit {
+    fn from(f: Force) -> Self {
+        Self::new(Quantity::Force(f))
+    }
+}
+
+impl From<Force> for Quantity {
+    fn from(f: Force) -> Self {
+        Self::Force(f)
+    }
+}
+
+impl TryFrom<&str> for Force {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "kN" => Ok(Force::Kilonewton),
+            "N" => Ok(Force::Newton),
+            "dyn" => Ok(Force::Dyne),
+            "lbf" => Ok(Force::PoundForce),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Force {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Force::Kilonewton => write!(f, "kN"),
+            Force::Newton => write!(f, "N"),
+            Force::Dyne => write!(f, "dyn"),
+            Force::PoundForce => write!(f, "lbf"),
+        }
+    }
+}
+
+impl Convert for Force {
+    const BASE: Self = Force::Newton;
+
+    const UNITS: &'static [Self] = &[
+        Force::Kilonewton,
+        Force::Newton,
+        Force::Dyne,
+        Force::PoundForce,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Force::Kilonewton => decimal(1, 3),
+            Force::Newton => decimal(1, 0),
+            Force::Dyne => decimal(1, -5),
+            Force::PoundForce => decimal(44482216152605, -13),
+        }
+    }
+}
+
+/// A unit of energy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Energy {
+    Megajoule,
+    Kilojoule,
+    Joule,
+    KilowattHour,
+    WattHour,
+    Kilocalorie,
+    Calorie,
+    BritishThermalUnit,
+    Electronvolt,
+}
+
+impl From<Energy> for Unit {
+    fn from(e: Energy) -> Self {
+        Self::new(Quantity::Energy(e))
+    }
+}
+
+impl From<Energy> for Quantity {
+    fn from(e: Energy) -> Self {
+        Self::Energy(e)
+    }
+}
+
+impl TryFrom<&str> for Energy {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "MJ" => Ok(Energy::Megajoule),
+            "kJ" => Ok(Energy::Kilojoule),
+            "J" => Ok(Energy::Joule),
+            "kWh" => Ok(Energy::KilowattHour),
+            "Wh" => Ok(Energy::WattHour),
+            "kcal" => Ok(Energy::Kilocalorie),
+            "cal" => Ok(Energy::Calorie),
+            "BTU" | "Btu" => Ok(Energy::BritishThermalUnit),
+            "eV" => Ok(Energy::Electronvolt),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Energy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Energy::Megajoule => write!(f, "MJ"),
+            Energy::Kilojoule => write!(f, "kJ"),
+            Energy::Joule => write!(f, "J"),
+            Energy::KilowattHour => write!(f, "kWh"),
+            Energy::WattHour => write!(f, "Wh"),
+            Energy::Kilocalorie => write!(f, "kcal"),
+            Energy::Calorie => write!(f, "cal"),
+            Energy::BritishThermalUnit => write!(f, "BTU"),
+            Energy::Electronvolt => write!(f, "eV"),
+        }
+    }
+}
+
+impl Convert for Energy {
+    const BASE: Self = Energy::Joule;
+
+    const UNITS: &'static [Self] = &[
+        Energy::Megajoule,
+        Energy::Kilojoule,
+        Energy::Joule,
+        Energy::KilowattHour,
+        Energy::WattHour,
+        Energy::Kilocalorie,
+        Energy::Calorie,
+        Energy::BritishThermalUnit,
+        Energy::Electronvolt,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Energy::Megajoule => decimal(1, 6),
+            Energy::Kilojoule => decimal(1, 3),
+            Energy::Joule => decimal(1, 0),
+            Energy::KilowattHour => decimal(36, 5),
+            Energy::WattHour => decimal(36, 2),
+            Energy::Kilocalorie => decimal(4184, 0),
+            Energy::Calorie => decimal(4184, -3),
+            Energy::BritishThermalUnit => decimal(105505585262, -8),
+            Energy::Electronvolt => decimal(1602176634, -28),
+        }
+    }
+}
+
+/// A unit of power.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Power {
+    Megawatt,
+    Kilowatt,
+    Watt,
+    Horsepower,
+}
+
+impl From<Power> for Unit {
+    fn from(p: Power) -> Self {
+        Self::new(Quantity::Power(p))
+    }
+}
+
+impl From<Power> for Quantity {
+    fn from(p: Power) -> Self {
+        Self::Power(p)
+    }
+}
+
+impl TryFrom<&str> for Power {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "MW" => Ok(Power::Megawatt),
+            "kW" => Ok(Power::Kilowatt),
+            "W" => Ok(Power::Watt),
+            "hp" => Ok(Power::Horsepower),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Power {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Power::Megawatt => write!(f, "MW"),
+            Power::Kilowatt => write!(f, "kW"),
+            Power::Watt => write!(f, "W"),
+            Power::Horsepower => write!(f, "hp"),
+        }
+    }
+}
+
+impl Convert for Power {
+    const BASE: Self = Power::Watt;
+
+    const UNITS: &'static [Self] = &[
+        Power::Megawatt,
+        Power::Kilowatt,
+        Power::Watt,
+        Power::Horsepower,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Power::Megawatt => decimal(1, 6),
+            Power::Kilowatt => decimal(1, 3),
+            Power::Watt => decimal(1, 0),
+            // 550 ft⋅lbf/s
+            Power::Horsepower => decimal(74569987158227022, -14),
+        }
+    }
+}
+
+/// A unit of pressure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Pressure {
+    Megapascal,
+    Kilopascal,
+    Pascal,
+    Bar,
+    Millibar,
+    Atmosphere,
+    Torr,
+    MillimeterOfMercury,
+    PoundPerSquareInch,
+}
+
+impl From<Pressure> for Unit {
+    fn from(p: Pressure) -> Self {
+        Self::new(Quantity::Pressure(p))
+    }
+}
+
+impl From<Pressure> for Quantity {
+    fn from(p: Pressure) -> Self {
+        Self::Pressure(p)
+    }
+}
+
+impl TryFrom<&str> for Pressure {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "MPa" => Ok(Pressure::Megapascal),
+            "kPa" => Ok(Pressure::Kilopascal),
+            "Pa" => Ok(Pressure::Pascal),
+            "bar" => Ok(Pressure::Bar),
+            "mbar" => Ok(Pressure::Millibar),
+            "atm" => Ok(Pressure::Atmosphere),
+            "Torr" | "torr" => Ok(Pressure::Torr),
+            "mmHg" => Ok(Pressure::MillimeterOfMercury),
+            "psi" => Ok(Pressure::PoundPerSquareInch),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Pressure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Pressure::Megapascal => write!(f, "MPa"),
+            Pressure::Kilopascal => write!(f, "kPa"),
+            Pressure::Pascal => write!(f, "Pa"),
+            Pressure::Bar => write!(f, "bar"),
+            Pressure::Millibar => write!(f, "mbar"),
+            Pressure::Atmosphere => write!(f, "atm"),
+            Pressure::Torr => write!(f, "Torr"),
+            Pressure::MillimeterOfMercury => write!(f, "mmHg"),
+            Pressure::PoundPerSquareInch => write!(f, "psi"),
+        }
+    }
+}
+
+impl Convert for Pressure {
+    const BASE: Self = Pressure::Pascal;
+
+    const UNITS: &'static [Self] = &[
+        Pressure::Megapascal,
+        Pressure::Kilopascal,
+        Pressure::Pascal,
+        Pressure::Bar,
+        Pressure::Millibar,
+        Pressure::Atmosphere,
+        Pressure::Torr,
+        Pressure::MillimeterOfMercury,
+        Pressure::PoundPerSquareInch,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
+        match self {
+            Pressure::Megapascal => decimal(1, 6),
+            Pressure::Kilopascal => decimal(1, 3),
+            Pressure::Pascal => decimal(1, 0),
+            Pressure::Bar => decimal(1, 5),
+            Pressure::Millibar => decimal(1, 2),
+            Pressure::Atmosphere => decimal(101325, 0),
+            // 1/760 atm
+            Pressure::Torr => Rational::from((101325, 760)),
+            Pressure::MillimeterOfMercury => decimal(133322387415, -9),
+            // lbf/in²
+            Pressure::PoundPerSquareInch => Force::PoundForce.conversion_factor_exact()
+                / Length::Inch.conversion_factor_exact().square(),
+        }
+    }
+}
+
+/// A unit of speed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Speed {
+    KilometerPerSecond,
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    FootPerSecond,
+    Knot,
+}
+
+impl From<Speed> for Unit {
+    fn from(s: Speed) -> Self {
+        Self::new(Quantity::Speed(s))
+    }
+}
+
+impl From<Speed> for Quantity {
+    fn from(s: Speed) -> Self {
+        Self::Speed(s)
+    }
+}
+
+impl TryFrom<&str> for Speed {
+    type Error = InvalidUnit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "km/s" => Ok(Speed::KilometerPerSecond),
+            "m/s" => Ok(Speed::MeterPerSecond),
+            "km/h" | "kph" => Ok(Speed::KilometerPerHour),
+            "mph" => Ok(Speed::MilePerHour),
+            "ft/s" | "fps" => Ok(Speed::FootPerSecond),
+            "kn" => Ok(Speed::Knot),
+            _ => Err(InvalidUnit { unit: value.to_owned() }),
+        }
+    }
+}
+
+impl Display for Speed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Speed::KilometerPerSecond => write!(f, "km/s"),
+            Speed::MeterPerSecond => write!(f, "m/s"),
+            Speed::KilometerPerHour => write!(f, "km/h"),
+            Speed::MilePerHour => write!(f, "mph"),
+            Speed::FootPerSecond => write!(f, "ft/s"),
+            Speed::Knot => write!(f, "kn"),
+        }
+    }
+}
+
+impl Convert for Speed {
+    const BASE: Self = Speed::MeterPerSecond;
+
+    const UNITS: &'static [Self] = &[
+        Speed::KilometerPerSecond,
+        Speed::MeterPerSecond,
+        Speed::KilometerPerHour,
+        Speed::MilePerHour,
+        Speed::FootPerSecond,
+        Speed::Knot,
+    ];
+
+    fn conversion_factor_exact(&self) -> Rational {
         match self {
-            Time::Century => 3.15576e9,
-            Time::Decade => 3.15576e8,
-            Time::Year => 3.15576e7,
-            Time::Week => 604800.0,
-            Time::Day => 86400.0,
-            Time::Hour => 3600.0,
-            Time::Minute => 60.0,
-            Time::Second => 1.0,
-            Time::Decisecond => 0.1,
-            Time::Centisecond => 0.01,
-            Time::Millisecond => 0.001,
-            Time::Microsecond => 1e-6,
-            Time::Nanosecond => 1e-9,
-            Time::Picosecond => 1e-12,
+            Speed::KilometerPerSecond => Length::Kilometer.conversion_factor_exact()
+                / Time::Second.conversion_factor_exact(),
+            Speed::MeterPerSecond => decimal(1, 0),
+            Speed::KilometerPerHour => Length::Kilometer.conversion_factor_exact()
+                / Time::Hour.conversion_factor_exact(),
+            Speed::MilePerHour => Length::Mile.conversion_factor_exact()
+                / Time::Hour.conversion_factor_exact(),
+            Speed::FootPerSecond => Length::Foot.conversion_factor_exact()
+                / Time::Second.conversion_factor_exact(),
+            Speed::Knot => Length::NauticalMile.conversion_factor_exact()
+                / Time::Hour.conversion_factor_exact(),
         }
     }
 }
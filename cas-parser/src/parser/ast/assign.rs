@@ -1,10 +1,19 @@
 use crate::{
     parser::{
         ast::{expr::Expr, helper::ParenDelimited, literal::{Literal, LitSym}},
-        error::{kind::{CompoundAssignmentInHeader, InvalidAssignmentLhs, InvalidCompoundAssignmentLhs}, Error},
+        error::{
+            kind::{
+                CompoundAssignmentInHeader,
+                DefaultReferencesLaterParam,
+                ExpectedSymbolName,
+                InvalidAssignmentLhs,
+                InvalidCompoundAssignmentLhs,
+            },
+            Error,
+        },
         fmt::Latex,
         garbage::Garbage,
-        token::op::AssignOp,
+        token::{op::AssignOp, Comma},
         Parse,
         Parser,
         ParseResult,
@@ -18,7 +27,7 @@ use serde::{Deserialize, Serialize};
 
 /// A parameter of a function declaration, such as `x` or `y = 1` in the declaration `f(x, y = 1) =
 /// x^y`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Param {
     /// A parameter with no default value, such as `x` in `f(x) = x^2`.
@@ -82,7 +91,7 @@ impl Latex for Param {
 /// optional default values, like in `f(x, y = 1)`. When a function with this header is called, the
 /// default values are used (i.e. `y = 1`), unless the caller provides their own values (`f(2,
 /// 3)`).
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FuncHeader {
     /// The name of the function.
@@ -108,10 +117,32 @@ impl<'source> Parse<'source> for FuncHeader {
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
         let name = input.try_parse::<LitSym>().forward_errors(recoverable_errors)?;
-        let surrounded = input.try_parse::<ParenDelimited<_>>().forward_errors(recoverable_errors)?;
+        let surrounded = input.try_parse::<ParenDelimited<Param>>().forward_errors(recoverable_errors)?;
+        let params = surrounded.value.values;
+
+        // default values are evaluated in order, so they cannot reference parameters declared after
+        // them
+        for (i, param) in params.iter().enumerate() {
+            let Param::Default(_, default) = param else {
+                continue;
+            };
+
+            for expr in default.post_order_iter() {
+                let Expr::Literal(Literal::Symbol(symbol)) = expr else {
+                    continue;
+                };
+
+                if let Some(later) = params[i + 1..].iter().find(|p| p.symbol().name == symbol.name) {
+                    recoverable_errors.push(Error::new(
+                        vec![symbol.span.clone(), later.symbol().span.clone()],
+                        DefaultReferencesLaterParam { name: symbol.name.clone() },
+                    ));
+                }
+            }
+        }
 
         let span = name.span.start..surrounded.close.span.end;
-        Ok(Self { name, params: surrounded.value.values, span })
+        Ok(Self { name, params, span })
     }
 }
 
@@ -142,8 +173,24 @@ impl Latex for FuncHeader {
     }
 }
 
-/// An assignment target, such as `x` or `f(x)`.
-#[derive(Debug, Clone, PartialEq)]
+/// Parses two or more symbols delimited by commas, such as `a, b` in `a, b = divmod(7, 2)`.
+fn parse_destructure(
+    input: &mut Parser,
+    recoverable_errors: &mut Vec<Error>,
+) -> Result<Vec<LitSym>, Vec<Error>> {
+    let mut symbols = vec![input.try_parse::<LitSym>().forward_errors(recoverable_errors)?];
+    input.try_parse::<Comma>().forward_errors(recoverable_errors)?;
+    loop {
+        symbols.push(input.try_parse::<LitSym>().forward_errors(recoverable_errors)?);
+        if input.try_parse::<Comma>().forward_errors(recoverable_errors).is_err() {
+            break;
+        }
+    }
+    Ok(symbols)
+}
+
+/// An assignment target, such as `x`, `f(x)`, or `a, b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AssignTarget {
     /// A symbol, such as `x`.
@@ -151,6 +198,10 @@ pub enum AssignTarget {
 
     /// A function, such as `f(x)`.
     Func(FuncHeader),
+
+    /// Two or more symbols that a tuple is destructured into, such as `a, b` in
+    /// `a, b = divmod(7, 2)`. The parentheses may also be written, as in `(a, b) = divmod(7, 2)`.
+    Destructure(Vec<LitSym>),
 }
 
 impl AssignTarget {
@@ -159,6 +210,9 @@ impl AssignTarget {
         match self {
             AssignTarget::Symbol(symbol) => symbol.span.clone(),
             AssignTarget::Func(func) => func.span(),
+            AssignTarget::Destructure(symbols) => {
+                symbols[0].span.start..symbols[symbols.len() - 1].span.end
+            },
         }
     }
 
@@ -168,6 +222,18 @@ impl AssignTarget {
         let op_span = op.span.clone();
         match expr {
             Expr::Literal(Literal::Symbol(symbol)) => ParseResult::Ok(AssignTarget::Symbol(symbol)),
+            Expr::Literal(Literal::Tuple(tuple))
+                if tuple.values.len() > 1
+                    && tuple.values.iter().all(|value| matches!(value, Expr::Literal(Literal::Symbol(_))))
+                    && !op.is_compound() => {
+                let symbols = tuple.values.into_iter()
+                    .map(|value| match value {
+                        Expr::Literal(Literal::Symbol(symbol)) => symbol,
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                ParseResult::Ok(AssignTarget::Destructure(symbols))
+            },
             Expr::Call(call) => {
                 let spans = vec![call.span.clone(), op_span.clone()];
                 let error = if op.is_compound() {
@@ -201,6 +267,14 @@ impl<'source> Parse<'source> for AssignTarget {
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
         let _ = return_if_ok!(input.try_parse().map(AssignTarget::Func).forward_errors(recoverable_errors));
+        if !input.state().in_comma_list {
+            let _ = return_if_ok!(input.try_parse_with_fn(|input| {
+                let mut recoverable_errors = Vec::new();
+                parse_destructure(input, &mut recoverable_errors)
+                    .map(|symbols| (AssignTarget::Destructure(symbols), recoverable_errors))
+                    .into()
+            }).forward_errors(recoverable_errors));
+        }
         input.try_parse().map(AssignTarget::Symbol).forward_errors(recoverable_errors)
     }
 }
@@ -210,6 +284,15 @@ impl std::fmt::Display for AssignTarget {
         match self {
             AssignTarget::Symbol(symbol) => write!(f, "{}", symbol),
             AssignTarget::Func(func) => write!(f, "{}", func),
+            AssignTarget::Destructure(symbols) => {
+                for (i, symbol) in symbols.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", symbol)?;
+                }
+                Ok(())
+            },
         }
     }
 }
@@ -219,12 +302,21 @@ impl Latex for AssignTarget {
         match self {
             AssignTarget::Symbol(symbol) => symbol.fmt_latex(f),
             AssignTarget::Func(func) => func.fmt_latex(f),
+            AssignTarget::Destructure(symbols) => {
+                for (i, symbol) in symbols.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    symbol.fmt_latex(f)?;
+                }
+                Ok(())
+            },
         }
     }
 }
 
 /// An assignment of a variable or function, such as `x = 1` or `f(x) = x^2`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Assign {
     /// The target to assign to.
@@ -263,14 +355,56 @@ impl Assign {
     }
 }
 
+/// Parses a keyword used as the target of an assignment, such as `not` in `not = 3`, reporting a
+/// recoverable [`ExpectedSymbolName`] error for it.
+///
+/// Returns [`None`] if the input does not start with a keyword followed by an assignment operator.
+fn parse_keyword_target(
+    input: &mut Parser,
+    recoverable_errors: &mut Vec<Error>,
+) -> Option<AssignTarget> {
+    let mut lookahead = input.clone();
+    let token = lookahead.next_token();
+    if !token.is_keyword() {
+        return None;
+    }
+    if lookahead.clone().try_parse::<AssignOp>().is_err() {
+        return None;
+    }
+
+    input.set_cursor(&lookahead);
+    recoverable_errors.push(Error::new(
+        vec![token.span.clone()],
+        ExpectedSymbolName { keyword: token.lexeme.to_owned() },
+    ));
+    Some(AssignTarget::Symbol(LitSym {
+        name: token.lexeme.to_owned(),
+        span: token.span,
+    }))
+}
+
 impl<'source> Parse<'source> for Assign {
     fn std_parse(
         input: &mut Parser<'source>,
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
-        let target = input.try_parse().forward_errors(recoverable_errors)?;
+        let target = match input.try_parse().forward_errors(recoverable_errors) {
+            Ok(target) => target,
+            Err(errs) => parse_keyword_target(input, recoverable_errors).ok_or(errs)?,
+        };
         let op = input.try_parse::<AssignOp>().forward_errors(recoverable_errors)?;
 
+        if matches!(target, AssignTarget::Destructure(_)) && op.is_compound() {
+            // can't compound assignment to multiple targets, for example:
+            //
+            // a, b += 5
+            //      ^^
+            recoverable_errors.push(Error::new(
+                vec![op.span.clone()],
+                InvalidCompoundAssignmentLhs,
+            ));
+        }
+
         let value = if matches!(target, AssignTarget::Func(_)) {
             if op.is_compound() {
                 // can't compound assignment to function, for example:
@@ -1,12 +1,16 @@
 use crate::parser::{
     ast::{
         assign::{Assign as AssignExpr, AssignTarget},
+        conversion::Conversion,
         expr::Expr,
+        let_expr::Let,
+        literal::{Literal, LitInt},
+        piecewise::Piecewise,
         unary::Unary,
     },
     error::{kind, Error},
     fmt::{Latex, fmt_pow},
-    token::{op::{AssignOp, Associativity, BinOp, BinOpKind, Precedence}, Assign},
+    token::{op::{AssignOp, Associativity, BinOp, BinOpKind, Precedence}, Assign, Name},
     Parse,
     Parser,
     ParseResult,
@@ -17,7 +21,7 @@ use std::{fmt, ops::Range};
 use serde::{Deserialize, Serialize};
 
 /// A binary operator, including assignment.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum BinOpExt {
     /// A binary operator, such as `+` or `*`.
     Op(BinOp),
@@ -29,6 +33,17 @@ enum BinOpExt {
 
     /// An assignment operator, such as `+=` or `/=`.
     Assign(AssignOp),
+
+    /// The percentage operator, such as `20% of 50`.
+    ///
+    /// This is desugared into `20 / 100 * 50` when the binary node is created.
+    PercentOf {
+        /// The region of the source code that the `%` was parsed from.
+        percent_span: Range<usize>,
+
+        /// The region of the source code that the `of` keyword was parsed from.
+        of_span: Range<usize>,
+    },
 }
 
 impl BinOpExt {
@@ -36,7 +51,7 @@ impl BinOpExt {
     fn precedence(&self) -> Precedence {
         match self {
             BinOpExt::Op(op) => op.precedence(),
-            BinOpExt::ImplicitMultiplication => Precedence::Factor,
+            BinOpExt::ImplicitMultiplication | BinOpExt::PercentOf { .. } => Precedence::Factor,
             BinOpExt::Assign(_) => Precedence::Assign,
         }
     }
@@ -54,8 +69,53 @@ impl From<AssignOp> for BinOpExt {
     }
 }
 
+/// If the given operator is `%` and the next token is the `of` keyword, consumes the keyword and
+/// returns the [`BinOpExt::PercentOf`] operator. Otherwise, the operator is returned as-is.
+fn parse_percent_of(input: &mut Parser, op: BinOp) -> BinOpExt {
+    if op.kind != BinOpKind::Mod {
+        return op.into();
+    }
+
+    let mut input_ahead = input.clone();
+    let Ok(of) = input_ahead.try_parse_then::<Name, _>(|name, input| {
+        if name.lexeme == "of" {
+            ParseResult::Ok(())
+        } else {
+            ParseResult::Unrecoverable(vec![input.error(kind::NonFatal)])
+        }
+    }).forward_errors(&mut Vec::new()) else {
+        return op.into();
+    };
+
+    input.set_cursor(&input_ahead);
+    BinOpExt::PercentOf { percent_span: op.span, of_span: of.span }
+}
+
+/// Parses the right-hand operand of a binary operator. If the end of the source code is reached
+/// before an operand is found, a [`kind::MissingOperand`] error is returned, pointing at the
+/// operator.
+fn parse_operand(
+    input: &mut Parser,
+    recoverable_errors: &mut Vec<Error>,
+    op: String,
+    op_span: Range<usize>,
+) -> Result<Expr, Vec<Error>> {
+    Unary::parse_or_lower(input, recoverable_errors).map_err(|errs| {
+        let mut input_ahead = input.clone();
+        input_ahead.advance_past_whitespace();
+        if input_ahead.current_token().is_some() {
+            return errs;
+        }
+
+        vec![Error::new(
+            vec![op_span, input.eof_span()],
+            kind::MissingOperand { op },
+        )]
+    })
+}
+
 /// A binary expression, such as `1 + 2`. Binary expressions can include nested expressions.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Binary {
     /// The left-hand side of the binary expression.
@@ -207,6 +267,32 @@ impl Binary {
                     span: start_span..end_span,
                 }))
             },
+            BinOpExt::PercentOf { percent_span, of_span } => {
+                let percent_end = percent_span.end;
+                let ratio = Expr::Binary(Binary {
+                    lhs: Box::new(lhs),
+                    op: BinOp {
+                        kind: BinOpKind::Div,
+                        implicit: false,
+                        span: percent_span.clone(),
+                    },
+                    rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                        value: "100".to_string(),
+                        span: percent_span,
+                    }))),
+                    span: start_span..percent_end,
+                });
+                Ok(Expr::Binary(Binary {
+                    lhs: Box::new(ratio),
+                    op: BinOp {
+                        kind: BinOpKind::Mul,
+                        implicit: false,
+                        span: of_span,
+                    },
+                    rhs: Box::new(rhs),
+                    span: start_span..end_span,
+                }))
+            },
             BinOpExt::Assign(op) => Ok(Expr::Assign(AssignExpr {
                 target: AssignTarget::try_from_with_op(lhs, &op).forward_errors(recoverable_errors)?,
                 op,
@@ -237,8 +323,14 @@ impl Binary {
                 }
             }).forward_errors(recoverable_errors) {
                 input.set_cursor(&input_ahead);
-                let rhs = Unary::parse_or_lower(input, recoverable_errors)?;
-                lhs = Self::complete_rhs(input, recoverable_errors, lhs, op.into(), rhs)?;
+                let (mut op_str, mut op_span) = (op.to_string(), op.span.clone());
+                let op = parse_percent_of(input, op);
+                if let BinOpExt::PercentOf { of_span, .. } = &op {
+                    op_str.push_str(" of");
+                    op_span.end = of_span.end;
+                }
+                let rhs = parse_operand(input, recoverable_errors, op_str, op_span)?;
+                lhs = Self::complete_rhs(input, recoverable_errors, lhs, op, rhs)?;
             } else if let Ok(assign) = input_ahead.try_parse_then::<AssignOp, _>(|_, input| {
                 if Precedence::Assign >= precedence {
                     ParseResult::Ok(())
@@ -251,9 +343,9 @@ impl Binary {
                 // expression, and there is some syntax is only valid in the context of an
                 // assignment expression (i.e. function headers)
                 input.set_cursor(&input_ahead);
-                let rhs = Unary::parse_or_lower(input, recoverable_errors)?;
+                let rhs = parse_operand(input, recoverable_errors, assign.to_string(), assign.span.clone())?;
                 lhs = Self::complete_rhs(input, recoverable_errors, lhs, assign.into(), rhs)?;
-            } else if BinOpKind::Mul.precedence() >= precedence {
+            } else if input.is_implicit_multiplication() && BinOpKind::Mul.precedence() >= precedence {
                 // implicit multiplication test
 
                 // do not continue if there is significant whitespace after `lhs`
@@ -264,6 +356,24 @@ impl Binary {
                     }
                 }
 
+                // in unit mode, `to` starts a unit conversion instead of an implicit
+                // multiplication
+                if Conversion::is_next(&input_ahead) {
+                    break;
+                }
+
+                // in a piecewise expression, `if` starts the condition of a branch instead of an
+                // implicit multiplication
+                if Piecewise::is_condition_next(&input_ahead) {
+                    break;
+                }
+
+                // in the value of a `let` expression, `in` starts the body of the expression
+                // instead of an implicit multiplication
+                if Let::is_in_next(&input_ahead) {
+                    break;
+                }
+
                 // ensure that we get here because there is *no* operator, not because the operator
                 // has lower precedence
                 if input_ahead.try_parse_then::<BinOp, _>(|op, input| {
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 
 /// A blocked expression. A [`Block`] can contain multiple expressions in the form of statements.
 /// The last statement in the block is the return value of the block.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Block {
     /// The inner statements.
@@ -38,7 +38,10 @@ impl<'source> Parse<'source> for Block {
     ) -> Result<Self, Vec<Error>> {
         let open_curly = input.try_parse::<OpenCurly>().forward_errors(recoverable_errors)?;
         let mut stmts = Vec::new();
-        while let Ok(stmt) = input.try_parse().forward_errors(recoverable_errors) {
+        // statements in a block are not part of any enclosing comma-separated list
+        while let Ok(stmt) = input.try_parse_with_state::<_, Stmt>(|state| {
+            state.in_comma_list = false;
+        }).forward_errors(recoverable_errors) {
             stmts.push(stmt);
         }
         let close_curly = input.try_parse::<CloseCurly>()
@@ -46,10 +49,10 @@ impl<'source> Parse<'source> for Block {
             .unwrap_or_else(|_| {
                 recoverable_errors.push(Error::new(
                     vec![open_curly.span.clone()],
-                    kind::UnclosedParenthesis { opening: true },
+                    kind::UnclosedCurlyBrace,
                 ));
 
-                // fake a close paren for recovery purposes
+                // fake a close curly brace for recovery purposes
                 Garbage::garbage()
             });
         Ok(Self {
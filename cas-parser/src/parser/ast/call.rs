@@ -1,8 +1,8 @@
 use crate::parser::{
     ast::{expr::Expr, helper::ParenDelimited, literal::LitSym},
-    error::{kind::TooManyDerivatives, Error},
+    error::{kind::{PositionalAfterNamedArgument, TooManyDerivatives}, Error},
     fmt::{Latex, fmt_pow},
-    token::Quote,
+    token::{Colon, Quote},
     Parse,
     Parser,
 };
@@ -11,18 +11,117 @@ use std::{fmt, ops::Range};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// An argument passed to a function call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CallArg {
+    /// A positional argument, such as `x` in `f(x)`.
+    Positional(Expr),
+
+    /// A named argument, such as `y: 2` in `f(x, y: 2)`.
+    Named {
+        /// The name of the parameter this argument is passed to.
+        name: LitSym,
+
+        /// The value of the argument.
+        value: Expr,
+
+        /// The region of the source code that this argument was parsed from.
+        span: Range<usize>,
+    },
+}
+
+impl CallArg {
+    /// Returns the span of the argument.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Positional(expr) => expr.span(),
+            Self::Named { span, .. } => span.clone(),
+        }
+    }
+
+    /// Returns the name of the argument, if it is a named argument.
+    pub fn name(&self) -> Option<&LitSym> {
+        match self {
+            Self::Positional(_) => None,
+            Self::Named { name, .. } => Some(name),
+        }
+    }
+
+    /// Returns the value of the argument.
+    pub fn value(&self) -> &Expr {
+        match self {
+            Self::Positional(expr) => expr,
+            Self::Named { value, .. } => value,
+        }
+    }
+
+    /// Returns the value of the argument, consuming the argument.
+    pub fn into_value(self) -> Expr {
+        match self {
+            Self::Positional(expr) => expr,
+            Self::Named { value, .. } => value,
+        }
+    }
+}
+
+impl<'source> Parse<'source> for CallArg {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let name = input.try_parse_with_fn(|input| {
+            let mut errors = Vec::new();
+            LitSym::std_parse(input, &mut errors)
+                .and_then(|name| Colon::std_parse(input, &mut errors).map(|_| name))
+                .map(|name| (name, errors))
+                .into()
+        }).forward_errors(recoverable_errors);
+
+        if let Ok(name) = name {
+            let value = input.try_parse::<Expr>().forward_errors(recoverable_errors)?;
+            let span = name.span.start..value.span().end;
+            return Ok(Self::Named { name, value, span });
+        }
+
+        input.try_parse().map(Self::Positional).forward_errors(recoverable_errors)
+    }
+}
+
+impl std::fmt::Display for CallArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Positional(expr) => expr.fmt(f),
+            Self::Named { name, value, .. } => write!(f, "{}: {}", name, value),
+        }
+    }
+}
+
+impl Latex for CallArg {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Positional(expr) => expr.fmt_latex(f),
+            Self::Named { name, value, .. } => {
+                name.fmt_latex(f)?;
+                write!(f, ": ")?;
+                value.fmt_latex(f)
+            },
+        }
+    }
+}
+
 /// A function call, such as `func(x, -40)`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Call {
     /// The name of the function to call.
     pub name: LitSym,
 
     /// The number of derivatives to take before calling the function.
-    pub derivatives: u8,
+    pub derivatives: usize,
 
-    /// The arguments to the function.
-    pub args: Vec<Expr>,
+    /// The arguments to the function. Positional arguments always come before named arguments.
+    pub args: Vec<CallArg>,
 
     /// The region of the source code that this function call was parsed from.
     pub span: Range<usize>,
@@ -53,12 +152,13 @@ impl<'source> Parse<'source> for Call {
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
         let name = input.try_parse::<LitSym>().forward_errors(recoverable_errors)?;
+        let max_derivatives = input.options().max_derivatives;
         let mut derivatives = 0usize;
         let mut quote_span: Option<Range<_>> = None;
         let mut too_many_derivatives = false;
 
         while let Ok(quote) = input.try_parse::<Quote>().forward_errors(recoverable_errors) {
-            if derivatives == u8::MAX.into() {
+            if derivatives == max_derivatives {
                 too_many_derivatives = true;
             }
 
@@ -71,17 +171,30 @@ impl<'source> Parse<'source> for Call {
         if too_many_derivatives {
             recoverable_errors.push(Error::new(
                 vec![quote_span.unwrap()],
-                TooManyDerivatives { derivatives }
+                TooManyDerivatives { derivatives, max: max_derivatives }
             ));
         }
 
-        let surrounded = input.try_parse::<ParenDelimited<_>>().forward_errors(recoverable_errors)?;
+        let surrounded = input.try_parse::<ParenDelimited<CallArg>>().forward_errors(recoverable_errors)?;
+
+        // positional arguments cannot follow named arguments
+        let mut found_named = false;
+        for arg in surrounded.value.values.iter() {
+            match arg {
+                CallArg::Positional(expr) if found_named => recoverable_errors.push(Error::new(
+                    vec![expr.span()],
+                    PositionalAfterNamedArgument,
+                )),
+                CallArg::Positional(_) => {},
+                CallArg::Named { .. } => found_named = true,
+            }
+        }
 
         // use `name` here before it is moved into the struct
         let span = name.span.start..surrounded.close.span.end;
         Ok(Self {
             name,
-            derivatives: derivatives as u8,
+            derivatives,
             args: surrounded.value.values,
             span,
             paren_span: surrounded.open.span.start..surrounded.close.span.end,
@@ -159,7 +272,11 @@ impl Latex for Call {
 
             fn inner_args(&self, f: &mut fmt::Formatter, call: &Call) -> fmt::Result {
                 match self {
-                    Self::Pow => fmt_pow(f, call.args.first(), call.args.get(1))?,
+                    Self::Pow => fmt_pow(
+                        f,
+                        call.args.first().map(CallArg::value),
+                        call.args.get(1).map(CallArg::value),
+                    )?,
                     Self::Root => {
                         if let Some(arg1) = call.args.get(1) {
                             write!(f, "[{}]", arg1.as_display())?;
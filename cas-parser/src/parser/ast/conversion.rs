@@ -0,0 +1,94 @@
+use crate::parser::{
+    ast::{expr::Expr, literal::LitSym},
+    error::{kind, Error},
+    fmt::Latex,
+    token::Name,
+    Parser,
+    ParseResult,
+};
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A conversion of a measurement to another unit, such as `(2 mi) to dm`.
+///
+/// Conversions are only parsed in unit mode, which is enabled by setting
+/// [`ParseOptions::unit_names`](crate::parser::ParseOptions::unit_names).
+/// The `to` keyword has the lowest precedence of any operator, so it applies to the entire
+/// expression on its left. For example, `5 km to m` evaluates to `5000 m`, and `(2 mi) to dm`
+/// converts the parenthesized measurement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conversion {
+    /// The value to convert.
+    pub value: Box<Expr>,
+
+    /// The name of the unit to convert to.
+    pub target: LitSym,
+
+    /// The region of the source code that the `to` keyword was parsed from.
+    pub to_span: Range<usize>,
+
+    /// The region of the source code that this conversion was parsed from.
+    pub span: Range<usize>,
+}
+
+impl Conversion {
+    /// Returns the span of the conversion.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns true if the parser is in unit mode, and the next token is the `to` keyword.
+    pub(crate) fn is_next(input: &Parser) -> bool {
+        input.is_unit_mode() && input.clone().try_parse_then::<Name, _>(|name, input| {
+            if name.lexeme == "to" {
+                ParseResult::Ok(())
+            } else {
+                ParseResult::Unrecoverable(vec![input.error(kind::NonFatal)])
+            }
+        }).is_ok()
+    }
+
+    /// If the next token is the `to` keyword (see [`Conversion::is_next`]), parses the target unit
+    /// and returns a [`Conversion`] of the given value. Otherwise, the value is returned as-is.
+    pub(crate) fn parse_suffix(
+        input: &mut Parser,
+        recoverable_errors: &mut Vec<Error>,
+        value: Expr,
+    ) -> Result<Expr, Vec<Error>> {
+        if !Self::is_next(input) {
+            return Ok(value);
+        }
+
+        let to = input.try_parse::<Name>().forward_errors(recoverable_errors)?;
+        let target = input.try_parse::<LitSym>()
+            .forward_errors(recoverable_errors)
+            .map_err(|_| vec![Error::new(vec![to.span.clone()], kind::ExpectedUnit)])?;
+        if !input.is_unit(&target.name) {
+            return Err(vec![Error::new(vec![target.span.clone()], kind::ExpectedUnit)]);
+        }
+        let span = value.span().start..target.span.end;
+
+        Ok(Expr::Conversion(Self {
+            value: Box::new(value),
+            target,
+            to_span: to.span,
+            span,
+        }))
+    }
+}
+
+impl std::fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} to {}", self.value, self.target)
+    }
+}
+
+impl Latex for Conversion {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt_latex(f)?;
+        write!(f, " \\to \\mathrm{{{}}}", self.target.name)
+    }
+}
@@ -5,10 +5,15 @@ use crate::{
             binary::Binary,
             block::Block,
             call::Call,
+            conversion::Conversion,
             if_expr::If,
+            let_expr::Let,
             literal::Literal,
             loop_expr::{Break, Continue, Loop},
+            measurement::Measurement,
             paren::Paren,
+            piecewise::Piecewise,
+            structural::ClearSpans,
             unary::Unary,
             while_expr::While,
         },
@@ -17,6 +22,7 @@ use crate::{
         iter::ExprIter,
         token::{op::Precedence, CloseParen},
         Parse,
+        ParseResult,
         Parser,
     },
     return_if_ok,
@@ -32,18 +38,25 @@ use serde::{Deserialize, Serialize};
 /// can be used as the right-hand side of an assignment, or as the argument to a function call.
 ///
 /// In CalcBot, the `c-calculate` command accepts an expression as its argument.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Expr {
     /// A literal value.
     Literal(Literal),
 
+    /// A measurement, such as `5 km`. This is only parsed in unit mode; see
+    /// [`ParseOptions::unit_names`](crate::parser::ParseOptions::unit_names).
+    Measurement(Measurement),
+
     /// A parenthesized expression, such as `(1 + 2)`.
     Paren(Paren),
 
     /// A blocked expression, such as `{1 + 2}`.
     Block(Block),
 
+    /// A piecewise expression, such as `{x^2 if x > 0; -x^2 otherwise}`.
+    Piecewise(Piecewise),
+
     /// An if expression, such as `if x > 0 then x else -x`.
     If(If),
 
@@ -53,6 +66,9 @@ pub enum Expr {
     /// A while loop expression, as in `while x > 0 then { ... }`.
     While(While),
 
+    /// A let expression, such as `let x = 3 in x * x`.
+    Let(Let),
+
     /// A break expression, used to exit a loop, optionally with a value.
     Break(Break),
 
@@ -70,24 +86,53 @@ pub enum Expr {
 
     /// An assignment of a variable or function, such as `x = 1` or `f(x) = x^2`.
     Assign(Assign),
+
+    /// A conversion of a measurement to another unit, such as `(2 mi) to dm`. This is only parsed
+    /// in unit mode; see [`ParseOptions::unit_names`](crate::parser::ParseOptions::unit_names).
+    Conversion(Conversion),
 }
 
 impl Expr {
+    /// Parses an expression from the given stream of tokens, stopping before any binary operator
+    /// with a precedence lower than the given minimum precedence. The remaining tokens are left
+    /// unconsumed.
+    ///
+    /// For example, parsing `1 + 2` with a minimum precedence of [`Precedence::Factor`] will only
+    /// parse `1`, since `+` has a lower precedence than `*`.
+    ///
+    /// If parsing fails, the stream is left unchanged.
+    pub fn parse_with_precedence<'source>(
+        input: &mut Parser<'source>,
+        precedence: Precedence,
+    ) -> ParseResult<Self> {
+        input.try_parse_with_fn(|input| {
+            let mut recoverable_errors = Vec::new();
+            Unary::parse_or_lower(input, &mut recoverable_errors)
+                .and_then(|lhs| Binary::parse_expr(input, &mut recoverable_errors, lhs, precedence))
+                .map(|(expr, _)| (expr, recoverable_errors))
+                .into()
+        })
+    }
+
     /// Returns the span of the expression.
     pub fn span(&self) -> Range<usize> {
         match self {
             Expr::Literal(literal) => literal.span(),
+            Expr::Measurement(measurement) => measurement.span(),
             Expr::Paren(paren) => paren.span(),
             Expr::Block(block) => block.span(),
+            Expr::Piecewise(piecewise) => piecewise.span(),
             Expr::If(if_expr) => if_expr.span(),
             Expr::Loop(loop_expr) => loop_expr.span(),
             Expr::While(while_expr) => while_expr.span(),
+            Expr::Let(let_expr) => let_expr.span(),
             Expr::Break(break_expr) => break_expr.span(),
             Expr::Continue(continue_expr) => continue_expr.span(),
             Expr::Call(call) => call.span(),
             Expr::Unary(unary) => unary.span(),
             Expr::Binary(binary) => binary.span(),
             Expr::Assign(assign) => assign.span(),
+            Expr::Conversion(conversion) => conversion.span(),
         }
     }
 
@@ -97,6 +142,18 @@ impl Expr {
         ExprIter::new(self)
     }
 
+    /// Returns true if the two expressions have the same structure, comparing everything except
+    /// their spans.
+    ///
+    /// This is useful for comparing the output of a transformation to an expected expression,
+    /// which is likely to be parsed from a different location.
+    pub fn structurally_eq(&self, other: &Expr) -> bool {
+        let (mut lhs, mut rhs) = (self.clone(), other.clone());
+        lhs.clear_spans();
+        rhs.clear_spans();
+        lhs == rhs
+    }
+
     /// If this expression is a [`Expr::Paren`], returns the innermost expression in the
     /// parenthesized expression. Otherwise, returns `self`.
     pub fn innermost(&self) -> &Expr {
@@ -119,7 +176,8 @@ impl<'source> Parse<'source> for Expr {
 
         let _ = return_if_ok!(input.try_parse().map(Self::Assign).forward_errors(recoverable_errors));
         let lhs = Unary::parse_or_lower(input, recoverable_errors)?;
-        Ok(Binary::parse_expr(input, recoverable_errors, lhs, Precedence::Any)?.0)
+        let expr = Binary::parse_expr(input, recoverable_errors, lhs, Precedence::Any)?.0;
+        Conversion::parse_suffix(input, recoverable_errors, expr)
     }
 }
 
@@ -127,17 +185,21 @@ impl std::fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Expr::Literal(literal) => literal.fmt(f),
+            Expr::Measurement(measurement) => measurement.fmt(f),
             Expr::Paren(paren) => paren.fmt(f),
             Expr::Block(block) => block.fmt(f),
+            Expr::Piecewise(piecewise) => piecewise.fmt(f),
             Expr::If(if_expr) => if_expr.fmt(f),
             Expr::Loop(loop_expr) => loop_expr.fmt(f),
             Expr::While(while_expr) => while_expr.fmt(f),
+            Expr::Let(let_expr) => let_expr.fmt(f),
             Expr::Break(break_expr) => break_expr.fmt(f),
             Expr::Continue(continue_expr) => continue_expr.fmt(f),
             Expr::Call(call) => call.fmt(f),
             Expr::Unary(unary) => unary.fmt(f),
             Expr::Binary(binary) => binary.fmt(f),
             Expr::Assign(assign) => assign.fmt(f),
+            Expr::Conversion(conversion) => conversion.fmt(f),
         }
     }
 }
@@ -146,17 +208,21 @@ impl Latex for Expr {
     fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Expr::Literal(literal) => literal.fmt_latex(f),
+            Expr::Measurement(measurement) => measurement.fmt_latex(f),
             Expr::Paren(paren) => paren.fmt_latex(f),
             Expr::Block(block) => block.fmt_latex(f),
+            Expr::Piecewise(piecewise) => piecewise.fmt_latex(f),
             Expr::If(if_expr) => if_expr.fmt_latex(f),
             Expr::Loop(loop_expr) => loop_expr.fmt_latex(f),
             Expr::While(while_expr) => while_expr.fmt_latex(f),
+            Expr::Let(let_expr) => let_expr.fmt_latex(f),
             Expr::Break(break_expr) => break_expr.fmt_latex(f),
             Expr::Continue(continue_expr) => continue_expr.fmt_latex(f),
             Expr::Call(call) => call.fmt_latex(f),
             Expr::Unary(unary) => unary.fmt_latex(f),
             Expr::Binary(binary) => binary.fmt_latex(f),
             Expr::Assign(assign) => assign.fmt_latex(f),
+            Expr::Conversion(conversion) => conversion.fmt_latex(f),
         }
     }
 }
@@ -167,17 +233,24 @@ impl Latex for Expr {
 /// expressions. Primary expressions are also self-contained. This means that primary expressions
 /// within a larger expression can be replaced with equivalent, but different kinds of primary
 /// expressions, and the larger expression will still be valid.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Primary {
     /// A literal value.
     Literal(Literal),
 
+    /// A measurement, such as `5 km`. This is only parsed in unit mode; see
+    /// [`ParseOptions::unit_names`](crate::parser::ParseOptions::unit_names).
+    Measurement(Measurement),
+
     /// A parenthesized expression, such as `(1 + 2)`.
     Paren(Paren),
 
     /// A blocked expression, such as `{1 + 2}`.
     Block(Block),
 
+    /// A piecewise expression, such as `{x^2 if x > 0; -x^2 otherwise}`.
+    Piecewise(Piecewise),
+
     /// An if expression, such as `if x > 0 then x else -x`.
     If(If),
 
@@ -187,6 +260,9 @@ pub enum Primary {
     /// A while loop expression, as in `while x > 0 then { ... }`.
     While(While),
 
+    /// A let expression, such as `let x = 3 in x * x`.
+    Let(Let),
+
     /// A break expression, used to exit a loop, optionally with a value.
     Break(Break),
 
@@ -202,34 +278,65 @@ impl Primary {
     pub fn span(&self) -> Range<usize> {
         match self {
             Primary::Literal(literal) => literal.span(),
+            Primary::Measurement(measurement) => measurement.span(),
             Primary::Paren(paren) => paren.span(),
             Primary::Block(block) => block.span(),
+            Primary::Piecewise(piecewise) => piecewise.span(),
             Primary::If(if_expr) => if_expr.span(),
             Primary::Loop(loop_expr) => loop_expr.span(),
             Primary::While(while_expr) => while_expr.span(),
+            Primary::Let(let_expr) => let_expr.span(),
             Primary::Break(break_expr) => break_expr.span(),
             Primary::Continue(continue_expr) => continue_expr.span(),
             Primary::Call(call) => call.span(),
         }
     }
-}
 
-impl<'source> Parse<'source> for Primary {
-    fn std_parse(
+    /// Parses a primary expression that starts with a keyword, such as an `if` expression.
+    ///
+    /// This and [`Primary::parse_braced`] are kept out of [`Primary::std_parse`], so that the
+    /// results of each attempt do not add to the stack space of each level of nested expressions.
+    fn parse_keyword_led<'source>(
         input: &mut Parser<'source>,
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
         let _ = return_if_ok!(input.try_parse().map(Self::If).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Self::Loop).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Self::While).forward_errors(recoverable_errors));
+        let _ = return_if_ok!(input.try_parse().map(Self::Let).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Self::Break).forward_errors(recoverable_errors));
-        let _ = return_if_ok!(input.try_parse().map(Self::Continue).forward_errors(recoverable_errors));
+        input.try_parse().map(Self::Continue).forward_errors(recoverable_errors)
+    }
+
+    /// Parses a primary expression surrounded by curly braces.
+    fn parse_braced<'source>(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        // piecewise expressions and blocks are both surrounded by curly braces, but blocks have no
+        // `if` or `otherwise` keywords after their statements; check for them first, so that
+        // nested blocks are not parsed twice at every level
+        if Piecewise::is_piecewise_next(input) {
+            let _ = return_if_ok!(input.try_parse().map(Self::Piecewise).forward_errors(recoverable_errors));
+        }
+        input.try_parse().map(Self::Block).forward_errors(recoverable_errors)
+    }
+}
+
+impl<'source> Parse<'source> for Primary {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let _ = return_if_ok!(Self::parse_keyword_led(input, recoverable_errors));
         // function calls can overlap with literals, so we need to try parsing a function call
         // first
         let _ = return_if_ok!(input.try_parse().map(Self::Call).forward_errors(recoverable_errors));
+        // measurements start with a literal, so they must be parsed before literals
+        let _ = return_if_ok!(input.try_parse().map(Self::Measurement).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Self::Literal).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Self::Paren).forward_errors(recoverable_errors));
-        input.try_parse().map(Self::Block).forward_errors(recoverable_errors)
+        Self::parse_braced(input, recoverable_errors)
     }
 }
 
@@ -237,11 +344,14 @@ impl From<Primary> for Expr {
     fn from(primary: Primary) -> Self {
         match primary {
             Primary::Literal(literal) => Self::Literal(literal),
+            Primary::Measurement(measurement) => Self::Measurement(measurement),
             Primary::Paren(paren) => Self::Paren(paren),
             Primary::Block(block) => Self::Block(block),
+            Primary::Piecewise(piecewise) => Self::Piecewise(piecewise),
             Primary::If(if_expr) => Self::If(if_expr),
             Primary::Loop(loop_expr) => Self::Loop(loop_expr),
             Primary::While(while_expr) => Self::While(while_expr),
+            Primary::Let(let_expr) => Self::Let(let_expr),
             Primary::Break(break_expr) => Self::Break(break_expr),
             Primary::Continue(continue_expr) => Self::Continue(continue_expr),
             Primary::Call(call) => Self::Call(call),
@@ -24,7 +24,9 @@ where
 
         loop {
             // manually catch unrecoverable errors so we can parse zero values
-            let Ok(value) = input.try_parse().forward_errors(recoverable_errors) else {
+            let Ok(value) = input.try_parse_with_state::<_, T>(|state| {
+                state.in_comma_list = true;
+            }).forward_errors(recoverable_errors) else {
                 break;
             };
             values.push(value);
@@ -1,5 +1,5 @@
 use crate::parser::{
-    error::Error,
+    error::{kind, Error},
     token::pair::Pair,
     Parse,
     Parser,
@@ -36,15 +36,21 @@ where
     ) -> Result<Self, Vec<Error>> {
         let open = input.try_parse().forward_errors(recoverable_errors)?;
 
-        // clone the input so we can scan forward without affecting the original input
-        let mut input_ahead = input.clone();
-
         // scan forward for the corresponding end token
         // if we don't find it, do not attempt to parse the inner value
+        let mut tokens = input.remaining_tokens().iter();
         let mut depth = 1;
         while depth > 0 {
-            let token = input_ahead.next_token()
-                .map_err(|eof| vec![eof])?;
+            let Some(token) = tokens.next() else {
+                // the tokens that could come next are needed for completion, so find them by
+                // parsing the inner value and end token anyway (see `Parser::parse_prefix`)
+                if input.is_recording_expected() {
+                    let mut inner = input.clone();
+                    let _ = inner.try_parse::<T>();
+                    let _ = inner.try_parse::<P::Close<'source>>();
+                }
+                return Err(vec![Error::new(vec![input.eof_span()], kind::UnexpectedEof)]);
+            };
 
             if token.kind == P::OPEN {
                 depth += 1;
@@ -13,7 +13,7 @@ use std::{fmt, ops::Range};
 use serde::{Deserialize, Serialize};
 
 /// An `if` expression, such as `if true 1 else 2`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct If {
     /// The condition of the `if` expression.
@@ -0,0 +1,135 @@
+use crate::parser::{
+    ast::{expr::Expr, literal::LitSym},
+    error::{kind, Error},
+    fmt::Latex,
+    garbage::Garbage,
+    keyword::Let as LetToken,
+    token::{Assign, Name},
+    Parse,
+    Parser,
+    ParseResult,
+};
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A `let` expression, such as `let x = 3 in x * x`. The variable is bound to the value only while
+/// the body is evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Let {
+    /// The name of the variable being bound.
+    pub name: LitSym,
+
+    /// The value the variable is bound to.
+    pub value: Box<Expr>,
+
+    /// The expression evaluated with the variable bound.
+    pub body: Box<Expr>,
+
+    /// The region of the source code that this expression was parsed from.
+    pub span: Range<usize>,
+
+    /// The span of the `let` keyword.
+    pub let_span: Range<usize>,
+
+    /// The span of the `in` keyword.
+    pub in_span: Range<usize>,
+}
+
+impl Let {
+    /// Returns the span of the `let` expression.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns true if the parser is in the value of a `let` expression, and the next token is the
+    /// `in` keyword starting its body.
+    ///
+    /// `in` is not a reserved word, since it is also the name of a unit (inches), so it only ends
+    /// an expression in this position.
+    pub(crate) fn is_in_next(input: &Parser) -> bool {
+        input.state().in_ends_expr && input.clone().try_parse::<InToken>().is_ok()
+    }
+}
+
+/// The `in` keyword of a `let` expression, which is parsed from a name.
+struct InToken {
+    /// The span of the keyword.
+    span: Range<usize>,
+}
+
+impl<'source> Parse<'source> for InToken {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        input.try_parse_then::<Name, _>(|name, input| {
+            if name.lexeme == "in" {
+                ParseResult::Ok(())
+            } else {
+                ParseResult::Unrecoverable(vec![input.error(kind::NonFatal)])
+            }
+        })
+            .forward_errors(recoverable_errors)
+            .map(|name| Self { span: name.span })
+    }
+}
+
+impl<'source> Parse<'source> for Let {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let let_token = input.try_parse::<LetToken>().forward_errors(recoverable_errors)?;
+        let name = input.try_parse::<LitSym>().forward_errors(recoverable_errors)?;
+        input.try_parse::<Assign>().forward_errors(recoverable_errors)?;
+        let value = input.try_parse_with_state::<_, Expr>(|state| {
+            state.in_ends_expr = true;
+        }).forward_errors(recoverable_errors)?;
+        let (in_span, body) = 'body: {
+            let Ok(in_token) = input.try_parse::<InToken>().forward_errors(recoverable_errors) else {
+                recoverable_errors.push(Error::new(
+                    vec![let_token.span.clone(), input.span()],
+                    kind::MissingLetKeyword {
+                        keyword: "in",
+                    },
+                ));
+                break 'body (0..0, Garbage::garbage());
+            };
+            let body = input.try_parse_with_state::<_, Expr>(|state| {
+                state.in_ends_expr = false;
+            }).forward_errors(recoverable_errors)?;
+            (in_token.span, body)
+        };
+        let span = let_token.span.start..body.span().end.max(value.span().end);
+
+        Ok(Self {
+            name,
+            value: Box::new(value),
+            body: Box::new(body),
+            span,
+            let_span: let_token.span,
+            in_span,
+        })
+    }
+}
+
+impl std::fmt::Display for Let {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "let {} = {} in {}", self.name, self.value, self.body)
+    }
+}
+
+impl Latex for Let {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\\text{{let }}")?;
+        self.name.fmt_latex(f)?;
+        write!(f, " = ")?;
+        self.value.fmt_latex(f)?;
+        write!(f, "\\text{{ in }}")?;
+        self.body.fmt_latex(f)?;
+        Ok(())
+    }
+}
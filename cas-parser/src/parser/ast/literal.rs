@@ -1,9 +1,9 @@
 use crate::{
     parser::{
-        ast::{expr::Expr, helper::SquareDelimited},
+        ast::{expr::Expr, helper::{Delimited, SquareDelimited}},
         error::{kind, Error},
         fmt::Latex,
-        token::{Boolean, CloseParen, Float, Name, Int, OpenParen, Quote},
+        token::{Boolean, CloseParen, Comma, Float, Name, Int, OpenParen, Quote},
         Parse,
         Parser,
         ParseResult,
@@ -17,7 +17,7 @@ use std::{collections::HashSet, fmt, ops::Range};
 use serde::{Deserialize, Serialize};
 
 /// An integer literal, representing as a [`String`].
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitInt {
     /// The value of the integer literal as a string.
@@ -55,7 +55,9 @@ impl Latex for LitInt {
 }
 
 /// A floating-point literal, represented as a [`String`].
-#[derive(Debug, Clone, PartialEq)]
+///
+/// This includes the special values `inf` and `nan`, representing positive infinity and NaN.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitFloat {
     /// The value of the floating-point literal as a string.
@@ -88,7 +90,11 @@ impl std::fmt::Display for LitFloat {
 
 impl Latex for LitFloat {
     fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value)
+        match self.value.as_str() {
+            "inf" => write!(f, "\\infty"),
+            "nan" => write!(f, "\\text{{NaN}}"),
+            value => write!(f, "{}", value),
+        }
     }
 }
 
@@ -101,7 +107,7 @@ pub const DIGITS: [char; 64] = [
 ];
 
 /// Helper struct to parse the digits used in various bases.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct RadixWord {
     /// The parsed digits.
     pub value: String,
@@ -114,12 +120,14 @@ impl RadixWord {
     fn parse(input: &mut Parser) -> Self {
         let mut value = String::new();
         let mut span = 0..0;
-        while let Ok(token) = input.next_token_raw() {
+        loop {
+            let token = input.next_token_raw();
             match token.kind {
                 TokenKind::Add
                     | TokenKind::Name
-                    | TokenKind::Int
-                    | TokenKind::Div => value.push_str(token.lexeme),
+                    | TokenKind::Int => value.push_str(token.lexeme),
+                // `÷` is also a division token, but is not a digit
+                TokenKind::Div if token.lexeme == "/" => value.push_str(token.lexeme),
                 _ => {
                     input.prev();
                     break;
@@ -139,27 +147,33 @@ impl RadixWord {
     }
 }
 
-/// Helper function to ensure the given string represents a valid base for radix notation.
-fn validate_radix_base(num: &Int) -> ParseResult<u8> {
+/// Helper function to ensure the given string represents a valid base for radix notation, within
+/// the range of bases allowed by the parser's [`ParseOptions`].
+///
+/// [`ParseOptions`]: crate::parser::ParseOptions
+fn validate_radix_base(num: &Int, input: &Parser) -> ParseResult<u8> {
+    let options = input.options();
+    let (min, max) = (options.min_radix.max(2), options.max_radix.min(DIGITS.len() as u8));
     match num.lexeme.parse() {
-        Ok(base) if (2..=64).contains(&base) => ParseResult::Ok(base),
-        Ok(base) if base < 2 => ParseResult::Recoverable(
+        Ok(base) if (min..=max).contains(&base) => ParseResult::Ok(base),
+        Ok(base) if base < min => ParseResult::Recoverable(
             64, // use base 64 to limit invalid radix digit errors
-            vec![Error::new(vec![num.span.clone()], kind::InvalidRadixBase { too_large: false })],
+            vec![Error::new(vec![num.span.clone()], kind::InvalidRadixBase { too_large: false, min, max })],
         ),
         _ => ParseResult::Recoverable(
             64,
-            vec![Error::new(vec![num.span.clone()], kind::InvalidRadixBase { too_large: true })],
+            vec![Error::new(vec![num.span.clone()], kind::InvalidRadixBase { too_large: true, min, max })],
         ),
     }
 }
 
 /// A number written in radix notation. Radix notation allows users to express integers in a base
 /// other than base 10.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitRadix {
-    /// The radix of the literal. This value must be between 2 and 64, inclusive.
+    /// The radix of the literal. This value must be between 2 and 64, inclusive, or within the
+    /// narrower range set in the parser's options.
     pub base: u8,
 
     /// The number, expressed in the given radix.
@@ -177,7 +191,7 @@ impl<'source> Parse<'source> for LitRadix {
         let num = input.try_parse().forward_errors(recoverable_errors)?;
         let quote = input.try_parse::<Quote>().forward_errors(recoverable_errors)?;
 
-        let base = validate_radix_base(&num).forward_errors(recoverable_errors)?;
+        let base = validate_radix_base(&num, input).forward_errors(recoverable_errors)?;
         let word = RadixWord::parse(input);
         if word.value.is_empty() {
             recoverable_errors.push(Error::new(vec![quote.span], kind::EmptyRadixLiteral {
@@ -249,7 +263,7 @@ impl Latex for LitRadix {
 }
 
 /// A boolean literal, either `true` or `false`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitBool {
     /// The value of the boolean literal.
@@ -290,7 +304,7 @@ impl Latex for LitBool {
 }
 
 /// A symbol / identifier literal. Symbols are used to represent variables and functions.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitSym {
     /// The name of the symbol.
@@ -338,9 +352,37 @@ impl Latex for LitSym {
     }
 }
 
+/// A reference to a numbered slot holding a value, such as an argument of a function call. Slots
+/// are never produced by the parser; an evaluator can replace symbols with slots, so that their
+/// values are looked up by index instead of by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LitSlot {
+    /// The index of the slot.
+    pub index: usize,
+
+    /// The name of the symbol that the slot replaced, used to display the slot.
+    pub name: String,
+
+    /// The region of the source code that the replaced symbol was parsed from.
+    pub span: Range<usize>,
+}
+
+impl std::fmt::Display for LitSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Latex for LitSlot {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 /// The unit type, written as `()`. The unit type is by-default returned by functions that do not
 /// return a value.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitUnit {
     /// The region of the source code that this literal was parsed from.
@@ -374,7 +416,7 @@ impl Latex for LitUnit {
 
 /// A list type, consisting of a list of expressions surrounded by square brackets and delimited by
 /// commas.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LitList {
     /// The list of expressions.
@@ -424,11 +466,103 @@ impl Latex for LitList {
     }
 }
 
+/// A tuple type, consisting of two or more expressions surrounded by parentheses and delimited by
+/// commas, such as `(1, 2)`. A tuple with a single expression must be written with a trailing
+/// comma, as in `(1,)`, to distinguish it from a parenthesized expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LitTuple {
+    /// The list of expressions.
+    pub values: Vec<Expr>,
+
+    /// The region of the source code that this literal was parsed from.
+    pub span: Range<usize>,
+}
+
+impl LitTuple {
+    /// Returns true if there is a comma directly inside the parentheses that were just opened. If
+    /// not, the parentheses surround a single expression, and parsing a tuple would only parse it
+    /// twice.
+    fn has_comma(input: &Parser) -> bool {
+        let mut depth = 1usize;
+        for token in input.remaining_tokens() {
+            match token.kind {
+                TokenKind::OpenParen | TokenKind::OpenSquare | TokenKind::OpenCurly => depth += 1,
+                TokenKind::CloseParen | TokenKind::CloseSquare | TokenKind::CloseCurly => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return false;
+                    }
+                },
+                TokenKind::Comma if depth == 1 => return true,
+                _ => (),
+            }
+        }
+        false
+    }
+}
+
+impl<'source> Parse<'source> for LitTuple {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let open = input.try_parse::<OpenParen>().forward_errors(recoverable_errors)?;
+        if !Self::has_comma(input) {
+            return Err(vec![input.error(kind::NonFatal)]);
+        }
+
+        let first = input.try_parse_with_state::<_, Expr>(|state| {
+            state.in_comma_list = true;
+        }).forward_errors(recoverable_errors)?;
+        input.try_parse::<Comma>().forward_errors(recoverable_errors)?;
+        let rest = input.try_parse::<Delimited<Comma, Expr>>().forward_errors(recoverable_errors)?;
+        let close = input.try_parse::<CloseParen>().forward_errors(recoverable_errors)?;
+
+        Ok(Self {
+            values: std::iter::once(first).chain(rest.values).collect(),
+            span: open.span.start..close.span.end,
+        })
+    }
+}
+
+impl std::fmt::Display for LitTuple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, value) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        if self.values.len() == 1 {
+            write!(f, ",")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Latex for LitTuple {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\\left(")?;
+        for (i, value) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            value.fmt_latex(f)?;
+        }
+        if self.values.len() == 1 {
+            write!(f, ",")?;
+        }
+        write!(f, "\\right)")
+    }
+}
+
 /// Represents a literal value in CalcScript.
 ///
 /// A literal is any value that can is written directly into the source code. For example, the
 /// number `1` is a literal (it is currently the only literal type supported by CalcScript).
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Literal {
     /// An integer literal.
@@ -454,6 +588,13 @@ pub enum Literal {
     /// A list type, consisting of a list of expressions surrounded by square brackets and delimited
     /// by commas.
     List(LitList),
+
+    /// A tuple type, consisting of a list of expressions surrounded by parentheses and delimited
+    /// by commas.
+    Tuple(LitTuple),
+
+    /// A reference to a numbered slot holding a value. This is never produced by the parser.
+    Slot(LitSlot),
 }
 
 impl Literal {
@@ -467,6 +608,8 @@ impl Literal {
             Literal::Symbol(name) => name.span.clone(),
             Literal::Unit(unit) => unit.span.clone(),
             Literal::List(list) => list.span.clone(),
+            Literal::Tuple(tuple) => tuple.span.clone(),
+            Literal::Slot(slot) => slot.span.clone(),
         }
     }
 }
@@ -482,6 +625,7 @@ impl<'source> Parse<'source> for Literal {
         let _ = return_if_ok!(input.try_parse().map(Literal::Float).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Literal::Symbol).forward_errors(recoverable_errors));
         let _ = return_if_ok!(input.try_parse().map(Literal::Unit).forward_errors(recoverable_errors));
+        let _ = return_if_ok!(input.try_parse().map(Literal::Tuple).forward_errors(recoverable_errors));
         input.try_parse().map(Literal::List).forward_errors(recoverable_errors)
     }
 }
@@ -496,6 +640,8 @@ impl std::fmt::Display for Literal {
             Literal::Symbol(name) => name.fmt(f),
             Literal::Unit(unit) => unit.fmt(f),
             Literal::List(list) => list.fmt(f),
+            Literal::Tuple(tuple) => tuple.fmt(f),
+            Literal::Slot(slot) => slot.fmt(f),
         }
     }
 }
@@ -510,6 +656,8 @@ impl Latex for Literal {
             Literal::Symbol(name) => name.fmt_latex(f),
             Literal::Unit(unit) => unit.fmt_latex(f),
             Literal::List(list) => list.fmt_latex(f),
+            Literal::Tuple(tuple) => tuple.fmt_latex(f),
+            Literal::Slot(slot) => slot.fmt_latex(f),
         }
     }
 }
@@ -0,0 +1,82 @@
+use crate::parser::{
+    ast::{let_expr::Let, literal::{Literal, LitFloat, LitInt, LitSym}},
+    error::{kind, Error},
+    fmt::Latex,
+    Parse,
+    Parser,
+    ParseResult,
+};
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A number followed by the name of a unit, such as `5 km`.
+///
+/// Measurements are only parsed in unit mode, which is enabled by setting
+/// [`ParseOptions::unit_names`](crate::parser::ParseOptions::unit_names).
+/// Otherwise, `5 km` is parsed as the implicit multiplication of `5` and `km`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Measurement {
+    /// The numeric value of the measurement. This is always a [`Literal::Integer`] or a
+    /// [`Literal::Float`].
+    pub value: Box<Literal>,
+
+    /// The name of the unit of the measurement.
+    pub unit: LitSym,
+
+    /// The region of the source code that this measurement was parsed from.
+    pub span: Range<usize>,
+}
+
+impl Measurement {
+    /// Returns the span of the measurement.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl<'source> Parse<'source> for Measurement {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        if !input.is_unit_mode() {
+            return Err(vec![input.error(kind::NonFatal)]);
+        }
+
+        let value = match input.try_parse::<LitFloat>().forward_errors(recoverable_errors) {
+            Ok(float) => Literal::Float(float),
+            Err(_) => Literal::Integer(input.try_parse::<LitInt>().forward_errors(recoverable_errors)?),
+        };
+        // in the value of a `let` expression, `in` starts the body of the expression instead of
+        // being the unit of a measurement
+        if Let::is_in_next(input) {
+            return Err(vec![input.error(kind::NonFatal)]);
+        }
+        let unit = input.try_parse_then::<LitSym, _>(|sym, input| {
+            if input.is_unit(&sym.name) {
+                ParseResult::Ok(())
+            } else {
+                ParseResult::Unrecoverable(vec![Error::new(vec![sym.span.clone()], kind::NonFatal)])
+            }
+        }).forward_errors(recoverable_errors)?;
+        let span = value.span().start..unit.span.end;
+
+        Ok(Self { value: Box::new(value), unit, span })
+    }
+}
+
+impl std::fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+impl Latex for Measurement {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt_latex(f)?;
+        write!(f, "\\ \\mathrm{{{}}}", self.unit.name)
+    }
+}
@@ -2,25 +2,37 @@ pub mod assign;
 pub mod binary;
 pub mod block;
 pub mod call;
+pub mod conversion;
 pub mod expr;
 pub mod helper;
 pub mod if_expr;
+pub mod let_expr;
 pub mod literal;
 pub mod loop_expr;
+pub mod measurement;
 pub mod paren;
+pub mod piecewise;
+pub mod program;
 pub mod stmt;
+pub mod structural;
 pub mod unary;
 pub mod while_expr;
 
 pub use assign::{Assign, AssignTarget, FuncHeader, Param};
 pub use binary::Binary;
 pub use block::Block;
-pub use call::Call;
+pub use call::{Call, CallArg};
+pub use conversion::Conversion;
 pub use expr::{Expr, Primary};
 pub use if_expr::If;
-pub use literal::{Literal, LitFloat, LitInt, LitRadix, LitSym, LitUnit};
+pub use let_expr::Let;
+pub use literal::{Literal, LitBool, LitFloat, LitInt, LitList, LitRadix, LitSym, LitTuple, LitUnit};
 pub use loop_expr::Loop;
+pub use measurement::Measurement;
 pub use paren::Paren;
+pub use piecewise::{Branch, Piecewise};
+pub use program::Program;
 pub use stmt::Stmt;
+pub use structural::ClearSpans;
 pub use unary::Unary;
 pub use while_expr::While;
@@ -7,13 +7,14 @@ use crate::parser::{
     Parse,
     Parser,
 };
+use crate::tokenizer::{Token, TokenKind};
 use std::{fmt, ops::Range};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A parenthesized expression. A [`Paren`] can only contain a single expression.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Paren {
     /// The inner expression.
@@ -29,6 +30,16 @@ impl Paren {
         self.span.clone()
     }
 
+    /// Returns the span of the opening parenthesis.
+    pub fn open_span(&self) -> Range<usize> {
+        self.span.start..self.span.start + 1
+    }
+
+    /// Returns the span of the closing parenthesis.
+    pub fn close_span(&self) -> Range<usize> {
+        self.span.end - 1..self.span.end
+    }
+
     /// Returns the innermost expression in the parenthesized expression.
     pub fn innermost(&self) -> &Expr {
         let mut inner = &self.expr;
@@ -48,27 +59,131 @@ impl Paren {
     }
 }
 
+/// Returns true if the token could combine with the expression inside a pair of parentheses (or
+/// change how the expression is parsed) if the parentheses were removed. `raw` is the token
+/// directly next to the parentheses, and `significant` is the nearest token that is not
+/// whitespace.
+///
+/// For example, removing the parentheses in `f(x)` or `2(3)` changes the meaning of the
+/// expression, since `fx` and `23` are entirely different tokens.
+fn touches(raw: Option<&Token>, significant: Option<&Token>, after: bool) -> bool {
+    let is_word = |token: &Token| token.lexeme.chars()
+        .next_back()
+        .is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+    let is_word_start = |token: &Token| token.lexeme.chars()
+        .next()
+        .is_some_and(|ch| ch.is_alphanumeric() || ch == '_' || ch == '.');
+    if raw.is_some_and(|token| if after { is_word_start(token) } else { is_word(token) }) {
+        return true;
+    }
+
+    significant.is_some_and(|token| match token.kind {
+        TokenKind::Name | TokenKind::Int | TokenKind::Float | TokenKind::Boolean | TokenKind::Dot
+            | TokenKind::Quote | TokenKind::Bin | TokenKind::Oct | TokenKind::Hex
+            | TokenKind::Symbol => true,
+        TokenKind::OpenParen | TokenKind::OpenSquare | TokenKind::OpenCurly => after,
+        TokenKind::CloseParen | TokenKind::CloseSquare | TokenKind::CloseCurly => !after,
+        _ => false,
+    })
+}
+
+/// Returns true if the parenthesized expression spanning from `before` (pointing at the opening
+/// parenthesis) to `after` (pointing just after the closing parenthesis) is redundant, i.e.
+/// removing the parentheses would result in the same parse.
+fn is_redundant(before: &Parser, after: &Parser, expr: &Expr) -> bool {
+    // only expressions that are always parsed as a single unit are considered; whether the
+    // parentheses around any other expression are redundant depends on the surrounding
+    // precedence
+    if !matches!(expr, Expr::Literal(_) | Expr::Paren(_) | Expr::Call(_)) {
+        return false;
+    }
+
+    let raw_before = before.prev_token();
+    let significant_before = {
+        let mut before = before.clone();
+        loop {
+            match before.prev_token() {
+                Some(token) if token.is_ignore() => before.prev(),
+                token => break token.cloned(),
+            }
+        }
+    };
+    let raw_after = after.current_token();
+    let significant_after = {
+        let mut after = after.clone();
+        after.advance_past_whitespace();
+        after.current_token().cloned()
+    };
+
+    !touches(raw_before, significant_before.as_ref(), false)
+        && !touches(raw_after, significant_after.as_ref(), true)
+}
+
+/// Parses the opening parenthesis of a [`Paren`], returning its span and a copy of the parser
+/// pointing at it, which is used to check if the parentheses are redundant.
+///
+/// This and [`parse_close`] are kept out of [`Paren::std_parse`], so that their temporaries do not
+/// add to the stack space of each level of nested parentheses.
+fn parse_open<'source>(
+    input: &mut Parser<'source>,
+    recoverable_errors: &mut Vec<Error>
+) -> Result<(Parser<'source>, Range<usize>), Vec<Error>> {
+    let mut before = input.clone();
+    before.advance_past_whitespace();
+    let open_paren = input.try_parse::<OpenParen>().forward_errors(recoverable_errors)?;
+
+    // `()` is the unit literal, and is never parsed as a `Paren`
+    if let Ok(close_paren) = input.clone().try_parse::<CloseParen>().forward_errors(recoverable_errors) {
+        return Err(vec![Error::new(
+            vec![open_paren.span.start..close_paren.span.end],
+            kind::EmptyParenthesis,
+        )]);
+    }
+
+    Ok((before, open_paren.span))
+}
+
+/// Parses the closing parenthesis of a [`Paren`] containing the given expression, returning the
+/// span of the whole [`Paren`].
+fn parse_close(
+    input: &mut Parser,
+    recoverable_errors: &mut Vec<Error>,
+    before: &Parser,
+    open_span: Range<usize>,
+    expr: &Expr,
+) -> Range<usize> {
+    let mut unclosed = false;
+    let close_paren = input.try_parse::<CloseParen>()
+        .forward_errors(recoverable_errors)
+        .unwrap_or_else(|_| {
+            recoverable_errors.push(Error::new(
+                vec![open_span.clone()],
+                kind::UnclosedParenthesis { opening: true },
+            ));
+            unclosed = true;
+
+            // fake a close paren for recovery purposes
+            Garbage::garbage()
+        });
+    let span = open_span.start..close_paren.span.end;
+    if !unclosed && is_redundant(before, input, expr) {
+        recoverable_errors.push(Error::new(vec![span.clone()], kind::RedundantParentheses));
+    }
+    span
+}
+
 impl<'source> Parse<'source> for Paren {
     fn std_parse(
         input: &mut Parser<'source>,
         recoverable_errors: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
-        let open_paren = input.try_parse::<OpenParen>().forward_errors(recoverable_errors)?;
+        let (before, open_span) = parse_open(input, recoverable_errors)?;
         let expr = input.try_parse().forward_errors(recoverable_errors)?;
-        let close_paren = input.try_parse::<CloseParen>()
-            .forward_errors(recoverable_errors)
-            .unwrap_or_else(|_| {
-                recoverable_errors.push(Error::new(
-                    vec![open_paren.span.clone()],
-                    kind::UnclosedParenthesis { opening: true },
-                ));
-
-                // fake a close paren for recovery purposes
-                Garbage::garbage()
-            });
+        let span = parse_close(input, recoverable_errors, &before, open_span, &expr);
+
         Ok(Self {
             expr: Box::new(expr),
-            span: open_paren.span.start..close_paren.span.end,
+            span,
         })
     }
 }
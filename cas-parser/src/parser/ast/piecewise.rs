@@ -0,0 +1,199 @@
+use crate::parser::{
+    ast::expr::Expr,
+    error::{kind, Error},
+    fmt::Latex,
+    garbage::Garbage,
+    keyword::{If as IfToken, Otherwise as OtherwiseToken},
+    token::{CloseCurly, OpenCurly, Semicolon},
+    Parse,
+    Parser,
+};
+use crate::tokenizer::{Keyword, TokenKind};
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A guarded branch of a [`Piecewise`] expression, such as `x^2 if x > 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Branch {
+    /// The value of the branch, used if the condition is true.
+    pub value: Expr,
+
+    /// The condition guarding the branch.
+    pub condition: Expr,
+
+    /// The span of the `if` keyword.
+    pub if_span: Range<usize>,
+}
+
+impl Branch {
+    /// Returns the span of the branch.
+    pub fn span(&self) -> Range<usize> {
+        self.value.span().start..self.condition.span().end
+    }
+}
+
+/// A piecewise expression, such as `{ x^2 if x > 0; -x^2 otherwise }`.
+///
+/// The conditions of each branch are tested in order, and the value of the first branch whose
+/// condition is true is the value of the expression. If no condition is true, the value of the
+/// `otherwise` branch is used, if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Piecewise {
+    /// The guarded branches, in the order they should be tested.
+    pub branches: Vec<Branch>,
+
+    /// The value to use if none of the branch conditions are true.
+    pub otherwise: Option<Box<Expr>>,
+
+    /// The span of the `otherwise` keyword.
+    pub otherwise_span: Option<Range<usize>>,
+
+    /// The region of the source code that this piecewise expression was parsed from.
+    pub span: Range<usize>,
+}
+
+impl Piecewise {
+    /// Returns the span of the piecewise expression.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns true if the parser is in the value of a piecewise branch, and the next token is the
+    /// `if` keyword starting the branch's condition.
+    pub(crate) fn is_condition_next(input: &Parser) -> bool {
+        input.state().if_ends_expr && input.clone().try_parse::<IfToken>().is_ok()
+    }
+
+    /// Returns true if the curly braces starting at the current token could surround a piecewise
+    /// expression. If not, they surround a block, and parsing a piecewise expression would only
+    /// parse the block twice.
+    ///
+    /// Each branch of a piecewise expression has an `if` keyword with no matching `then` keyword,
+    /// or is the `otherwise` branch. Neither is possible inside a block.
+    pub(crate) fn is_piecewise_next(input: &Parser) -> bool {
+        let mut tokens = input.remaining_tokens().iter().skip_while(|token| token.is_ignore());
+        if tokens.next().is_none_or(|token| token.kind != TokenKind::OpenCurly) {
+            return false;
+        }
+
+        let mut depth = 1usize;
+        let mut unmatched_ifs = 0isize;
+        for token in tokens {
+            match token.kind {
+                TokenKind::OpenParen | TokenKind::OpenSquare | TokenKind::OpenCurly => depth += 1,
+                TokenKind::CloseParen | TokenKind::CloseSquare | TokenKind::CloseCurly => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                },
+                _ if depth == 1 => match token.keyword() {
+                    Some(Keyword::Otherwise) => return true,
+                    Some(Keyword::If) => unmatched_ifs += 1,
+                    Some(Keyword::Then) => unmatched_ifs -= 1,
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+        unmatched_ifs > 0
+    }
+}
+
+impl<'source> Parse<'source> for Piecewise {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let open_curly = input.try_parse::<OpenCurly>().forward_errors(recoverable_errors)?;
+        let mut branches = Vec::new();
+        let mut otherwise = None;
+        loop {
+            let value = input.try_parse_with_state::<_, Expr>(|state| {
+                state.if_ends_expr = true;
+            }).forward_errors(recoverable_errors)?;
+
+            if let Ok(if_token) = input.try_parse::<IfToken>().forward_errors(recoverable_errors) {
+                let condition = input.try_parse_with_state::<_, Expr>(|state| {
+                    state.if_ends_expr = false;
+                }).forward_errors(recoverable_errors)?;
+                branches.push(Branch {
+                    value,
+                    condition,
+                    if_span: if_token.span,
+                });
+            } else {
+                // without a condition, this must be the last branch
+                let otherwise_token = input.try_parse::<OtherwiseToken>().forward_errors(recoverable_errors)?;
+                otherwise = Some((value, otherwise_token.span));
+                break;
+            }
+
+            if input.try_parse::<Semicolon>().forward_errors(recoverable_errors).is_err() {
+                break;
+            }
+        }
+        let close_curly = input.try_parse::<CloseCurly>()
+            .forward_errors(recoverable_errors)
+            .unwrap_or_else(|_| {
+                recoverable_errors.push(Error::new(
+                    vec![open_curly.span.clone()],
+                    kind::UnclosedCurlyBrace,
+                ));
+
+                // fake a close curly brace for recovery purposes
+                Garbage::garbage()
+            });
+        let (otherwise, otherwise_span) = match otherwise {
+            Some((value, span)) => (Some(Box::new(value)), Some(span)),
+            None => (None, None),
+        };
+
+        Ok(Self {
+            branches,
+            otherwise,
+            otherwise_span,
+            span: open_curly.span.start..close_curly.span.end,
+        })
+    }
+}
+
+impl std::fmt::Display for Piecewise {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (i, branch) in self.branches.iter().enumerate() {
+            if i != 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{} if {}", branch.value, branch.condition)?;
+        }
+        if let Some(otherwise) = &self.otherwise {
+            if !self.branches.is_empty() {
+                write!(f, "; ")?;
+            }
+            write!(f, "{} otherwise", otherwise)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Latex for Piecewise {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\\begin{{cases}}")?;
+        for branch in &self.branches {
+            branch.value.fmt_latex(f)?;
+            write!(f, " & \\text{{if }}")?;
+            branch.condition.fmt_latex(f)?;
+            write!(f, " \\\\ ")?;
+        }
+        if let Some(otherwise) = &self.otherwise {
+            otherwise.fmt_latex(f)?;
+            write!(f, " & \\text{{otherwise}}")?;
+        }
+        write!(f, "\\end{{cases}}")
+    }
+}
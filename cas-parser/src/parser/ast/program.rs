@@ -0,0 +1,73 @@
+use crate::parser::{
+    ast::stmt::Stmt,
+    error::Error,
+    fmt::Latex,
+    Parse,
+    Parser,
+};
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A complete program, made up of a sequence of statements. Statements are separated by
+/// semicolons or newlines. The last statement in the program is the return value of the program.
+///
+/// Unlike a [`Block`](crate::parser::ast::block::Block), a program is not surrounded by curly
+/// braces; it is the top-level construct of a CalcScript source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Program {
+    /// The statements of the program.
+    pub stmts: Vec<Stmt>,
+
+    /// The region of the source code that this [`Program`] was parsed from.
+    pub span: Range<usize>,
+}
+
+impl Program {
+    /// Returns the span of the [`Program`].
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl<'source> Parse<'source> for Program {
+    fn std_parse(
+        input: &mut Parser<'source>,
+        recoverable_errors: &mut Vec<Error>
+    ) -> Result<Self, Vec<Error>> {
+        let mut stmts = Vec::new();
+        while !input.only_whitespace_remains() {
+            stmts.push(input.try_parse::<Stmt>().forward_errors(recoverable_errors)?);
+        }
+
+        let span = match (stmts.first(), stmts.last()) {
+            (Some(first), Some(last)) => first.span.start..last.span.end,
+            _ => 0..0,
+        };
+        Ok(Self { stmts, span })
+    }
+}
+
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, stmt) in self.stmts.iter().enumerate() {
+            // statements without a semicolon were separated by a newline
+            if i > 0 && self.stmts[i - 1].semicolon.is_none() {
+                writeln!(f)?;
+            }
+            stmt.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Latex for Program {
+    fn fmt_latex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for stmt in &self.stmts {
+            stmt.fmt_latex(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+//! Span-insensitive comparison and hashing of AST nodes.
+//!
+//! Every AST node records the region of the source code it was parsed from, so the derived
+//! [`PartialEq`] implementations consider two structurally identical expressions at different
+//! offsets to be unequal. The [`ClearSpans`] trait resets those spans, so that only the structure
+//! of the nodes remains to be compared.
+//!
+//! The [`Hash`] implementations of AST nodes skip spans entirely, so that structurally identical
+//! expressions hash equally regardless of where they were parsed from. This is consistent with
+//! both the derived [`PartialEq`] and [`Expr::structurally_eq`]. To key a map by the shape of an
+//! expression, clear the spans of the key before inserting or looking it up.
+
+use crate::parser::{
+    ast::{
+        assign::{Assign, AssignTarget, FuncHeader, Param},
+        binary::Binary,
+        block::Block,
+        call::{Call, CallArg},
+        conversion::Conversion,
+        expr::{Expr, Primary},
+        if_expr::If,
+        let_expr::Let,
+        literal::{LitBool, LitFloat, LitInt, LitList, LitRadix, LitSlot, LitSym, LitTuple, LitUnit, Literal},
+        loop_expr::{Break, Continue, Loop},
+        measurement::Measurement,
+        paren::Paren,
+        piecewise::{Branch, Piecewise},
+        program::Program,
+        stmt::Stmt,
+        unary::Unary,
+        while_expr::While,
+    },
+    token::op::{AssignOp, BinOp, UnaryOp},
+};
+use std::{hash::{Hash, Hasher}, mem, ops::Range};
+
+/// A type containing spans that can be cleared.
+pub trait ClearSpans {
+    /// Sets every span in this value, including the spans of nested values, to `0..0`.
+    fn clear_spans(&mut self);
+}
+
+impl ClearSpans for Range<usize> {
+    fn clear_spans(&mut self) {
+        *self = 0..0;
+    }
+}
+
+impl<T: ClearSpans> ClearSpans for Option<T> {
+    fn clear_spans(&mut self) {
+        if let Some(value) = self {
+            value.clear_spans();
+        }
+    }
+}
+
+impl<T: ClearSpans> ClearSpans for Box<T> {
+    fn clear_spans(&mut self) {
+        (**self).clear_spans();
+    }
+}
+
+impl<T: ClearSpans> ClearSpans for Vec<T> {
+    fn clear_spans(&mut self) {
+        for value in self {
+            value.clear_spans();
+        }
+    }
+}
+
+/// Implements [`ClearSpans`] for a `struct` by clearing the spans of the given fields. Fields that
+/// cannot contain spans should not be listed.
+macro_rules! clear_fields {
+    ($($ty:ident { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl ClearSpans for $ty {
+                fn clear_spans(&mut self) {
+                    $(self.$field.clear_spans();)*
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`ClearSpans`] for an `enum` whose variants each contain exactly one value.
+macro_rules! clear_variants {
+    ($($ty:ident { $($variant:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl ClearSpans for $ty {
+                fn clear_spans(&mut self) {
+                    match self {
+                        $($ty::$variant(value) => value.clear_spans(),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+clear_fields! {
+    LitInt { span },
+    LitFloat { span },
+    LitRadix { span },
+    LitBool { span },
+    LitSym { span },
+    LitUnit { span },
+    LitList { values, span },
+    LitTuple { values, span },
+    LitSlot { span },
+    UnaryOp { span },
+    BinOp { span },
+    AssignOp { span },
+    Measurement { value, unit, span },
+    Paren { expr, span },
+    Block { stmts, span },
+    Branch { value, condition, if_span },
+    Piecewise { branches, otherwise, otherwise_span, span },
+    If { condition, then_expr, else_expr, span, if_span, then_span, else_span },
+    Loop { body, span, loop_span },
+    While { condition, body, span, while_span, then_span },
+    Let { name, value, body, span, let_span, in_span },
+    Break { value, span, break_span },
+    Continue { span },
+    Call { name, args, span, paren_span },
+    Unary { operand, op, span },
+    Binary { lhs, op, rhs, span },
+    FuncHeader { name, params, span },
+    Assign { target, op, value, span },
+    Conversion { value, target, to_span, span },
+    Stmt { expr, semicolon, span },
+    Program { stmts, span },
+}
+
+clear_variants! {
+    Literal { Integer, Float, Radix, Boolean, Symbol, Unit, List, Tuple, Slot },
+    AssignTarget { Symbol, Func, Destructure },
+    Expr {
+        Literal,
+        Measurement,
+        Paren,
+        Block,
+        Piecewise,
+        If,
+        Loop,
+        While,
+        Let,
+        Break,
+        Continue,
+        Call,
+        Unary,
+        Binary,
+        Assign,
+        Conversion,
+    },
+    Primary { Literal, Measurement, Paren, Block, Piecewise, If, Loop, While, Let, Break, Continue, Call },
+}
+
+impl ClearSpans for Param {
+    fn clear_spans(&mut self) {
+        match self {
+            Param::Symbol(symbol) => symbol.clear_spans(),
+            Param::Default(symbol, expr) => {
+                symbol.clear_spans();
+                expr.clear_spans();
+            },
+        }
+    }
+}
+
+impl ClearSpans for CallArg {
+    fn clear_spans(&mut self) {
+        match self {
+            CallArg::Positional(expr) => expr.clear_spans(),
+            CallArg::Named { name, value, span } => {
+                name.clear_spans();
+                value.clear_spans();
+                span.clear_spans();
+            },
+        }
+    }
+}
+
+/// Implements [`Hash`] for a `struct` by hashing the given fields. Spans should not be listed.
+macro_rules! hash_fields {
+    ($($ty:ident { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl Hash for $ty {
+                fn hash<H: Hasher>(&self, _state: &mut H) {
+                    $(self.$field.hash(_state);)*
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`Hash`] for an `enum` whose variants each contain exactly one value.
+macro_rules! hash_variants {
+    ($($ty:ident { $($variant:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl Hash for $ty {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    mem::discriminant(self).hash(state);
+                    match self {
+                        $($ty::$variant(value) => value.hash(state),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+hash_fields! {
+    LitInt { value },
+    LitFloat { value },
+    LitRadix { base, value },
+    LitBool { value },
+    LitSym { name },
+    LitUnit {},
+    LitList { values },
+    LitTuple { values },
+    LitSlot { index, name },
+    UnaryOp { kind },
+    BinOp { kind, implicit },
+    AssignOp { kind },
+    Measurement { value, unit },
+    Paren { expr },
+    Block { stmts },
+    Branch { value, condition },
+    Piecewise { branches, otherwise },
+    If { condition, then_expr, else_expr },
+    Loop { body },
+    While { condition, body },
+    Let { name, value, body },
+    Break { value },
+    Continue {},
+    Call { name, derivatives, args },
+    Unary { operand, op },
+    Binary { lhs, op, rhs },
+    FuncHeader { name, params },
+    Assign { target, op, value },
+    Conversion { value, target },
+    Program { stmts },
+}
+
+hash_variants! {
+    Literal { Integer, Float, Radix, Boolean, Symbol, Unit, List, Tuple, Slot },
+    AssignTarget { Symbol, Func, Destructure },
+    Expr {
+        Literal,
+        Measurement,
+        Paren,
+        Block,
+        Piecewise,
+        If,
+        Loop,
+        While,
+        Let,
+        Break,
+        Continue,
+        Call,
+        Unary,
+        Binary,
+        Assign,
+        Conversion,
+    },
+    Primary { Literal, Measurement, Paren, Block, Piecewise, If, Loop, While, Let, Break, Continue, Call },
+}
+
+impl Hash for Param {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Param::Symbol(symbol) => symbol.hash(state),
+            Param::Default(symbol, expr) => {
+                symbol.hash(state);
+                expr.hash(state);
+            },
+        }
+    }
+}
+
+impl Hash for Stmt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expr.hash(state);
+        self.semicolon.is_some().hash(state);
+    }
+}
+
+impl Hash for CallArg {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            CallArg::Positional(expr) => expr.hash(state),
+            CallArg::Named { name, value, .. } => {
+                name.hash(state);
+                value.hash(state);
+            },
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use crate::{
     parser::{
-        ast::{binary::Binary, expr::{Expr, Primary}},
+        ast::{binary::Binary, expr::{Expr, Primary}, literal::{Literal, LitInt}},
         error::{kind, Error},
         fmt::Latex,
-        token::op::{Associativity, UnaryOp},
+        token::op::{Associativity, BinOp, BinOpKind, UnaryOp, UnaryOpKind},
         Parser,
         ParseResult,
     },
     return_if_ok,
 };
+use crate::tokenizer::TokenKind;
 use std::{fmt, ops::Range};
 
 #[cfg(feature = "serde")]
@@ -26,6 +27,47 @@ fn try_parse_unary_op(input: &mut Parser, associativity: Associativity) -> Resul
     }).forward_errors(&mut Vec::new())
 }
 
+/// If the token directly after the given operand (with no whitespace in between) is a run of
+/// superscript digits, such as in `2²³`, consumes it and desugars the superscript into an
+/// exponentiation, `2^23`. Otherwise, the operand is returned as-is.
+fn parse_superscript(input: &mut Parser, operand: Expr) -> Expr {
+    let Some(token) = input.current_token().filter(|token| token.kind == TokenKind::Superscript) else {
+        return operand;
+    };
+    let span = token.span.clone();
+    let value = token.lexeme.chars()
+        .map(|ch| match ch {
+            '⁰' => '0',
+            '¹' => '1',
+            '²' => '2',
+            '³' => '3',
+            '⁴' => '4',
+            '⁵' => '5',
+            '⁶' => '6',
+            '⁷' => '7',
+            '⁸' => '8',
+            '⁹' => '9',
+            _ => unreachable!("superscript token contains only superscript digits"),
+        })
+        .collect();
+    input.next_token_raw();
+
+    let start_span = operand.span().start;
+    Expr::Binary(Binary {
+        lhs: Box::new(operand),
+        op: BinOp {
+            kind: BinOpKind::Exp,
+            implicit: false,
+            span: span.clone(),
+        },
+        rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+            value,
+            span: span.clone(),
+        }))),
+        span: start_span..span.end,
+    })
+}
+
 /// A unary expression, such as `2!`. Unary expressions can include nested expressions.
 ///
 /// Unary expressions do not directly implement [`Parse`] due to performance implications involving
@@ -34,7 +76,7 @@ fn try_parse_unary_op(input: &mut Parser, associativity: Associativity) -> Resul
 /// parse unary expressions.
 ///
 /// [`Parse`]: crate::parser::Parse
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Unary {
     /// The operand of the unary expression (left or right, depending on the associativity).
@@ -63,6 +105,17 @@ impl Unary {
             Binary::parse_expr(input, recoverable_errors, lhs, op_precedence)?.0
         };
         let end_span = operand.span().end;
+
+        // warn about ambiguous expressions, such as `-3!`, which is parsed as `-(3!)`
+        if op.kind == UnaryOpKind::Neg {
+            if let Expr::Unary(Unary { op: UnaryOp { kind: UnaryOpKind::Factorial, .. }, .. }) = &operand {
+                recoverable_errors.push(Error::new(
+                    vec![start_span..end_span],
+                    kind::AmbiguousNegFactorial,
+                ));
+            }
+        }
+
         Ok(Self {
             operand: Box::new(operand),
             op,
@@ -78,15 +131,16 @@ impl Unary {
     /// is no operator present.
     pub fn parse_left_or_operand(input: &mut Parser, recoverable_errors: &mut Vec<Error>) -> Result<Expr, Vec<Error>> {
         let operand = input.try_parse::<Primary>().forward_errors(recoverable_errors)?;
+        let operand = parse_superscript(input, operand.into());
         let start_span = operand.span().start;
 
         // one operator must be present
         let op = match try_parse_unary_op(input, Associativity::Left) {
             Ok(op) => op,
-            Err(_) => return Ok(operand.into()),
+            Err(_) => return Ok(operand),
         };
         let mut result = Self {
-            operand: Box::new(operand.into()),
+            operand: Box::new(operand),
             op,
             span: start_span..input.prev_token().unwrap().span.end,
         };
@@ -1,8 +1,8 @@
 use ariadne::Fmt;
 use cas_attrs::ErrorKind;
-use cas_error::{ErrorKind, EXPR};
+use cas_error::{ErrorKind, Severity, EXPR};
 use crate::tokenizer::TokenKind;
-use std::{collections::HashSet, ops::Range};
+use std::{borrow::Cow, collections::HashSet, ops::Range};
 
 /// An intentionally useless error. This should only be used for non-fatal errors, as it contains
 /// no useful information.
@@ -22,6 +22,28 @@ pub struct NonFatal;
 )]
 pub struct UnexpectedEof;
 
+/// There was nothing to parse; the source code was empty or contained only whitespace and comments.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "no input was given",
+    labels = [format!("I expected to see an {} here", "expression".fg(EXPR))],
+)]
+pub struct EmptyInput;
+
+/// The end of the source code was reached after an operator, before its right-hand operand.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("missing right operand for `{}`", self.op),
+    labels = [
+        "this operator".to_string(),
+        format!("I expected to see an {} here", "expression".fg(EXPR)),
+    ],
+)]
+pub struct MissingOperand {
+    /// The operator that is missing its right operand.
+    pub op: String,
+}
+
 /// The end of the source code was expected, but something else was found.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -34,30 +56,111 @@ pub struct ExpectedEof;
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
     message = "unexpected token",
-    labels = [format!("expected one of: {}", self.expected.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", "))],
-    help = format!("found {:?}", self.found),
+    labels = [format!("expected one of: {}", self.sorted_expected().iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", "))],
+    help = format!("found {:?} ({:?})", self.found_lexeme, self.found),
 )]
 pub struct UnexpectedToken {
     /// The token(s) that were expected.
-    pub expected: &'static [TokenKind],
+    pub expected: Cow<'static, [TokenKind]>,
 
     /// The token that was found.
     pub found: TokenKind,
+
+    /// The source text of the token that was found.
+    pub found_lexeme: String,
+}
+
+impl UnexpectedToken {
+    /// Returns the expected tokens, sorted and without duplicates, so that the error message does
+    /// not depend on the order in which the expected tokens were collected.
+    pub fn sorted_expected(&self) -> Vec<TokenKind> {
+        let mut expected = self.expected.to_vec();
+        expected.sort();
+        expected.dedup();
+        expected
+    }
 }
 
+/// A character was found that is not part of any token in the language.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("unexpected character `{}`", self.ch),
+    labels = ["this character"],
+    help = "remove this character, or check for a typo"
+)]
+pub struct UnexpectedChar {
+    /// The character that was found.
+    pub ch: char,
+}
+
+/// A unary minus is directly followed by a factorial, such as in `-3!`. This is parsed as `-(3!)`,
+/// which can be surprising.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "ambiguous negation of a factorial",
+    labels = ["this is parsed as the negation of the factorial"],
+    help = format!("add parentheses to make the intent clear: {} or {}", "-(x!)".fg(EXPR), "(-x)!".fg(EXPR)),
+    severity = Severity::Warning,
+)]
+pub struct AmbiguousNegFactorial;
+
+/// Parentheses surround an expression that is already parsed as a single unit, such as `(3)`, so
+/// removing them would not change how the expression is parsed.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "redundant parentheses",
+    labels = ["these parentheses can be removed"],
+    severity = Severity::Warning,
+)]
+pub struct RedundantParentheses;
+
+/// The default value of a parameter references a parameter that is declared after it.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "default value references a later parameter",
+    labels = [
+        format!("`{}` is used here", self.name),
+        format!("`{}` is declared here", self.name),
+    ],
+    help = "default values can only reference parameters declared before them"
+)]
+pub struct DefaultReferencesLaterParam {
+    /// The name of the parameter that was referenced.
+    pub name: String,
+}
+
+/// A positional argument was found after a named argument in a function call.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "positional argument found after a named argument",
+    labels = ["this positional argument"],
+    help = "move positional arguments before any named arguments"
+)]
+pub struct PositionalAfterNamedArgument;
+
 /// Encountered a keyword when a symbol name was expected.
-// TODO: this error is not currently reported, see `impl Parse for LitSym`
+// TODO: this error is only reported for the target of an assignment, see `impl Parse for LitSym`
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
     message = "expected symbol name",
     labels = [format!("found keyword `{}`", self.keyword)],
-    help = "you cannot use keywords as symbol names"
+    help = format!("you cannot use keywords as symbol names; try a different name, such as `{}_`", self.keyword),
+    note = format!("`{}` is a reserved keyword and cannot be assigned to", self.keyword),
 )]
 pub struct ExpectedSymbolName {
     /// The keyword that was found.
     pub keyword: String,
 }
 
+/// The `to` keyword of a unit conversion was not followed by the name of a unit.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "expected a unit to convert to",
+    labels = ["here"],
+    help = format!("write the name of the target unit after `to`, such as {}", "2 mi to km".fg(EXPR)),
+)]
+pub struct ExpectedUnit;
+
 /// The base used in radix notation was out of the allowed range.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -67,11 +170,17 @@ pub struct ExpectedSymbolName {
     } else {
         "this value is too small"
     }],
-    help = format!("the base must be {}", "between 2 and 64, inclusive".fg(EXPR)),
+    help = format!("the base must be {}", format!("between {} and {}, inclusive", self.min, self.max).fg(EXPR)),
 )]
 pub struct InvalidRadixBase {
     /// The given base was too large. (Otherwise, it was too small.)
     pub too_large: bool,
+
+    /// The smallest allowed base.
+    pub min: u8,
+
+    /// The largest allowed base.
+    pub max: u8,
 }
 
 /// An invalid digit was used in a radix literal.
@@ -94,8 +203,41 @@ pub struct InvalidRadixDigit {
     pub last_op_digit: Option<(char, Range<usize>)>,
 }
 
+/// Builds a table mapping the values of the given digits to the digits themselves, where the digit
+/// at index `i` has the value `i`. Runs of consecutive characters are grouped together, such as
+/// `10–15 → a–f`.
+fn digit_table(digits: &[char]) -> String {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (value, &digit) in digits.iter().enumerate() {
+        match runs.last_mut() {
+            Some((_, end)) if char::from_u32(digits[*end] as u32 + 1) == Some(digit) => *end = value,
+            _ => runs.push((value, value)),
+        }
+    }
+
+    runs.into_iter()
+        .map(|(start, end)| if start == end {
+            format!("{} → {}", start, digits[start])
+        } else {
+            format!("{}–{} → {}–{}", start, end, digits[start], digits[end])
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // manual ErrorKind implementation to support the `last_op_digit` field
 impl ErrorKind for InvalidRadixDigit {
+    fn message(&self) -> String {
+        format!(
+            "invalid digits in radix notation: `{}`",
+            self.digits
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join("`, `"),
+        )
+    }
+
     fn build_report(
         &self,
         src_id: &'static str,
@@ -138,20 +280,22 @@ impl ErrorKind for InvalidRadixDigit {
 
         let mut builder =
             ariadne::Report::build(ariadne::ReportKind::Error, src_id, spans[0].start)
-                .with_message(format!(
-                    "invalid digits in radix notation: `{}`",
-                    self.digits
-                        .iter()
-                        .map(|c| c.to_string())
-                        .collect::<Vec<_>>()
-                        .join("`, `"),
-                ))
+                .with_message(self.message())
                 .with_labels(labels);
         builder.set_help(format!(
             "base {} uses these digits (from lowest to highest value): {}",
             self.radix,
             self.allowed.iter().collect::<String>().fg(EXPR)
         ));
+
+        // past base 10, it is not obvious which letters map to which values
+        if self.radix > 10 {
+            builder.set_note(format!(
+                "in base {}, the digits have these values: {}",
+                self.radix,
+                digit_table(self.allowed),
+            ));
+        }
         builder.finish()
     }
 }
@@ -171,6 +315,15 @@ pub struct EmptyRadixLiteral {
     pub allowed: &'static [char],
 }
 
+/// A pair of parentheses used for grouping contained no expression.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "missing expression inside parentheses",
+    labels = ["these parentheses are empty"],
+    help = format!("write an expression inside the parentheses, such as {}", "(1 + 2)".fg(EXPR)),
+)]
+pub struct EmptyParenthesis;
+
 /// A parenthesis was not closed.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -188,6 +341,15 @@ pub struct UnclosedParenthesis {
     pub opening: bool,
 }
 
+/// The opening curly brace of a block or piecewise expression was not closed.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = "unclosed curly brace",
+    labels = ["this curly brace is not closed"],
+    help = "add a closing curly brace `}` somewhere after this",
+)]
+pub struct UnclosedCurlyBrace;
+
 /// The left-hand-side of an assignment was not a valid symbol or function header.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
@@ -226,12 +388,15 @@ pub struct CompoundAssignmentInHeader;
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
     message = "too many derivatives in prime notation",
-    labels = ["you can only take at most 255 derivatives of a function"],
+    labels = [format!("you can only take at most {} derivatives of a function", self.max)],
     help = format!("I counted {} derivatives here", self.derivatives),
 )]
 pub struct TooManyDerivatives {
     /// The number of derivatives that were found.
     pub derivatives: usize,
+
+    /// The maximum number of derivatives allowed by the parser.
+    pub max: usize,
 }
 
 /// Missing `then` or `else` keyword in an `if` expression.
@@ -256,6 +421,17 @@ pub struct MissingIfBranch {
     pub keyword: &'static str,
 }
 
+/// Missing `in` keyword in a `let` expression.
+#[derive(Debug, Clone, ErrorKind, PartialEq)]
+#[error(
+    message = format!("missing `{}` in `let` expression", self.keyword),
+    labels = ["this `let` expression".to_string(), format!("I expected to see `{}` here", self.keyword)],
+)]
+pub struct MissingLetKeyword {
+    /// The keyword that was expected.
+    pub keyword: &'static str,
+}
+
 /// Cannot use `break` outside of a loop.
 #[derive(Debug, Clone, ErrorKind, PartialEq)]
 #[error(
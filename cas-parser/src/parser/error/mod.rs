@@ -1,8 +1,9 @@
 pub mod kind;
 
 use ariadne::Report;
-use cas_error::ErrorKind;
-use std::ops::Range;
+use cas_error::{ErrorKind, Severity};
+use crate::{parser::line_col, tokenizer::{Token, TokenKind}};
+use std::{any::Any, borrow::Cow, ops::Range};
 
 /// A general parsing error.
 #[derive(Debug)]
@@ -20,8 +21,80 @@ impl Error {
         Self { spans, kind: Box::new(kind) }
     }
 
+    /// Creates an error for finding the given token when one of the `expected` tokens was
+    /// expected.
+    ///
+    /// This is a [`kind::UnexpectedToken`] error, or a [`kind::UnexpectedEof`] error if the token
+    /// is the end of the source code.
+    pub fn unexpected_token(token: &Token, expected: Cow<'static, [TokenKind]>) -> Self {
+        if token.is_eof() {
+            return Self::new(vec![token.span.clone()], kind::UnexpectedEof);
+        }
+
+        Self::new(vec![token.span.clone()], kind::UnexpectedToken {
+            expected,
+            found: token.kind,
+            found_lexeme: token.lexeme.to_owned(),
+        })
+    }
+
+    /// Returns true if this error is only a warning, and does not prevent parsing from succeeding.
+    pub fn is_warning(&self) -> bool {
+        self.kind.severity() == Severity::Warning
+    }
+
+    /// Returns the kind of error as a reference to the concrete type `T`, or [`None`] if the error
+    /// is of a different kind.
+    pub fn downcast_kind<T: ErrorKind>(&self) -> Option<&T> {
+        (self.kind.as_ref() as &dyn Any).downcast_ref()
+    }
+
+    /// Returns the set of tokens that were expected if this error is an
+    /// [`kind::UnexpectedToken`] error, or [`None`] otherwise.
+    ///
+    /// This can be used to determine what could legally come next after a failed parse, such as
+    /// when building completions.
+    pub fn expected_tokens(&self) -> Option<&[TokenKind]> {
+        self.downcast_kind::<kind::UnexpectedToken>()
+            .map(|kind| kind.expected.as_ref())
+    }
+
     /// Build a report from this error kind.
     pub fn build_report(&self) -> Report<(&'static str, Range<usize>)> {
         self.kind.build_report("input", &self.spans)
     }
+
+    /// Renders this error as plain text, without colors or box-drawing characters, which is useful
+    /// for logs and test snapshots.
+    ///
+    /// The output is a `line:col: message` header, followed by the source line containing the start
+    /// of the first span, with the span underlined by carets. Line and column numbers start at 1.
+    pub fn render_plain(&self, src: &str) -> String {
+        let message = self.kind.message();
+        let Some(span) = self.spans.first() else {
+            return message;
+        };
+
+        // line breaks are counted the same way as in `Parser::line_col`, and are not part of the
+        // printed line
+        let start = span.start.min(src.len());
+        let (line_num, col) = line_col(src, start);
+        let line_start = src[..start].rfind(['\n', '\r']).map_or(0, |i| i + 1);
+        let line_end = src[start..].find(['\n', '\r']).map_or(src.len(), |i| start + i);
+        let line = &src[line_start..line_end];
+
+        // underline to the end of the span, or the end of the line if the span continues past it
+        let end = span.end.clamp(start, line_end);
+        let width = src[start..end].chars().count().max(1);
+
+        format!(
+            "{}:{}: {}\n{}\n{}{}",
+            line_num + 1,
+            col + 1,
+            message,
+            line,
+            " ".repeat(col),
+            "^".repeat(width),
+        )
+    }
 }
@@ -1,5 +1,5 @@
 use std::fmt::{Display, Formatter, Result};
-use super::{ast::expr::Expr, token::op::BinOpKind};
+use super::{ast::expr::Expr, token::op::BinOpKind, Commented};
 
 /// A trait for types that can be formatted as LaTeX.
 pub trait Latex {
@@ -54,11 +54,34 @@ pub fn fmt_pow(f: &mut Formatter, left: Option<&Expr>, right: Option<&Expr>) ->
     write!(f, "}}")
 }
 
+/// Formats the given values one per line, along with the comments attached to them by
+/// [`Parser::try_parse_full_many_with_comments`]. The comments that follow the last value are
+/// placed on their own lines at the end.
+///
+/// Leading comments are placed on their own line(s) before their value. Trailing comments are
+/// placed at the end of their value's line.
+///
+/// [`Parser::try_parse_full_many_with_comments`]: super::Parser::try_parse_full_many_with_comments
+pub fn fmt_with_comments<T: Display>(values: &[Commented<T>], end: &[&str]) -> String {
+    let mut lines = Vec::new();
+    for commented in values {
+        lines.extend(commented.leading.iter().map(|comment| comment.to_string()));
+        let mut line = commented.value.to_string();
+        for comment in &commented.trailing {
+            line.push(' ');
+            line.push_str(comment);
+        }
+        lines.push(line);
+    }
+    lines.extend(end.iter().map(|comment| comment.to_string()));
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::parser::Parser;
+    use crate::parser::{ast::stmt::Stmt, Parser};
 
     #[test]
     fn fmt_display() {
@@ -104,4 +127,23 @@ mod tests {
 
         assert_eq!(fmt, "\\mathrm{ f } \\left(x\\right) = \\frac{1}{x}+\\frac{5}{x^{2}}+\\frac{6}{x^{3}}");
     }
+
+    #[test]
+    fn fmt_with_comments_inside_expr() {
+        let source = "3 + // note\n4";
+        let (stmts, end) = Parser::new(source).try_parse_full_many_with_comments::<Stmt>().unwrap();
+
+        assert_eq!(fmt_with_comments(&stmts, &end), "3+4 // note");
+    }
+
+    #[test]
+    fn fmt_with_comments_leading_trailing() {
+        let source = "// leading\nx = 1;  // trailing\nx + 2\n// end";
+        let (stmts, end) = Parser::new(source).try_parse_full_many_with_comments::<Stmt>().unwrap();
+
+        assert_eq!(
+            fmt_with_comments(&stmts, &end),
+            "// leading\nx = 1; // trailing\nx+2\n// end",
+        );
+    }
 }
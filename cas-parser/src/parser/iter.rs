@@ -41,7 +41,7 @@ impl<'a> Iterator for ExprIter<'a> {
         loop {
             let expr = self.stack.last()?;
             match expr {
-                Expr::Literal(_) => return self.visit(),
+                Expr::Literal(_) | Expr::Measurement(_) => return self.visit(),
                 Expr::Paren(paren) => {
                     if self.is_last_visited(&paren.expr) {
                         return self.visit();
@@ -49,6 +49,20 @@ impl<'a> Iterator for ExprIter<'a> {
                     self.stack.push(&paren.expr);
                 },
                 Expr::Block(_) => return self.visit(), // NOTE: inner statements are not visited
+                Expr::Piecewise(piecewise) => {
+                    let last = piecewise.otherwise.as_deref()
+                        .or_else(|| piecewise.branches.last().map(|branch| &branch.condition));
+                    if last.is_none_or(|last| self.is_last_visited(last)) {
+                        return self.visit();
+                    }
+                    if let Some(otherwise) = &piecewise.otherwise {
+                        self.stack.push(otherwise);
+                    }
+                    for branch in piecewise.branches.iter().rev() {
+                        self.stack.push(&branch.condition);
+                        self.stack.push(&branch.value);
+                    }
+                },
                 Expr::If(if_expr) => {
                     if let Some(else_expr) = &if_expr.else_expr {
                         if self.is_last_visited(else_expr) {
@@ -78,6 +92,13 @@ impl<'a> Iterator for ExprIter<'a> {
                     self.stack.push(&while_expr.body);
                     self.stack.push(&while_expr.condition);
                 },
+                Expr::Let(let_expr) => {
+                    if self.is_last_visited(&let_expr.body) {
+                        return self.visit();
+                    }
+                    self.stack.push(&let_expr.body);
+                    self.stack.push(&let_expr.value);
+                },
                 Expr::Break(break_expr) => {
                     if let Some(value) = &break_expr.value {
                         if self.is_last_visited(value) {
@@ -90,11 +111,11 @@ impl<'a> Iterator for ExprIter<'a> {
                 },
                 Expr::Continue(_) => return self.visit(),
                 Expr::Call(call) => {
-                    if call.args.is_empty() || self.is_last_visited(call.args.last().unwrap()) {
+                    if call.args.is_empty() || self.is_last_visited(call.args.last().unwrap().value()) {
                         return self.visit();
                     }
                     for arg in call.args.iter().rev() {
-                        self.stack.push(arg);
+                        self.stack.push(arg.value());
                     }
                 },
                 Expr::Unary(unary) => {
@@ -116,6 +137,12 @@ impl<'a> Iterator for ExprIter<'a> {
                     }
                     self.stack.push(&assign.value);
                 },
+                Expr::Conversion(conversion) => {
+                    if self.is_last_visited(&conversion.value) {
+                        return self.visit();
+                    }
+                    self.stack.push(&conversion.value);
+                },
             }
         }
     }
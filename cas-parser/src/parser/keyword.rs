@@ -1,8 +1,8 @@
 use crate::{
-    parser::{error::{kind, Error}, garbage::Garbage, Parser, Parse},
+    parser::{error::Error, garbage::Garbage, Parser, Parse},
     tokenizer::TokenKind,
 };
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 
 /// Generates a unit struct for each keyword, as well as a simple [`Parse`] implementation for each
 /// keyword. This enables the parser to use and request keywords as a type, which is much more
@@ -21,7 +21,7 @@ macro_rules! keywords {
                     input: &mut Parser<'source>,
                     _: &mut Vec<Error>
                 ) -> Result<Self, Vec<Error>> {
-                    let token = input.next_token().map_err(|e| vec![e])?;
+                    let token = input.next_token();
 
                     if token.kind == TokenKind::Keyword {
                         if token.lexeme != stringify!($lexeme) {
@@ -30,20 +30,14 @@ macro_rules! keywords {
                             //     found: token.kind,
                             // })]);
                             // TODO
-                            return Err(vec![Error::new(vec![token.span], kind::UnexpectedToken {
-                                expected: &[TokenKind::Keyword],
-                                found: token.kind,
-                            })]);
+                            return Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[TokenKind::Keyword]))]);
                         }
                         Ok(Self {
                             lexeme: token.lexeme,
                             span: token.span,
                         })
                     } else {
-                        Err(vec![Error::new(vec![token.span], kind::UnexpectedToken {
-                            expected: &[TokenKind::Keyword],
-                            found: token.kind,
-                        })])
+                        Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[TokenKind::Keyword]))])
                     }
                 }
             }
@@ -61,8 +55,10 @@ keywords!(
     (If, if)
     (Then, then)
     (Else, else)
+    (Otherwise, otherwise)
     (Loop, loop)
     (While, while)
     (Break, break)
     (Continue, continue)
+    (Let, let)
 );
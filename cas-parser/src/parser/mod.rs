@@ -8,8 +8,8 @@ pub mod token;
 
 use cas_error::ErrorKind;
 use error::{Error, kind};
-use super::tokenizer::{tokenize_complete, Token};
-use std::{ops::Range, sync::Arc};
+use super::tokenizer::{tokenize_complete, Token, TokenKind};
+use std::{borrow::Cow, ops::Range, sync::{Arc, Mutex}};
 
 /// State that can be used to determine if certain parse trees are valid (e.g. if a checking if a
 /// `break` expression is inside a loop).
@@ -20,6 +20,141 @@ pub struct ParserState {
     /// Whether loop control expressions are allowed in the current context. This is used to
     /// determine if a `break` or `continue` expression is valid.
     pub allow_loop_control: bool,
+
+    /// Whether an `if` keyword ends the current expression, instead of starting an implicitly
+    /// multiplied `if` expression. This is used to parse the branches of a
+    /// [`Piecewise`](crate::parser::ast::piecewise::Piecewise) expression.
+    pub if_ends_expr: bool,
+
+    /// Whether the parser is inside a comma-separated list, such as the arguments of a function
+    /// call. In a list, commas separate the values of the list, so they cannot also separate the
+    /// targets of a destructuring assignment, such as `a, b = (1, 2)`.
+    pub in_comma_list: bool,
+
+    /// Whether an `in` keyword ends the current expression, instead of being parsed as a symbol.
+    /// This is used to parse the value of a [`Let`](crate::parser::ast::let_expr::Let) expression.
+    pub in_ends_expr: bool,
+}
+
+/// Options that control which language features are available to a [`Parser`], created with
+/// [`Parser::with_options`].
+///
+/// The [`Default`] options reproduce the behavior of [`Parser::new`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions {
+    /// Whether implicit multiplication, such as `2x` or `x(x + 1)`, is allowed. When disabled,
+    /// two expressions next to each other without an operator between them result in an error.
+    ///
+    /// Defaults to `true`.
+    pub implicit_multiplication: bool,
+
+    /// Determines which names are units when parsing in unit mode, in which a number followed by
+    /// the name of a unit, such as `5 km`, is parsed as an [`ast::Measurement`]. If [`None`],
+    /// unit mode is disabled.
+    ///
+    /// Defaults to [`None`].
+    pub unit_names: Option<fn(&str) -> bool>,
+
+    /// The smallest base allowed in radix notation, such as the `2` in `2'1010`. Values below 2
+    /// are treated as 2.
+    ///
+    /// Defaults to `2`.
+    pub min_radix: u8,
+
+    /// The largest base allowed in radix notation. There are only enough digits for bases up to
+    /// 64, so values above 64 are treated as 64.
+    ///
+    /// Defaults to `64`.
+    pub max_radix: u8,
+
+    /// The largest number of derivatives that can be taken of a function in prime notation, such
+    /// as the two derivatives in `f''(x)`.
+    ///
+    /// Defaults to `255`.
+    pub max_derivatives: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            implicit_multiplication: true,
+            unit_names: None,
+            min_radix: 2,
+            max_radix: 64,
+            max_derivatives: 255,
+        }
+    }
+}
+
+/// The kinds of tokens that were expected at the furthest position where parsing failed. See
+/// [`Parser::parse_prefix`].
+#[derive(Debug, Default)]
+struct Expected {
+    /// The start of the furthest token that failed to parse.
+    offset: usize,
+
+    /// The kinds of tokens that were expected in place of that token.
+    kinds: Vec<TokenKind>,
+}
+
+impl Expected {
+    /// Records that one of the given kinds of tokens was expected in place of the token starting at
+    /// the given offset. Tokens expected before the furthest offset are discarded.
+    fn record(&mut self, offset: usize, kinds: &[TokenKind]) {
+        if offset > self.offset {
+            self.offset = offset;
+            self.kinds.clear();
+        }
+        if offset == self.offset {
+            self.kinds.extend_from_slice(kinds);
+        }
+    }
+}
+
+/// A value parsed with [`Parser::try_parse_full_many_with_comments`], along with the comments
+/// attached to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commented<'source, T> {
+    /// The parsed value.
+    pub value: T,
+
+    /// The comments on their own line(s) before the value.
+    pub leading: Vec<&'source str>,
+
+    /// The comments inside the value, or after it on the same line as its end.
+    pub trailing: Vec<&'source str>,
+}
+
+/// The values parsed with [`Parser::try_parse_full_many_with_comments`], and the comments after
+/// the last value.
+type CommentedMany<'source, T> = (Vec<Commented<'source, T>>, Vec<&'source str>);
+
+/// A saved position in the token stream of a [`Parser`], created with [`Parser::checkpoint`].
+///
+/// Restoring a checkpoint with [`Parser::restore`] moves the parser's cursor back to the saved
+/// position. A checkpoint only stores the cursor position, so it is cheap to create and copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The index of the **next** token to be parsed at the time the checkpoint was created.
+    cursor: usize,
+}
+
+/// Returns the zero-based line and column numbers of the given byte offset into the source code.
+/// See [`Parser::line_col`].
+pub(crate) fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let (mut line, mut col) = (0, 0);
+    let mut chars = source.char_indices().peekable();
+    while let Some((_, ch)) = chars.next_if(|&(i, _)| i < offset) {
+        match ch {
+            '\r' if chars.peek().is_some_and(|&(_, next)| next == '\n') => (),
+            '\r' | '\n' => {
+                line += 1;
+                col = 0;
+            },
+            _ => col += 1,
+        }
+    }
+    (line, col)
 }
 
 /// A high-level parser for the language. This is the type to use to parse an arbitrary piece of
@@ -29,6 +164,9 @@ pub struct ParserState {
 /// thus is relatively cheap to clone.
 #[derive(Debug, Clone)]
 pub struct Parser<'source> {
+    /// The source code that this parser is parsing.
+    source: &'source str,
+
     /// The tokens that this parser is currently parsing.
     tokens: Arc<[Token<'source>]>,
 
@@ -40,18 +178,95 @@ pub struct Parser<'source> {
     ///
     /// The state cannot be mutated directly; it can only be changed when parsing using the [`Parser::try_parse_with_state`] method.
     state: ParserState,
+
+    /// The language features available to this parser. See [`ParseOptions`].
+    options: ParseOptions,
+
+    /// The tokens that were expected at the furthest position where parsing failed, shared with
+    /// speculative copies of this parser. This is only recorded while running
+    /// [`Parser::parse_prefix`].
+    expected: Option<Arc<Mutex<Expected>>>,
 }
 
 impl<'source> Parser<'source> {
-    /// Create a new parser for the given source.
+    /// Create a new parser for the given source, using the default [`ParseOptions`].
     pub fn new(source: &'source str) -> Self {
+        Self::with_options(source, ParseOptions::default())
+    }
+
+    /// Create a new parser for the given source, using the given [`ParseOptions`].
+    pub fn with_options(source: &'source str, options: ParseOptions) -> Self {
         Self {
+            source,
             tokens: tokenize_complete(source).into(),
             cursor: 0,
             state: ParserState::default(),
+            options,
+            expected: None,
         }
     }
 
+    /// Returns the options this parser was created with.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Returns true if implicit multiplication is enabled. See
+    /// [`ParseOptions::implicit_multiplication`].
+    pub fn is_implicit_multiplication(&self) -> bool {
+        self.options.implicit_multiplication
+    }
+
+    /// Returns true if unit mode is enabled. See [`ParseOptions::unit_names`].
+    pub fn is_unit_mode(&self) -> bool {
+        self.options.unit_names.is_some()
+    }
+
+    /// Returns true if unit mode is enabled and the given name is a unit.
+    pub fn is_unit(&self, name: &str) -> bool {
+        self.options.unit_names.is_some_and(|is_unit| is_unit(name))
+    }
+
+    /// Create a new parser for the given source, returning an error if the source contains a
+    /// character that cannot be tokenized.
+    ///
+    /// Unlike [`Parser::new`], which leaves such characters in the token stream to be reported as
+    /// unexpected tokens during parsing, this function reports the exact offending character and
+    /// its span.
+    pub fn try_new(source: &'source str) -> Result<Self, Error> {
+        let parser = Self::new(source);
+        if let Some(token) = parser.tokens.iter().find(|token| token.kind == TokenKind::Symbol) {
+            // the `Symbol` token always matches exactly one character
+            let ch = token.lexeme.chars().next().unwrap();
+            return Err(Error::new(vec![token.span.clone()], kind::UnexpectedChar { ch }));
+        }
+        Ok(parser)
+    }
+
+    /// Returns the source code that this parser is parsing.
+    pub fn source(&self) -> &'source str {
+        self.source
+    }
+
+    /// Returns the region of the source code covered by the given span, such as the span of a
+    /// parsed expression or error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds of the source code, or does not lie on character
+    /// boundaries.
+    pub fn source_slice(&self, span: Range<usize>) -> &'source str {
+        &self.source[span]
+    }
+
+    /// Returns the zero-based line and column numbers of the given byte offset into the source
+    /// code. The column is counted in characters.
+    ///
+    /// `\n`, `\r\n`, and a lone `\r` each count as a single line break.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        line_col(self.source, offset)
+    }
+
     /// Returns an immutable reference to the parser's state.
     pub fn state(&self) -> &ParserState {
         &self.state
@@ -63,6 +278,21 @@ impl<'source> Parser<'source> {
         self.cursor = other.cursor;
     }
 
+    /// Saves the current cursor position, so that it can later be restored with
+    /// [`Parser::restore`].
+    ///
+    /// This can be used to implement custom speculative parsing logic without the closure form of
+    /// [`Parser::try_parse_with_fn`].
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { cursor: self.cursor }
+    }
+
+    /// Moves the cursor back to the position saved in the given [`Checkpoint`]. It is assumed that
+    /// the checkpoint was created from a parser pointing to the same source code.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.cursor = checkpoint.cursor;
+    }
+
     /// Creates an error that points at the current token, or the end of the source code if the
     /// cursor is at the end of the stream.
     pub fn error(&self, kind: impl ErrorKind + 'static) -> Error {
@@ -71,7 +301,19 @@ impl<'source> Parser<'source> {
 
     /// Returns a span pointing at the end of the source code.
     pub fn eof_span(&self) -> Range<usize> {
-        self.tokens.last().map_or(0..0, |token| token.span.end..token.span.end)
+        self.eof_token().span.clone()
+    }
+
+    /// Returns the [`TokenKind::Eof`] token at the end of the token stream.
+    fn eof_token(&self) -> &Token<'source> {
+        // the tokenizer always appends an `Eof` token, and the parser never removes it
+        self.tokens.last().unwrap()
+    }
+
+    /// Returns true if the cursor is at the end of the stream, i.e., at the [`TokenKind::Eof`]
+    /// token or past it. Whitespace tokens are not skipped.
+    pub fn is_eof(&self) -> bool {
+        self.peek_token().is_eof()
     }
 
     /// Returns the span of the current token, or the end of the source code if the cursor is at
@@ -96,10 +338,28 @@ impl<'source> Parser<'source> {
         self.tokens.get(self.cursor.checked_sub(1)?)
     }
 
+    /// Returns the remaining tokens, starting from the current token, including whitespace tokens,
+    /// but not the [`TokenKind::Eof`] token. The cursor is not moved.
+    pub fn remaining_tokens(&self) -> &[Token<'source>] {
+        self.tokens[..self.tokens.len() - 1].get(self.cursor..).unwrap_or_default()
+    }
+
+    /// Returns true if the remaining tokens are all whitespace or comments. The cursor is not
+    /// moved.
+    pub(crate) fn only_whitespace_remains(&self) -> bool {
+        self.remaining_tokens().iter().all(Token::is_ignore)
+    }
+
     /// Returns the current token. The cursor is not moved. Returns [`None`] if the cursor is at
     /// the end of the stream.
     pub fn current_token(&self) -> Option<&Token<'source>> {
-        self.tokens.get(self.cursor)
+        self.tokens.get(self.cursor).filter(|token| !token.is_eof())
+    }
+
+    /// Returns the current token, or the [`TokenKind::Eof`] token if the cursor is at the end of
+    /// the stream. The cursor is not moved, and whitespace tokens are not skipped.
+    pub fn peek_token(&self) -> &Token<'source> {
+        self.tokens.get(self.cursor).unwrap_or_else(|| self.eof_token())
     }
 
     /// Advances the cursor past whitespace tokens to the next non-whitespace token. The cursor is
@@ -177,21 +437,35 @@ impl<'source> Parser<'source> {
 
     /// Returns the current token, then advances the cursor. Whitespace tokens are skipped.
     ///
-    /// Returns an EOF error if there are no more tokens.
-    pub fn next_token(&mut self) -> Result<Token<'source>, Error> {
+    /// Returns the [`TokenKind::Eof`] token if there are no more tokens.
+    pub fn next_token(&mut self) -> Token<'source> {
         self.advance_past_whitespace();
         self.next_token_raw()
     }
 
     /// Returns the current token, then advances the cursor. Whitespace tokens **are not** skipped.
     ///
-    /// Returns an EOF error if there are no more tokens.
-    pub fn next_token_raw(&mut self) -> Result<Token<'source>, Error> {
-        let result = self.current_token()
-            .cloned() // cloning is cheap; only Range<_> is cloned
-            .ok_or_else(|| self.error(kind::UnexpectedEof));
+    /// Returns the [`TokenKind::Eof`] token if there are no more tokens. The cursor is still
+    /// advanced in this case, so that [`Parser::prev`] undoes the call.
+    pub fn next_token_raw(&mut self) -> Token<'source> {
+        let token = self.peek_token().clone(); // cloning is cheap; only Range<_> is cloned
         self.cursor += 1;
-        result
+        token
+    }
+
+    /// Returns the current token if it is of the given kind, then advances the cursor. Whitespace
+    /// tokens are skipped.
+    ///
+    /// Returns an [`kind::UnexpectedEof`] error if there are no more tokens, or an
+    /// [`kind::UnexpectedToken`] error if the token is of a different kind.
+    pub fn expect(&mut self, kind: TokenKind) -> Result<Token<'source>, Error> {
+        let token = self.next_token();
+
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(self.unexpected_token(&token, Cow::Owned(vec![kind])))
+        }
     }
 
     /// Speculatively parses a value from the given stream of tokens. This function can be used
@@ -223,9 +497,12 @@ impl<'source> Parser<'source> {
         modify_state(&mut state);
 
         let mut new_parser = Self {
+            source: self.source,
             tokens: self.tokens.clone(),
             cursor: self.cursor,
             state,
+            options: self.options,
+            expected: self.expected.clone(),
         };
 
         let t = new_parser.try_parse();
@@ -278,50 +555,206 @@ impl<'source> Parser<'source> {
 
     /// Attempts to parse a value from the given stream of tokens. All the tokens must be consumed
     /// by the parser; if not, an error is returned.
+    ///
+    /// Warnings do not cause parsing to fail, and are discarded. Use
+    /// [`Parser::try_parse_full_with_warnings`] to retrieve them.
     pub fn try_parse_full<T: Parse<'source>>(&mut self) -> Result<T, Vec<Error>> {
+        self.try_parse_full_with_warnings().map(|(value, _)| value)
+    }
+
+    /// Attempts to parse a value from the given stream of tokens. All the tokens must be consumed
+    /// by the parser; if not, an error is returned.
+    ///
+    /// If parsing succeeds, the value is returned along with any warnings that were encountered.
+    /// Otherwise, all errors and warnings are returned.
+    ///
+    /// If parsing fails and there are no tokens to parse other than whitespace and comments, a
+    /// single [`kind::EmptyInput`] error is returned instead.
+    pub fn try_parse_full_with_warnings<T: Parse<'source>>(&mut self) -> Result<(T, Vec<Error>), Vec<Error>> {
         let mut errors = Vec::new();
-        let value = T::parse(self).forward_errors(&mut errors)?;
+        let empty_span = self.remaining_tokens()
+            .iter()
+            .all(Token::is_ignore)
+            .then(|| self.span().start..self.eof_span().end);
+        let value = T::parse(self)
+            .forward_errors(&mut errors)
+            .map_err(|errs| match empty_span {
+                Some(span) => vec![Error::new(vec![span], kind::EmptyInput)],
+                None => errs,
+            })?;
 
         // consume whitespace
         self.advance_past_whitespace();
 
-        if self.cursor < self.tokens.len() {
+        if !self.is_eof() {
             errors.push(self.error(kind::ExpectedEof));
         }
 
-        if errors.is_empty() {
-            Ok(value)
+        if errors.iter().all(Error::is_warning) {
+            Ok((value, errors))
         } else {
             Err(errors)
         }
     }
 
+    /// Parses as much of the remaining tokens as possible. This is intended for autocompletion of
+    /// incomplete source code.
+    ///
+    /// Returns a tuple containing:
+    ///
+    /// - the value parsed from the start of the remaining tokens, or [`None`] if no value could be
+    ///   parsed without errors,
+    /// - the span of the incomplete tail of the source code following that value, which is empty
+    ///   if all the tokens were parsed,
+    /// - the kinds of tokens that could legally come next.
+    ///
+    /// The expected tokens are those of the [`kind::UnexpectedToken`] errors encountered at the
+    /// furthest position that parsing reached, including errors from speculative parses that were
+    /// backtracked. For a prefix of valid source code, this is the end of the source code.
+    ///
+    /// If a value is parsed, the cursor is advanced past the tokens it was parsed from.
+    pub fn parse_prefix<T: Parse<'source>>(&mut self) -> (Option<T>, Range<usize>, Vec<TokenKind>) {
+        let expected = Arc::new(Mutex::new(Expected::default()));
+        let mut parser = Self {
+            expected: Some(expected.clone()),
+            ..self.clone()
+        };
+
+        let value = match parser.try_parse::<T>() {
+            ParseResult::Ok(value) => Some(value),
+            _ => None,
+        };
+        self.set_cursor(&parser);
+
+        // a value parsed from all of the tokens could also be followed by the end of the input
+        let mut tail = self.clone();
+        tail.advance_past_whitespace();
+        if value.is_some() && tail.is_eof() {
+            parser.record_expected(tail.peek_token(), &[TokenKind::Eof]);
+        }
+        let tail_span = tail.span().start..self.eof_span().end;
+
+        let mut expected = std::mem::take(&mut expected.lock().unwrap().kinds);
+        expected.sort();
+        expected.dedup();
+        (value, tail_span, expected)
+    }
+
+    /// Returns true if this parser is recording the tokens expected where parsing fails. See
+    /// [`Parser::parse_prefix`].
+    pub(crate) fn is_recording_expected(&self) -> bool {
+        self.expected.is_some()
+    }
+
+    /// Records that one of the given kinds of tokens was expected in place of the given token, if
+    /// this parser is recording expected tokens. See [`Parser::parse_prefix`].
+    fn record_expected(&self, token: &Token, kinds: &[TokenKind]) {
+        if let Some(expected) = &self.expected {
+            expected.lock().unwrap().record(token.span.start, kinds);
+        }
+    }
+
+    /// Creates an error for an unexpected token that was read from this parser, where one of the
+    /// given kinds of tokens was expected. See [`Error::unexpected_token`].
+    pub(crate) fn unexpected_token(&self, token: &Token, expected: Cow<'static, [TokenKind]>) -> Error {
+        self.record_expected(token, &expected);
+        Error::unexpected_token(token, expected)
+    }
+
     /// Attempts to parse multiple values from the given stream of tokens. All the tokens must be
     /// consumed by the parser; if not, an error is returned.
+    ///
+    /// Warnings do not cause parsing to fail, and are discarded. If a value fails to parse, the
+    /// errors encountered while parsing it are returned.
     pub fn try_parse_full_many<T: std::fmt::Debug + Parse<'source>>(&mut self) -> Result<Vec<T>, Vec<Error>> {
         let mut errors = Vec::new();
         let mut values = Vec::new();
 
-        while self.cursor < self.tokens.len() {
-            let Ok(value) = T::parse(self).forward_errors(&mut errors) else {
-                break;
-            };
-            values.push(value);
+        while !self.only_whitespace_remains() {
+            match T::parse(self).forward_errors(&mut errors) {
+                Ok(value) => values.push(value),
+                Err(mut errs) => {
+                    errors.append(&mut errs);
+                    return Err(errors);
+                },
+            }
         }
 
         // consume whitespace
         self.advance_past_whitespace();
 
-        if self.cursor < self.tokens.len() {
+        if !self.is_eof() {
             errors.push(self.error(kind::ExpectedEof));
         }
 
-        if errors.is_empty() {
+        if errors.iter().all(Error::is_warning) {
             Ok(values)
         } else {
             Err(errors)
         }
     }
+
+    /// Attempts to parse multiple values from the given stream of tokens, like
+    /// [`Parser::try_parse_full_many`], attaching the comments in the source code to the values.
+    ///
+    /// Comments on their own line(s) before a value are attached to it as leading comments.
+    /// Comments inside a value, or after it on the same line as its end, are attached to it as
+    /// trailing comments. The comments after the last line of the last value are returned
+    /// separately.
+    pub fn try_parse_full_many_with_comments<T: Parse<'source>>(
+        &mut self,
+    ) -> Result<CommentedMany<'source, T>, Vec<Error>> {
+        let mut errors = Vec::new();
+        let mut values: Vec<Commented<T>> = Vec::new();
+
+        loop {
+            // the comments between the previous value and the next one; the ones on the same line
+            // as the end of the previous value trail it
+            let start = self.cursor;
+            self.advance_past_whitespace();
+            let mut same_line = !values.is_empty();
+            let mut leading = Vec::new();
+            for token in &self.tokens[start..self.cursor] {
+                match token.kind {
+                    TokenKind::NewLine => same_line = false,
+                    TokenKind::Comment if same_line => {
+                        values.last_mut().unwrap().trailing.push(token.lexeme.trim_end());
+                    },
+                    TokenKind::Comment => leading.push(token.lexeme.trim_end()),
+                    _ => (),
+                }
+            }
+
+            if self.is_eof() {
+                break if errors.iter().all(Error::is_warning) {
+                    Ok((values, leading))
+                } else {
+                    Err(errors)
+                };
+            }
+
+            let value_start = self.cursor;
+            let value = match T::parse(self).forward_errors(&mut errors) {
+                Ok(value) => value,
+                Err(mut errs) => {
+                    errors.append(&mut errs);
+                    return Err(errors);
+                },
+            };
+
+            // leave the whitespace and comments after the value to be attached on the next
+            // iteration
+            while self.cursor > value_start && self.tokens[self.cursor - 1].is_ignore() {
+                self.cursor -= 1;
+            }
+            let trailing = self.tokens[value_start..self.cursor]
+                .iter()
+                .filter(|token| token.kind == TokenKind::Comment)
+                .map(|token| token.lexeme.trim_end())
+                .collect();
+            values.push(Commented { value, leading, trailing });
+        }
+    }
 }
 
 /// Any type that can be parsed from a source of tokens.
@@ -421,6 +854,12 @@ impl<T> ParseResult<T> {
         matches!(self, ParseResult::Ok(_))
     }
 
+    /// Returns `true` if the result is [`ParseResult::Recoverable`] or
+    /// [`ParseResult::Unrecoverable`].
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
     /// Calls the provided closure with a reference to the contained unrecoverable error.
     ///
     /// This is equivalent to [`Result::inspect_err`].
@@ -488,7 +927,59 @@ mod tests {
     use super::*;
 
     use ast::*;
-    use token::op::{AssignOp, AssignOpKind, BinOp, BinOpKind, UnaryOp, UnaryOpKind};
+    use token::op::{AssignOp, AssignOpKind, BinOp, BinOpKind, Precedence, UnaryOp, UnaryOpKind};
+
+    /// Asserts that the span of every node in the given expression points to a valid region of the
+    /// source code, and that the span of each node encloses the spans of its children.
+    fn assert_span_covers(expr: &Expr, source: &str) {
+        let span = expr.span();
+        assert!(span.start <= span.end, "span {:?} of `{}` is reversed", span, expr);
+        assert!(
+            source.get(span.clone()).is_some(),
+            "span {:?} of `{}` does not index valid source bytes",
+            span,
+            expr,
+        );
+
+        let children: Vec<&Expr> = match expr {
+            Expr::Literal(Literal::List(list)) => list.values.iter().collect(),
+            Expr::Literal(Literal::Tuple(tuple)) => tuple.values.iter().collect(),
+            Expr::Literal(_) | Expr::Measurement(_) | Expr::Continue(_) => vec![],
+            Expr::Paren(paren) => vec![&paren.expr],
+            Expr::Block(block) => block.stmts.iter().map(|stmt| &stmt.expr).collect(),
+            Expr::Piecewise(piecewise) => piecewise.branches.iter()
+                .flat_map(|branch| [&branch.value, &branch.condition])
+                .chain(piecewise.otherwise.as_deref())
+                .collect(),
+            Expr::If(if_expr) => [&if_expr.condition, &if_expr.then_expr]
+                .into_iter()
+                .chain(&if_expr.else_expr)
+                .map(|expr| &**expr)
+                .collect(),
+            Expr::Loop(loop_expr) => vec![&loop_expr.body],
+            Expr::While(while_expr) => vec![&while_expr.condition, &while_expr.body],
+            Expr::Let(let_expr) => vec![&let_expr.value, &let_expr.body],
+            Expr::Break(break_expr) => break_expr.value.iter().map(|expr| &**expr).collect(),
+            Expr::Call(call) => call.args.iter().map(CallArg::value).collect(),
+            Expr::Unary(unary) => vec![&unary.operand],
+            Expr::Binary(binary) => vec![&binary.lhs, &binary.rhs],
+            Expr::Assign(assign) => vec![&assign.value],
+            Expr::Conversion(conversion) => vec![&conversion.value],
+        };
+
+        for child in children {
+            let child_span = child.span();
+            assert!(
+                span.start <= child_span.start && child_span.end <= span.end,
+                "span {:?} of `{}` does not enclose span {:?} of its child `{}`",
+                span,
+                expr,
+                child_span,
+                child,
+            );
+            assert_span_covers(child, source);
+        }
+    }
 
     #[test]
     fn literal_int() {
@@ -595,6 +1086,24 @@ mod tests {
         })));
     }
 
+    #[test]
+    fn literal_inf() {
+        let mut parser = Parser::new("-inf");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Literal(Literal::Float(LitFloat {
+                value: "inf".to_string(),
+                span: 1..4,
+            }))),
+            op: UnaryOp {
+                kind: UnaryOpKind::Neg,
+                span: 0..1,
+            },
+            span: 0..4,
+        }));
+    }
+
     #[test]
     fn literal_symbol() {
         let mut parser = Parser::new("pi");
@@ -606,6 +1115,35 @@ mod tests {
         })));
     }
 
+    #[test]
+    fn literal_bool() {
+        let mut parser = Parser::new("true");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Literal(Literal::Boolean(LitBool {
+            value: true,
+            span: 0..4,
+        })));
+    }
+
+    #[test]
+    fn literal_bool_not_symbol() {
+        let mut parser = Parser::new("not false");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Literal(Literal::Boolean(LitBool {
+                value: false,
+                span: 4..9,
+            }))),
+            op: UnaryOp {
+                kind: UnaryOpKind::Not,
+                span: 0..3,
+            },
+            span: 0..9,
+        }));
+    }
+
     #[test]
     fn unary_left_associativity() {
         let mut parser = Parser::new("3!!");
@@ -631,6 +1169,31 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn unary_plus() {
+        let mut parser = Parser::new("+3");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "3".to_string(),
+                span: 1..2,
+            }))),
+            op: UnaryOp {
+                kind: UnaryOpKind::Pos,
+                span: 0..1,
+            },
+            span: 0..2,
+        }));
+
+        // binary addition takes priority over unary plus
+        let mut parser = Parser::new("1 + +(a + b)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Expr::Binary(binary) = expr else { panic!("expected binary, got {:?}", expr) };
+        assert_eq!(binary.op.kind, BinOpKind::Add);
+        assert!(matches!(*binary.rhs, Expr::Unary(Unary { op: UnaryOp { kind: UnaryOpKind::Pos, .. }, .. })));
+    }
+
     #[test]
     fn unary_right_associativity() {
         let mut parser = Parser::new("not not --3");
@@ -676,14 +1239,101 @@ mod tests {
         assert!(parser.try_parse_full::<Expr>().is_err());
     }
 
+    #[test]
+    fn paren_standalone() {
+        let paren = Parser::new("(3 + 4)").try_parse_full::<Paren>().unwrap();
+        assert_eq!(paren.span, 0..7);
+        assert_eq!(paren.open_span(), 0..1);
+        assert_eq!(paren.close_span(), 6..7);
+        assert!(matches!(*paren.expr, Expr::Binary(Binary { op: BinOp { kind: BinOpKind::Add, .. }, .. })));
+
+        let errs = Parser::new("( )").try_parse_full::<Paren>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![0..3]);
+        assert!(errs[0].downcast_kind::<kind::EmptyParenthesis>().is_some());
+
+        let errs = Parser::new("(3 + 4").try_parse_full::<Paren>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![0..1]);
+        assert!(errs[0].downcast_kind::<kind::UnclosedParenthesis>().is_some());
+
+        // as an expression, `()` is still the unit literal
+        let expr = Parser::new("()").try_parse_full::<Expr>().unwrap();
+        assert!(matches!(expr, Expr::Literal(Literal::Unit(_))));
+    }
+
+    #[test]
+    fn factorial_of_paren_group() {
+        let mut parser = Parser::new("(2+3i)!");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        let Expr::Unary(unary) = expr else { panic!("expected unary, got {:?}", expr) };
+        assert_eq!(unary.op.kind, UnaryOpKind::Factorial);
+        assert_eq!(unary.span, 0..7);
+
+        // the factorial applies to the entire group, not just `3i`
+        let Expr::Paren(paren) = *unary.operand else { panic!("expected paren") };
+        assert_eq!(paren.span, 0..6);
+        assert!(matches!(*paren.expr, Expr::Binary(Binary { op: BinOp { kind: BinOpKind::Add, .. }, .. })));
+    }
+
+    #[test]
+    fn unicode_operators() {
+        for (unicode, ascii) in [("3 × 4", "3 * 4"), ("8 ÷ 2", "8 / 2"), ("5 − −2", "5 - -2")] {
+            let unicode_expr = Parser::new(unicode).try_parse_full::<Expr>().unwrap();
+            let ascii_expr = Parser::new(ascii).try_parse_full::<Expr>().unwrap();
+
+            // the only difference is in the spans, since the unicode signs are wider
+            assert_eq!(unicode_expr.to_string(), ascii_expr.to_string());
+            let (Expr::Binary(unicode_bin), Expr::Binary(ascii_bin)) = (unicode_expr, ascii_expr) else {
+                panic!("expected binary expressions");
+            };
+            assert_eq!(unicode_bin.op.kind, ascii_bin.op.kind);
+            assert_eq!(unicode_bin.op.span, 2..2 + unicode.chars().nth(2).unwrap().len_utf8());
+        }
+
+        // `÷` is not a digit in radix notation, unlike `/`
+        let expr = Parser::new("16'ff÷2").try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.to_string(), "16'ff/2");
+    }
+
+    #[test]
+    fn superscript_exponent() {
+        let expr = Parser::new("2²³").try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "2".to_string(),
+                span: 0..1,
+            }))),
+            op: BinOp {
+                kind: BinOpKind::Exp,
+                implicit: false,
+                span: 1..5,
+            },
+            rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "23".to_string(),
+                span: 1..5,
+            }))),
+            span: 0..5,
+        }));
+
+        // the superscript binds tighter than any other operator
+        let expr = Parser::new("3x² + 1").try_parse_full::<Expr>().unwrap();
+        assert_eq!(expr.to_string(), "3x^2+1");
+
+        // a superscript must directly follow its base
+        assert!(Parser::new("2 ²").try_parse_full::<Expr>().is_err());
+        assert!(Parser::new("²").try_parse_full::<Expr>().is_err());
+    }
+
     #[test]
     fn unary_complicated() {
         let mut parser = Parser::new("not 3!! + -4");
         let expr = parser.try_parse_full::<Expr>().unwrap();
 
-        assert_eq!(expr, Expr::Binary(Binary {
-            lhs: Box::new(Expr::Unary(Unary {
-                operand: Box::new(Expr::Unary(Unary {
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Binary(Binary {
+                lhs: Box::new(Expr::Unary(Unary {
                     operand: Box::new(Expr::Unary(Unary {
                         operand: Box::new(Expr::Literal(Literal::Integer(LitInt {
                             value: "3".to_string(),
@@ -701,32 +1351,304 @@ mod tests {
                     },
                     span: 4..7,
                 })),
+                op: BinOp {
+                    kind: BinOpKind::Add,
+                    implicit: false,
+                    span: 8..9,
+                },
+                rhs: Box::new(Expr::Unary(Unary {
+                    operand: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                        value: "4".to_string(),
+                        span: 11..12,
+                    }))),
+                    op: UnaryOp {
+                        kind: UnaryOpKind::Neg,
+                        span: 10..11,
+                    },
+                    span: 10..12,
+                })),
+                span: 4..12,
+            })),
+            op: UnaryOp {
+                kind: UnaryOpKind::Not,
+                span: 0..3,
+            },
+            span: 0..12,
+        }));
+    }
+
+    #[test]
+    fn bit_not_precedence() {
+        let mut parser = Parser::new("~3 + 1");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Unary(Unary {
+                operand: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                    value: "3".to_string(),
+                    span: 1..2,
+                }))),
+                op: UnaryOp {
+                    kind: UnaryOpKind::BitNot,
+                    span: 0..1,
+                },
+                span: 0..2,
+            })),
+            op: BinOp {
+                kind: BinOpKind::Add,
+                implicit: false,
+                span: 3..4,
+            },
+            rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "1".to_string(),
+                span: 5..6,
+            }))),
+            span: 0..6,
+        }));
+    }
+
+    #[test]
+    fn not_precedence() {
+        let mut parser = Parser::new("not a == b");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Binary(Binary {
+                lhs: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                    name: "a".to_string(),
+                    span: 4..5,
+                }))),
+                op: BinOp {
+                    kind: BinOpKind::Eq,
+                    implicit: false,
+                    span: 6..8,
+                },
+                rhs: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                    name: "b".to_string(),
+                    span: 9..10,
+                }))),
+                span: 4..10,
+            })),
+            op: UnaryOp {
+                kind: UnaryOpKind::Not,
+                span: 0..3,
+            },
+            span: 0..10,
+        }));
+
+        // `not` still binds more tightly than `&&`
+        let mut parser = Parser::new("not a && b");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Unary(Unary {
+                operand: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                    name: "a".to_string(),
+                    span: 4..5,
+                }))),
                 op: UnaryOp {
                     kind: UnaryOpKind::Not,
                     span: 0..3,
                 },
-                span: 0..7,
+                span: 0..5,
             })),
             op: BinOp {
-                kind: BinOpKind::Add,
+                kind: BinOpKind::And,
                 implicit: false,
-                span: 8..9,
+                span: 6..8,
             },
-            rhs: Box::new(Expr::Unary(Unary {
+            rhs: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                name: "b".to_string(),
+                span: 9..10,
+            }))),
+            span: 0..10,
+        }));
+    }
+
+    #[test]
+    fn redundant_paren_warning() {
+        for source in ["(3)", "(3) + 4", "((x + 1))", "-(f(x))"] {
+            let mut parser = Parser::new(source);
+            let (_, warnings) = parser.try_parse_full_with_warnings::<Expr>().unwrap();
+            assert_eq!(warnings.len(), 1, "expected a warning for `{}`", source);
+            assert_eq!(
+                format!("{:?}", warnings[0].kind),
+                format!("{:?}", kind::RedundantParentheses),
+            );
+        }
+    }
+
+    #[test]
+    fn necessary_paren_no_warning() {
+        for source in ["(3 + 4) * 5", "2(3)", "(2)3", "(f)(x)", "not(x)", "(x)y"] {
+            let mut parser = Parser::new(source);
+            let (_, warnings) = parser.try_parse_full_with_warnings::<Expr>().unwrap();
+            assert!(warnings.is_empty(), "unexpected warning for `{}`: {:?}", source, warnings);
+        }
+
+        // unclosed parentheses are reported as an error, not also as redundant
+        let errs = Parser::new("(3").try_parse_full::<Expr>().unwrap_err();
+        assert!(errs.iter().all(|err| !err.is_warning()), "unexpected warning: {:?}", errs);
+    }
+
+    #[test]
+    fn unary_neg_factorial_warning() {
+        let mut parser = Parser::new("-3!");
+        let (expr, warnings) = parser.try_parse_full_with_warnings::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Unary(Unary {
+            operand: Box::new(Expr::Unary(Unary {
                 operand: Box::new(Expr::Literal(Literal::Integer(LitInt {
-                    value: "4".to_string(),
-                    span: 11..12,
+                    value: "3".to_string(),
+                    span: 1..2,
                 }))),
                 op: UnaryOp {
-                    kind: UnaryOpKind::Neg,
-                    span: 10..11,
+                    kind: UnaryOpKind::Factorial,
+                    span: 2..3,
                 },
-                span: 10..12,
+                span: 1..3,
             })),
-            span: 0..12,
+            op: UnaryOp {
+                kind: UnaryOpKind::Neg,
+                span: 0..1,
+            },
+            span: 0..3,
+        }));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].is_warning());
+        assert_eq!(warnings[0].spans, vec![0..3]);
+        assert_eq!(format!("{:?}", warnings[0].kind), format!("{:?}", kind::AmbiguousNegFactorial));
+
+        // warnings do not cause parsing to fail
+        assert!(Parser::new("-3!").try_parse_full::<Expr>().is_ok());
+    }
+
+    #[test]
+    fn binary_mod_word() {
+        let mut parser = Parser::new("7 mod 3");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "7".to_string(),
+                span: 0..1,
+            }))),
+            op: BinOp {
+                kind: BinOpKind::Mod,
+                implicit: false,
+                span: 2..5,
+            },
+            rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "3".to_string(),
+                span: 6..7,
+            }))),
+            span: 0..7,
+        }));
+    }
+
+    #[test]
+    fn implicit_multiplication_switch() {
+        let mut parser = Parser::new("2x");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(matches!(expr, Expr::Binary(Binary { op: BinOp { kind: BinOpKind::Mul, implicit: true, .. }, .. })));
+
+        let mut parser = Parser::with_options("2x", ParseOptions {
+            implicit_multiplication: false,
+            ..Default::default()
+        });
+        let errs = parser.try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![1..2]);
+
+        // explicit multiplication is unaffected
+        let mut parser = Parser::with_options("2 * x", ParseOptions {
+            implicit_multiplication: false,
+            ..Default::default()
+        });
+        assert!(parser.try_parse_full::<Expr>().is_ok());
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = ParseOptions {
+            implicit_multiplication: false,
+            ..Default::default()
+        };
+        let mut parser = Parser::with_options("2x", options);
+        assert!(!parser.is_implicit_multiplication());
+        assert!(parser.try_parse_full::<Expr>().is_err());
+
+        let mut parser = Parser::with_options("2 * x", options);
+        assert!(parser.try_parse_full::<Expr>().is_ok());
+    }
+
+    #[test]
+    fn max_derivatives() {
+        let source = format!("f{}(x)", "'".repeat(300));
+        assert!(Parser::new(&source).try_parse_full::<Expr>().is_err());
+
+        let options = ParseOptions {
+            max_derivatives: 300,
+            ..Default::default()
+        };
+        let expr = Parser::with_options(&source, options).try_parse_full::<Expr>().unwrap();
+        let Expr::Call(call) = expr else { panic!("expected a call, found {:?}", expr) };
+        assert_eq!(call.derivatives, 300);
+
+        let options = ParseOptions {
+            max_derivatives: 2,
+            ..Default::default()
+        };
+        assert!(Parser::with_options("f''(x)", options).try_parse_full::<Expr>().is_ok());
+        let errs = Parser::with_options("f'''(x)", options).try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(
+            format!("{:?}", errs[0].kind),
+            format!("{:?}", kind::TooManyDerivatives { derivatives: 3, max: 2 }),
+        );
+    }
+
+    #[test]
+    fn percent_of() {
+        let mut parser = Parser::new("20% of 50");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "20% of 50");
+
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Binary(Binary {
+                lhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                    value: "20".to_string(),
+                    span: 0..2,
+                }))),
+                op: BinOp {
+                    kind: BinOpKind::Div,
+                    implicit: false,
+                    span: 2..3,
+                },
+                rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                    value: "100".to_string(),
+                    span: 2..3,
+                }))),
+                span: 0..3,
+            })),
+            op: BinOp {
+                kind: BinOpKind::Mul,
+                implicit: false,
+                span: 4..6,
+            },
+            rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "50".to_string(),
+                span: 7..9,
+            }))),
+            span: 0..9,
         }));
     }
 
+    #[test]
+    fn mod_word_is_not_symbol() {
+        let mut parser = Parser::new("mod = 5");
+        assert!(parser.try_parse_full::<Expr>().is_err());
+    }
+
     #[test]
     fn binary_left_associativity() {
         let mut parser = Parser::new("3 * x * 5");
@@ -844,10 +1766,46 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn binary_right_associativity_double_star() {
+        let mut parser = Parser::new("2 ** 3 ** 2");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Binary(Binary {
+            lhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "2".to_string(),
+                span: 0..1,
+            }))),
+            op: BinOp {
+                kind: BinOpKind::Exp,
+                implicit: false,
+                span: 2..4,
+            },
+            rhs: Box::new(Expr::Binary(Binary {
+                lhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                    value: "3".to_string(),
+                    span: 5..6,
+                }))),
+                op: BinOp {
+                    kind: BinOpKind::Exp,
+                    implicit: false,
+                    span: 7..9,
+                },
+                rhs: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                    value: "2".to_string(),
+                    span: 10..11,
+                }))),
+                span: 5..11,
+            })),
+            span: 0..11,
+        }));
+    }
+
     #[test]
     fn binary_complicated() {
         let mut parser = Parser::new("1 + 2 * 3 - 4 / 5 ^ 6");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "1 + 2 * 3 - 4 / 5 ^ 6");
 
         // 2 * 3
         let mul = Expr::Binary(Binary {
@@ -983,6 +1941,7 @@ mod tests {
     fn complicated_binary_and_unary() {
         let mut parser = Parser::new("pi^2 * 17! / -4.9 + e");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "pi^2 * 17! / -4.9 + e");
 
         assert_eq!(expr, Expr::Binary(Binary {
             lhs: Box::new(Expr::Binary(Binary {
@@ -1052,6 +2011,78 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn measurement_unit_mode() {
+        let mut parser = Parser::with_options("5 km", ParseOptions {
+            unit_names: Some(|name| name == "km"),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Measurement(Measurement {
+            value: Box::new(Literal::Integer(LitInt {
+                value: "5".to_string(),
+                span: 0..1,
+            })),
+            unit: LitSym {
+                name: "km".to_string(),
+                span: 2..4,
+            },
+            span: 0..4,
+        }));
+    }
+
+    #[test]
+    fn measurement_unknown_unit() {
+        // names that are not units fall back to implicit multiplication
+        let mut parser = Parser::with_options("5 foo", ParseOptions {
+            unit_names: Some(|name| name == "km"),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(matches!(expr, Expr::Binary(Binary {
+            op: BinOp { kind: BinOpKind::Mul, implicit: true, .. },
+            ..
+        })));
+
+        // unit mode is disabled by default
+        let mut parser = Parser::new("5 km");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(matches!(expr, Expr::Binary(_)));
+    }
+
+    #[test]
+    fn unit_conversion() {
+        let mut parser = Parser::with_options("(2 mi) to dm", ParseOptions {
+            unit_names: Some(|name| matches!(name, "mi" | "dm")),
+            ..Default::default()
+        });
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "(2 mi) to dm");
+
+        let Expr::Conversion(conversion) = expr else {
+            panic!("expected a conversion, found {:?}", expr);
+        };
+        assert!(matches!(conversion.value.innermost(), Expr::Measurement(_)));
+        assert_eq!(conversion.target.name, "dm");
+        assert_eq!(conversion.to_span, 7..9);
+    }
+
+    #[test]
+    fn unit_conversion_expected_unit() {
+        let mut parser = Parser::with_options("2 mi to foo", ParseOptions {
+            unit_names: Some(|name| name == "mi"),
+            ..Default::default()
+        });
+        let errs = parser.try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![8..11]);
+
+        // `to` is an ordinary symbol outside of unit mode
+        let mut parser = Parser::new("2 mi to dm");
+        assert!(parser.try_parse_full::<Expr>().is_ok());
+    }
+
     #[test]
     fn implicit_multiplication() {
         let mut parser = Parser::new("2(3 + 4)");
@@ -1511,6 +2542,7 @@ mod tests {
     fn parenthesized_complicated() {
         let mut parser = Parser::new("(3 * 9 + 4.0 / 11.9 % (6 - 3))");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "(3 * 9 + 4.0 / 11.9 % (6 - 3))");
 
         assert_eq!(expr, Expr::Paren(Paren {
             expr: Box::new(Expr::Binary(Binary {
@@ -1588,6 +2620,7 @@ mod tests {
     fn block_code() {
         let mut parser = Parser::new("{ x = 5; y = 6; x + y }");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "{ x = 5; y = 6; x + y }");
 
         assert_eq!(expr, Expr::Block(Block {
             stmts: vec![
@@ -1658,6 +2691,7 @@ mod tests {
     fn if_block() {
         let mut parser = Parser::new("if d then { abs''(d) } else { f = 1; f }");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "if d then { abs''(d) } else { f = 1; f }");
 
         assert_eq!(expr, Expr::If(If {
             condition: Box::new(Expr::Literal(Literal::Symbol(LitSym {
@@ -1674,10 +2708,10 @@ mod tests {
                             },
                             derivatives: 2,
                             args: vec![
-                                Expr::Literal(Literal::Symbol(LitSym {
+                                CallArg::Positional(Expr::Literal(Literal::Symbol(LitSym {
                                     name: "d".to_string(),
                                     span: 18..19,
-                                })),
+                                }))),
                             ],
                             span: 12..20,
                             paren_span: 17..20,
@@ -1727,6 +2761,106 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn piecewise() {
+        let source = "{ x^2 if x > 0; -x^2 otherwise }";
+        let mut parser = Parser::new(source);
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, source);
+
+        let Expr::Piecewise(piecewise) = &expr else {
+            panic!("expected a piecewise expression, found {:?}", expr);
+        };
+        assert_eq!(piecewise.branches.len(), 1);
+        assert_eq!(piecewise.branches[0].value.to_string(), "x^2");
+        assert_eq!(piecewise.branches[0].condition.to_string(), "x>0");
+        assert_eq!(piecewise.branches[0].if_span, 6..8);
+        assert_eq!(piecewise.otherwise.as_ref().unwrap().to_string(), "-x^2");
+        assert_eq!(piecewise.otherwise_span, Some(21..30));
+        assert_eq!(piecewise.span, 0..32);
+
+        // no `otherwise` branch
+        let mut parser = Parser::new("{1 if a; 2 if b}");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        let Expr::Piecewise(piecewise) = expr else {
+            panic!("expected a piecewise expression");
+        };
+        assert_eq!(piecewise.branches.len(), 2);
+        assert_eq!(piecewise.otherwise, None);
+
+        // blocks are unaffected
+        let mut parser = Parser::new("{x = 1; x}");
+        assert!(matches!(parser.try_parse_full::<Expr>().unwrap(), Expr::Block(_)));
+
+        let mut parser = Parser::new("{if a then 1 else 2}");
+        assert!(matches!(parser.try_parse_full::<Expr>().unwrap(), Expr::Block(_)));
+
+        // an `if` expression as the value of a branch
+        let mut parser = Parser::new("{if a then 1 else 2 if b; 3 otherwise}");
+        assert!(matches!(parser.try_parse_full::<Expr>().unwrap(), Expr::Piecewise(_)));
+
+        let errs = Parser::new("{1 if a; 2 otherwise").try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs[0].spans, vec![0..1]);
+        assert!(errs[0].downcast_kind::<kind::UnclosedCurlyBrace>().is_some());
+    }
+
+    #[test]
+    fn nested_blocks() {
+        // deep nesting should not take exponential time
+        let source = format!("{}1{}", "{".repeat(40), "}".repeat(40));
+        let mut parser = Parser::new(&source);
+        assert!(matches!(parser.try_parse_full::<Expr>().unwrap(), Expr::Block(_)));
+    }
+
+    #[test]
+    fn unclosed_block() {
+        // reported the same way as an unclosed piecewise expression
+        let errs = Parser::new("{x = 1; x").try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs[0].spans, vec![0..1]);
+        assert!(errs[0].downcast_kind::<kind::UnclosedCurlyBrace>().is_some());
+    }
+
+    #[test]
+    fn tuple_literal() {
+        let source = "(1, (2 + 3), [4, 5])";
+        let mut parser = Parser::new(source);
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, source);
+        let Expr::Literal(Literal::Tuple(tuple)) = &expr else {
+            panic!("expected a tuple, found {:?}", expr);
+        };
+        assert_eq!(tuple.values.len(), 3);
+        assert_eq!(tuple.span, 0..20);
+
+        // a trailing comma makes a single-element tuple, not a parenthesized expression
+        let mut parser = Parser::new("(1,)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert!(matches!(&expr, Expr::Literal(Literal::Tuple(tuple)) if tuple.values.len() == 1));
+        assert_eq!(expr.to_string(), "(1,)");
+
+        let mut parser = Parser::new("(1)");
+        assert!(matches!(parser.try_parse_full::<Expr>().unwrap(), Expr::Paren(_)));
+    }
+
+    #[test]
+    fn destructure_assign() {
+        for source in ["a, b = divmod(7, 2)", "(a, b) = divmod(7, 2)"] {
+            let mut parser = Parser::new(source);
+            let expr = parser.try_parse_full::<Expr>().unwrap();
+            let Expr::Assign(assign) = expr else {
+                panic!("expected an assignment");
+            };
+            let AssignTarget::Destructure(symbols) = assign.target else {
+                panic!("expected a destructuring assignment in `{}`", source);
+            };
+            let names = symbols.iter().map(|symbol| symbol.name.as_str()).collect::<Vec<_>>();
+            assert_eq!(names, ["a", "b"]);
+        }
+
+        let mut parser = Parser::new("a, b += 1");
+        assert!(parser.try_parse_full::<Expr>().is_err());
+    }
+
     #[test]
     fn assign_to_var() {
         let mut parser = Parser::new("fx += 1 / pi");
@@ -1761,6 +2895,59 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn assign_standalone() {
+        let mut parser = Parser::new("x = 3");
+        let assign = parser.try_parse_full::<Assign>().unwrap();
+
+        assert_eq!(assign, Assign {
+            target: AssignTarget::Symbol(LitSym {
+                name: "x".to_string(),
+                span: 0..1,
+            }),
+            op: AssignOp {
+                kind: AssignOpKind::Assign,
+                span: 2..3,
+            },
+            value: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "3".to_string(),
+                span: 4..5,
+            }))),
+            span: 0..5,
+        });
+
+        let mut parser = Parser::new("f(x) = x");
+        let assign = parser.try_parse_full::<Assign>().unwrap();
+
+        assert_eq!(assign, Assign {
+            target: AssignTarget::Func(FuncHeader {
+                name: LitSym {
+                    name: "f".to_string(),
+                    span: 0..1,
+                },
+                params: vec![
+                    Param::Symbol(LitSym {
+                        name: "x".to_string(),
+                        span: 2..3,
+                    }),
+                ],
+                span: 0..4,
+            }),
+            op: AssignOp {
+                kind: AssignOpKind::Assign,
+                span: 5..6,
+            },
+            value: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                name: "x".to_string(),
+                span: 7..8,
+            }))),
+            span: 0..8,
+        });
+
+        // an expression that is not an assignment
+        assert!(Parser::new("x + 3").try_parse_full::<Assign>().is_err());
+    }
+
     #[test]
     fn assign_to_function() {
         let mut parser = Parser::new("f(x) = x^2 + 5x");
@@ -1832,6 +3019,7 @@ mod tests {
     fn assign_to_complicated_function() {
         let mut parser = Parser::new("discrim(a = 1, b = 5, c) = b^2 - 4a * c");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "discrim(a = 1, b = 5, c) = b^2 - 4a * c");
 
         assert_eq!(expr, Expr::Assign(Assign {
             target: AssignTarget::Func(FuncHeader {
@@ -1941,10 +3129,10 @@ mod tests {
             },
             derivatives: 0,
             args: vec![
-                Expr::Literal(Literal::Symbol(LitSym {
+                CallArg::Positional(Expr::Literal(Literal::Symbol(LitSym {
                     name: "x".to_string(),
                     span: 2..3,
-                })),
+                }))),
             ],
             span: 0..4,
             paren_span: 1..4,
@@ -1963,20 +3151,59 @@ mod tests {
             },
             derivatives: 6,
             args: vec![
-                Expr::Literal(Literal::Integer(LitInt {
+                CallArg::Positional(Expr::Literal(Literal::Integer(LitInt {
                     value: "8".to_string(),
                     span: 18..19,
-                })),
-                Expr::Literal(Literal::Integer(LitInt {
+                }))),
+                CallArg::Positional(Expr::Literal(Literal::Integer(LitInt {
                     value: "5".to_string(),
                     span: 22..23,
-                })),
+                }))),
             ],
             span: 0..25,
             paren_span: 16..25,
         }));
     }
 
+    #[test]
+    fn function_call_named_args() {
+        let mut parser = Parser::new("f(1, y: 2)");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+
+        assert_eq!(expr, Expr::Call(Call {
+            name: LitSym {
+                name: "f".to_string(),
+                span: 0..1,
+            },
+            derivatives: 0,
+            args: vec![
+                CallArg::Positional(Expr::Literal(Literal::Integer(LitInt {
+                    value: "1".to_string(),
+                    span: 2..3,
+                }))),
+                CallArg::Named {
+                    name: LitSym {
+                        name: "y".to_string(),
+                        span: 5..6,
+                    },
+                    value: Expr::Literal(Literal::Integer(LitInt {
+                        value: "2".to_string(),
+                        span: 8..9,
+                    })),
+                    span: 5..9,
+                },
+            ],
+            span: 0..10,
+            paren_span: 1..10,
+        }));
+    }
+
+    #[test]
+    fn function_call_positional_after_named() {
+        let mut parser = Parser::new("f(x: 1, 2)");
+        assert!(parser.try_parse_full::<Expr>().is_err());
+    }
+
     #[test]
     fn blank_function_header() {
         let mut parser = Parser::new("f() = 5");
@@ -2010,10 +3237,63 @@ mod tests {
         assert!(parser.try_parse_full::<Expr>().is_err());
     }
 
+    #[test]
+    fn let_expr() {
+        let source = "let x = 3 in x * x";
+        let mut parser = Parser::new(source);
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, source);
+
+        assert_eq!(expr, Expr::Let(Let {
+            name: LitSym {
+                name: "x".to_string(),
+                span: 4..5,
+            },
+            value: Box::new(Expr::Literal(Literal::Integer(LitInt {
+                value: "3".to_string(),
+                span: 8..9,
+            }))),
+            body: Box::new(Expr::Binary(Binary {
+                lhs: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                    name: "x".to_string(),
+                    span: 13..14,
+                }))),
+                op: BinOp {
+                    kind: BinOpKind::Mul,
+                    implicit: false,
+                    span: 15..16,
+                },
+                rhs: Box::new(Expr::Literal(Literal::Symbol(LitSym {
+                    name: "x".to_string(),
+                    span: 17..18,
+                }))),
+                span: 13..18,
+            })),
+            span: 0..18,
+            let_span: 0..3,
+            in_span: 10..12,
+        }));
+
+        // `in` is only a keyword in the value of a `let` expression
+        let mut parser = Parser::with_options("let d = 2y in 5 in", ParseOptions {
+            unit_names: Some(|name| name == "in"),
+            ..Default::default()
+        });
+        let Expr::Let(let_expr) = parser.try_parse_full::<Expr>().unwrap() else {
+            panic!("expected a let expression");
+        };
+        assert_eq!(let_expr.value.to_string(), "2y");
+        assert!(matches!(*let_expr.body, Expr::Measurement(_)));
+
+        let mut parser = Parser::new("let x = 3 x");
+        assert!(parser.try_parse_full::<Expr>().is_err());
+    }
+
     #[test]
     fn oneline_while_loop() {
         let mut parser = Parser::new("while x < 5 then x += 1");
         let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_span_covers(&expr, "while x < 5 then x += 1");
 
         assert_eq!(expr, Expr::While(While {
             condition: Box::new(Expr::Binary(Binary {
@@ -2066,4 +3346,410 @@ if iseven(x) then {
 }");
         assert!(parser.try_parse_full_many::<Stmt>().is_ok());
     }
+
+    #[test]
+    fn checkpoint_restore() {
+        let mut parser = Parser::new("1 + 2");
+        let checkpoint = parser.checkpoint();
+
+        let first = parser.next_token();
+        assert_eq!(first.kind, TokenKind::Int);
+        assert_eq!(parser.next_token().kind, TokenKind::Add);
+
+        parser.restore(checkpoint);
+        assert_eq!(parser.next_token(), first);
+    }
+
+    #[test]
+    fn expect_token() {
+        let mut parser = Parser::new("+ 3");
+        let token = parser.expect(TokenKind::Add).unwrap();
+        assert_eq!(token.lexeme, "+");
+        assert_eq!(token.span, 0..1);
+
+        let mut parser = Parser::new("3");
+        let err = parser.expect(TokenKind::Add).unwrap_err();
+        assert_eq!(err.spans, vec![0..1]);
+        assert_eq!(
+            format!("{:?}", err.kind),
+            format!("{:?}", kind::UnexpectedToken {
+                expected: Cow::Borrowed(&[TokenKind::Add]),
+                found: TokenKind::Int,
+                found_lexeme: "3".to_string(),
+            }),
+        );
+
+        let mut parser = Parser::new("");
+        let err = parser.expect(TokenKind::Add).unwrap_err();
+        assert_eq!(format!("{:?}", err.kind), format!("{:?}", kind::UnexpectedEof));
+    }
+
+    #[test]
+    fn expected_tokens() {
+        let errs = Parser::new("3").try_parse_full::<token::CloseParen>().unwrap_err();
+        assert_eq!(errs[0].expected_tokens(), Some(&[TokenKind::CloseParen][..]));
+        assert_eq!(
+            errs[0].downcast_kind::<kind::UnexpectedToken>().map(|kind| kind.found),
+            Some(TokenKind::Int),
+        );
+
+        // other kinds of errors have no expected set
+        let err = Parser::new("").expect(TokenKind::Add).unwrap_err();
+        assert_eq!(err.expected_tokens(), None);
+    }
+
+    #[test]
+    fn render_plain() {
+        let source = "x = 1\n  foo";
+        let mut parser = Parser::new(source);
+        parser.cursor = 7;
+        let err = parser.expect(TokenKind::Add).unwrap_err();
+        assert_eq!(
+            err.render_plain(source),
+            "2:3: unexpected token\n  foo\n  ^^^",
+        );
+
+        // the line number agrees with `line_col` for every kind of line break, and the line
+        // break is not printed
+        for source in ["x = 1\r\n  foo", "x = 1\r  foo"] {
+            let mut parser = Parser::new(source);
+            parser.cursor = 7;
+            let err = parser.expect(TokenKind::Add).unwrap_err();
+            assert_eq!(parser.line_col(err.spans[0].start), (1, 2));
+            assert_eq!(
+                err.render_plain(source),
+                "2:3: unexpected token\n  foo\n  ^^^",
+            );
+        }
+    }
+
+    #[test]
+    fn sorted_expected_tokens() {
+        let kind = kind::UnexpectedToken {
+            expected: Cow::Owned(vec![TokenKind::CloseParen, TokenKind::Add, TokenKind::CloseParen]),
+            found: TokenKind::Int,
+            found_lexeme: "3".to_string(),
+        };
+        assert_eq!(kind.sorted_expected(), vec![TokenKind::Add, TokenKind::CloseParen]);
+
+        // the same failing input always reports the same expected tokens, in the same order
+        let render = || {
+            let errs = Parser::new("f(3 4").try_parse_full::<Expr>().unwrap_err();
+            let mut out = Vec::new();
+            for err in errs {
+                err.build_report().write(("input", ariadne::Source::from("f(3 4")), &mut out).unwrap();
+            }
+            out
+        };
+        let first = render();
+        for _ in 0..10 {
+            assert_eq!(render(), first);
+        }
+    }
+
+    #[test]
+    fn parse_prefix() {
+        let mut parser = Parser::new("sin(");
+        let (value, tail, expected) = parser.parse_prefix::<Expr>();
+        assert_eq!(value, Some(Expr::Literal(Literal::Symbol(LitSym {
+            name: "sin".to_string(),
+            span: 0..3,
+        }))));
+        assert_eq!(tail, 3..4);
+
+        // an argument or the closing parenthesis can come next
+        for kind in [TokenKind::CloseParen, TokenKind::Int, TokenKind::Name, TokenKind::Sub, TokenKind::OpenParen] {
+            assert!(expected.contains(&kind), "expected `{:?}` in {:?}", kind, expected);
+        }
+        for kind in [TokenKind::Comma, TokenKind::Mul, TokenKind::CloseSquare] {
+            assert!(!expected.contains(&kind), "unexpected `{:?}` in {:?}", kind, expected);
+        }
+
+        let mut parser = Parser::new("sin(x)");
+        let (value, tail, expected) = parser.parse_prefix::<Expr>();
+        assert!(matches!(value, Some(Expr::Call(_))));
+        assert_eq!(tail, 6..6);
+        assert!(expected.contains(&TokenKind::Add));
+        assert!(!expected.contains(&TokenKind::CloseParen));
+    }
+
+    #[test]
+    fn source_slice() {
+        let mut parser = Parser::new("x + 0.25 * y");
+        let expr = parser.try_parse_full::<Expr>().unwrap();
+        assert_eq!(parser.source_slice(expr.span()), "x + 0.25 * y");
+
+        let Expr::Binary(Binary { rhs, .. }) = expr else { panic!("expected binary") };
+        let Expr::Binary(Binary { lhs, .. }) = *rhs else { panic!("expected binary") };
+        let Expr::Literal(Literal::Float(float)) = *lhs else { panic!("expected float") };
+        assert_eq!(parser.source_slice(float.span), "0.25");
+    }
+
+    #[test]
+    fn crlf_line_col() {
+        let source = "x = 1 // one\r\ny = 2\r\n\r\nx + y";
+        let parser = Parser::new(source);
+        assert_eq!(parser.line_col(0), (0, 0));
+        assert_eq!(parser.line_col(source.find('y').unwrap()), (1, 0));
+        assert_eq!(parser.line_col(source.find('+').unwrap()), (3, 2));
+
+        // the `\r` of a CRLF is not a line break, or a column, on its own
+        assert_eq!(parser.line_col(source.find('\r').unwrap() + 1), (0, 12));
+        assert_eq!(Parser::new("a\rb").line_col(2), (1, 0));
+
+        let stmts = Parser::new(source).try_parse_full_many::<Stmt>().unwrap();
+        assert_eq!(stmts.len(), 3);
+    }
+
+    #[test]
+    fn unexpected_char() {
+        let err = Parser::try_new("2 @ 3").unwrap_err();
+        assert_eq!(err.spans, vec![2..3]);
+        assert_eq!(format!("{:?}", err.kind), format!("{:?}", kind::UnexpectedChar { ch: '@' }));
+
+        // multi-byte characters report the span of the entire character
+        let err = Parser::try_new("1 + €").unwrap_err();
+        assert_eq!(err.spans, vec![4..7]);
+        assert_eq!(format!("{:?}", err.kind), format!("{:?}", kind::UnexpectedChar { ch: '€' }));
+
+        assert!(Parser::try_new("1 + 2").is_ok());
+    }
+
+    #[test]
+    fn keyword_assignment_target() {
+        let source = "not = 3";
+        let errs = Parser::new(source).try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![0..3]);
+
+        let mut report = Vec::new();
+        errs[0].build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("try a different name, such as `not_`"));
+        assert!(report.contains("`not` is a reserved keyword and cannot be assigned to"));
+    }
+
+    #[test]
+    fn unexpected_token_lexeme() {
+        let source = "foo";
+        let err = Parser::new(source).expect(TokenKind::OpenParen).unwrap_err();
+
+        let mut report = Vec::new();
+        err.build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("found \"foo\" (Name)"), "{}", report);
+    }
+
+    #[test]
+    fn structurally_eq() {
+        let a = Parser::new("1 + 2").try_parse_full::<Expr>().unwrap();
+        let b = Parser::new("  1 +  2").try_parse_full::<Expr>().unwrap();
+        assert_ne!(a, b);
+        assert!(a.structurally_eq(&b));
+
+        let c = Parser::new("1 + 3").try_parse_full::<Expr>().unwrap();
+        assert!(!a.structurally_eq(&c));
+
+        let d = Parser::new("(1 + 2)").try_parse_full::<Expr>().unwrap();
+        assert!(!a.structurally_eq(&d));
+        assert!(a.structurally_eq(d.innermost()));
+    }
+
+    #[test]
+    fn structural_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let hasher = RandomState::new();
+        let a = Parser::new("f(x, 2) * sin(x)").try_parse_full::<Expr>().unwrap();
+        let b = Parser::new("  f(x,   2)  *  sin(x)").try_parse_full::<Expr>().unwrap();
+        assert!(a.structurally_eq(&b));
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+
+        let c = Parser::new("f(x, 3) * sin(x)").try_parse_full::<Expr>().unwrap();
+        assert_ne!(hasher.hash_one(&a), hasher.hash_one(&c));
+    }
+
+    #[test]
+    fn empty_input() {
+        for (source, span) in [("", 0..0), ("   ", 0..3)] {
+            let errs = Parser::new(source).try_parse_full::<Expr>().unwrap_err();
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].spans, vec![span]);
+            assert_eq!(format!("{:?}", errs[0].kind), format!("{:?}", kind::EmptyInput));
+        }
+
+        // an empty program is still valid
+        assert!(Parser::new("  ").try_parse_full::<Program>().is_ok());
+    }
+
+    #[test]
+    fn missing_operand() {
+        let errs = Parser::new("3 +").try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![2..3, 3..3]);
+        assert_eq!(
+            format!("{:?}", errs[0].kind),
+            format!("{:?}", kind::MissingOperand { op: "+".to_string() }),
+        );
+
+        let errs = Parser::new("x = ").try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs[0].spans[0], 2..3);
+        assert!(errs[0].downcast_kind::<kind::MissingOperand>().is_some());
+
+        // the error is reported for a whole program, too
+        for source in ["3 +", "x = ", "y = 2\n3 +"] {
+            let errs = Parser::new(source).try_parse_full::<Program>().unwrap_err();
+            assert!(errs[0].downcast_kind::<kind::MissingOperand>().is_some(), "source: {}", source);
+
+            let errs = Parser::new(source).try_parse_full_many::<Stmt>().unwrap_err();
+            assert!(errs[0].downcast_kind::<kind::MissingOperand>().is_some(), "source: {}", source);
+        }
+    }
+
+    #[test]
+    fn next_token_eof() {
+        let source = "  ";
+        let mut parser = Parser::new(source);
+        assert!(parser.current_token().is_some());
+        assert!(!parser.is_eof());
+
+        let token = parser.next_token();
+        assert_eq!(token, Token { span: 2..2, kind: TokenKind::Eof, lexeme: "" });
+        assert!(parser.is_eof());
+        assert!(parser.current_token().is_none());
+
+        // peeking past the end keeps returning the `Eof` token
+        assert_eq!(parser.peek_token(), &token);
+        assert_eq!(parser.next_token(), token);
+        assert_eq!(parser.peek_token(), &token);
+
+        let err = Parser::new(source).expect(TokenKind::Add).unwrap_err();
+        assert!(err.downcast_kind::<kind::UnexpectedEof>().is_some());
+        assert_eq!(err.spans, vec![2..2]);
+
+        let mut report = Vec::new();
+        err.build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("unexpected end of file"));
+    }
+
+    #[test]
+    fn multi_span_error() {
+        // errors can point at several regions of the source at once, each with its own label
+        let source = "if x 3";
+        let err = Error::new(vec![0..2, 6..6], kind::MissingIfKeyword { keyword: "then" });
+        assert_eq!(err.spans, vec![0..2, 6..6]);
+
+        let mut report = Vec::new();
+        err.build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("this `if` expression"));
+        assert!(report.contains("I expected to see `then` here"));
+
+        // the parser produces the same error
+        let errs = Parser::new(source).try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs[0].spans, err.spans);
+    }
+
+    #[test]
+    fn radix_max_base() {
+        let options = ParseOptions {
+            max_radix: 36,
+            ..Default::default()
+        };
+        assert!(Parser::with_options("36'z", options).try_parse_full::<Expr>().is_ok());
+
+        let errs = Parser::with_options("40'abc", options).try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].spans, vec![0..2]);
+        assert_eq!(
+            format!("{:?}", errs[0].kind),
+            format!("{:?}", kind::InvalidRadixBase { too_large: true, min: 2, max: 36 }),
+        );
+
+        // the default range is unchanged
+        assert!(Parser::new("40'abc").try_parse_full::<Expr>().is_ok());
+    }
+
+    #[test]
+    fn radix_digit_table() {
+        let source = "64'a_b";
+        let errs = Parser::new(source).try_parse_full::<Expr>().unwrap_err();
+        assert_eq!(errs.len(), 1);
+
+        let mut report = Vec::new();
+        errs[0].build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("0–9 → 0–9, 10–35 → a–z, 36–61 → A–Z, 62 → +, 63 → /"));
+
+        // small bases have no table
+        let source = "8'19";
+        let errs = Parser::new(source).try_parse_full::<Expr>().unwrap_err();
+        let mut report = Vec::new();
+        errs[0].build_report().write(("input", ariadne::Source::from(source)), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(!report.contains("→"));
+    }
+
+    #[test]
+    fn precedence_total_order() {
+        use Precedence::*;
+        let precedences = [
+            Any, Assign, Or, And, Not, Compare, BitOr, BitAnd, Shift, Term, Factor, Neg, BitNot, Exp,
+            Factorial,
+        ];
+
+        assert!(Exp > Term);
+        for a in precedences {
+            for b in precedences {
+                assert_eq!(a.cmp(&b), (a as u8).cmp(&(b as u8)));
+                assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_with_precedence() {
+        let mut parser = Parser::new("1 + 2");
+        let expr = Expr::parse_with_precedence(&mut parser, Precedence::Factor)
+            .forward_errors(&mut Vec::new())
+            .unwrap();
+
+        assert_eq!(expr, Expr::Literal(Literal::Integer(LitInt {
+            value: "1".to_string(),
+            span: 0..1,
+        })));
+        assert_eq!(parser.next_token().kind, TokenKind::Add);
+        assert_eq!(parser.next_token().kind, TokenKind::Int);
+        assert_eq!(parser.next_token().kind, TokenKind::Eof);
+    }
+
+    #[test]
+    fn default_param_references_later_param() {
+        let mut parser = Parser::new("f(x = y, y = 1) = x + y");
+        assert!(parser.try_parse_full::<Expr>().is_err());
+
+        let mut parser = Parser::new("f(x, y = x) = x + y");
+        assert!(parser.try_parse_full::<Expr>().is_ok());
+    }
+
+    #[test]
+    fn program() {
+        let mut parser = Parser::new("f(x) = x^2; f(3)
+g = 2");
+        let program = parser.try_parse_full::<Program>().unwrap();
+
+        assert_eq!(program.stmts.len(), 3);
+        assert_eq!(program.stmts[0].semicolon, Some(10..11));
+        assert_eq!(program.stmts[1].semicolon, None);
+        assert_eq!(program.span, 0..22);
+
+        let mut parser = Parser::new("a\nb");
+        let program = parser.try_parse_full::<Program>().unwrap();
+        assert_eq!(program.to_string(), "a\nb");
+
+        let source = program.to_string();
+        let mut parser = Parser::new(&source);
+        assert_eq!(parser.try_parse_full::<Program>().unwrap().stmts.len(), 2);
+    }
 }
@@ -2,10 +2,10 @@ pub mod op;
 pub mod pair;
 
 use crate::{
-    parser::{error::{kind, Error}, garbage::Garbage, Parser, Parse},
+    parser::{error::Error, garbage::Garbage, Parser, Parse},
     tokenizer::TokenKind,
 };
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 
 /// Generates a unit struct for each token kind, as well as a simple [`Parse`] implementation for
 /// each token kind. This enables the parser to use and request token kinds as a type, which is
@@ -24,7 +24,7 @@ macro_rules! token_kinds {
                     input: &mut Parser<'source>,
                     _: &mut Vec<Error>
                 ) -> Result<Self, Vec<Error>> {
-                    let token = input.next_token().map_err(|e| vec![e])?;
+                    let token = input.next_token();
 
                     if token.kind == TokenKind::$name {
                         Ok(Self {
@@ -32,10 +32,7 @@ macro_rules! token_kinds {
                             span: token.span,
                         })
                     } else {
-                        Err(vec![Error::new(vec![token.span], kind::UnexpectedToken {
-                            expected: &[TokenKind::$name],
-                            found: token.kind,
-                        })])
+                        Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[TokenKind::$name]))])
                     }
                 }
             }
@@ -60,6 +57,7 @@ token_kinds!(
     Sub
     Mul
     Div
+    IntDiv
     Mod
     Exp
     Greater
@@ -94,6 +92,7 @@ token_kinds!(
     Name
     Keyword
     Comma
+    Colon
     OpenParen
     CloseParen
     OpenCurly
@@ -1,10 +1,10 @@
 //! Structs to help parse binary and unary operators.
 
 use crate::{
-    parser::{error::{Error, kind}, fmt::Latex, Parse, Parser},
+    parser::{error::Error, fmt::Latex, Parse, Parser},
     tokenizer::TokenKind,
 };
-use std::{fmt, ops::Range};
+use std::{borrow::Cow, fmt, ops::Range};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -44,6 +44,12 @@ pub enum Precedence {
     /// Precedence of logical and (`and`).
     And,
 
+    /// Precedence of logical not (`not`).
+    ///
+    /// This is lower than the precedence of comparisons, so `not a == b` is parsed as
+    /// `not (a == b)`.
+    Not,
+
     /// Precedence of comparisons (`>`, `>=`, `<`, `<=`, `==`, `!=`, `~==`, and `~!=`).
     Compare,
 
@@ -63,20 +69,17 @@ pub enum Precedence {
     /// factors.
     Factor,
 
-    /// Precedence of unary subtraction (`-`).
+    /// Precedence of unary subtraction (`-`) and unary addition (`+`).
     Neg,
 
+    /// Precedence of bitwise not (`~`).
+    BitNot,
+
     /// Precedence of exponentiation (`^`).
     Exp,
 
     /// Precedence of factorial (`!`).
     Factorial,
-
-    /// Precedence of bitwise not (`~`).
-    BitNot,
-
-    /// Precedence of logical not (`not`).
-    Not,
 }
 
 impl PartialOrd for Precedence {
@@ -94,13 +97,14 @@ impl Ord for Precedence {
 }
 
 /// The unary operation that is being performed.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnaryOpKind {
     Not,
     BitNot,
     Factorial,
     Neg,
+    Pos,
 }
 
 impl UnaryOpKind {
@@ -110,21 +114,21 @@ impl UnaryOpKind {
             Self::Not => Precedence::Not,
             Self::BitNot => Precedence::BitNot,
             Self::Factorial => Precedence::Factorial,
-            Self::Neg => Precedence::Neg,
+            Self::Neg | Self::Pos => Precedence::Neg,
         }
     }
 
     /// Returns the associativity of the unary operation.
     pub fn associativity(&self) -> Associativity {
         match self {
-            Self::Neg | Self::BitNot | Self::Not => Associativity::Right,
+            Self::Neg | Self::Pos | Self::BitNot | Self::Not => Associativity::Right,
             Self::Factorial => Associativity::Left,
         }
     }
 }
 
 /// A unary operator that takes one operand.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnaryOp {
     /// The kind of unary operator.
@@ -151,24 +155,20 @@ impl<'source> Parse<'source> for UnaryOp {
         input: &mut Parser<'source>,
         _: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
-        let token = input.next_token().map_err(|e| vec![e])?;
+        let token = input.next_token();
         let kind = match token.kind {
             TokenKind::Not => Ok(UnaryOpKind::Not),
             TokenKind::BitNot => Ok(UnaryOpKind::BitNot),
             TokenKind::Factorial => Ok(UnaryOpKind::Factorial),
             TokenKind::Sub => Ok(UnaryOpKind::Neg),
-            _ => Err(vec![Error::new(
-                vec![token.span.clone()],
-                kind::UnexpectedToken {
-                    expected: &[
-                        TokenKind::Not,
-                        TokenKind::BitNot,
-                        TokenKind::Factorial,
-                        TokenKind::Sub,
-                    ],
-                    found: token.kind,
-                },
-            )]),
+            TokenKind::Add => Ok(UnaryOpKind::Pos),
+            _ => Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[
+                TokenKind::Not,
+                TokenKind::BitNot,
+                TokenKind::Factorial,
+                TokenKind::Sub,
+                TokenKind::Add,
+            ]))]),
         }?;
 
         Ok(Self {
@@ -185,6 +185,7 @@ impl std::fmt::Display for UnaryOp {
             UnaryOpKind::BitNot => write!(f, "~"),
             UnaryOpKind::Factorial => write!(f, "!"),
             UnaryOpKind::Neg => write!(f, "-"),
+            UnaryOpKind::Pos => write!(f, "+"),
         }
     }
 }
@@ -196,17 +197,19 @@ impl Latex for UnaryOp {
             UnaryOpKind::BitNot => write!(f, "\\sim "),
             UnaryOpKind::Factorial => write!(f, "!"),
             UnaryOpKind::Neg => write!(f, "-"),
+            UnaryOpKind::Pos => write!(f, "+"),
         }
     }
 }
 
 /// The binary operation that is being performed.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BinOpKind {
     Exp,
     Mul,
     Div,
+    IntDiv,
     Mod,
     Add,
     Sub,
@@ -231,7 +234,7 @@ impl BinOpKind {
     pub fn precedence(&self) -> Precedence {
         match self {
             Self::Exp => Precedence::Exp,
-            Self::Mul | Self::Div | Self::Mod => Precedence::Factor,
+            Self::Mul | Self::Div | Self::IntDiv | Self::Mod => Precedence::Factor,
             Self::Add | Self::Sub => Precedence::Term,
             Self::BitRight | Self::BitLeft => Precedence::Shift,
             Self::BitAnd => Precedence::BitAnd,
@@ -247,7 +250,7 @@ impl BinOpKind {
     pub fn associativity(&self) -> Associativity {
         match self {
             Self::Exp => Associativity::Right,
-            Self::Mul | Self::Div | Self::Mod
+            Self::Mul | Self::Div | Self::IntDiv | Self::Mod
                 | Self::Add | Self::Sub
                 | Self::BitRight | Self::BitLeft
                 | Self::BitAnd | Self::BitOr
@@ -259,7 +262,7 @@ impl BinOpKind {
 }
 
 /// A binary operator that takes two operands.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BinOp {
     /// The kind of binary operator.
@@ -289,11 +292,12 @@ impl<'source> Parse<'source> for BinOp {
         input: &mut Parser<'source>,
         _: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
-        let token = input.next_token().map_err(|e| vec![e])?;
+        let token = input.next_token();
         let kind = match token.kind {
             TokenKind::Exp => Ok(BinOpKind::Exp),
             TokenKind::Mul => Ok(BinOpKind::Mul),
             TokenKind::Div => Ok(BinOpKind::Div),
+            TokenKind::IntDiv => Ok(BinOpKind::IntDiv),
             TokenKind::Mod => Ok(BinOpKind::Mod),
             TokenKind::Add => Ok(BinOpKind::Add),
             TokenKind::Sub => Ok(BinOpKind::Sub),
@@ -311,19 +315,29 @@ impl<'source> Parse<'source> for BinOp {
             TokenKind::ApproxNotEq => Ok(BinOpKind::ApproxNotEq),
             TokenKind::And => Ok(BinOpKind::And),
             TokenKind::Or => Ok(BinOpKind::Or),
-            _ => Err(vec![Error::new(
-                vec![token.span.clone()],
-                kind::UnexpectedToken {
-                    expected: &[
-                        TokenKind::Exp,
-                        TokenKind::Mul,
-                        TokenKind::Div,
-                        TokenKind::Add,
-                        TokenKind::Sub,
-                    ],
-                    found: token.kind,
-                },
-            )]),
+            _ => Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[
+                TokenKind::Exp,
+                TokenKind::Mul,
+                TokenKind::Div,
+                TokenKind::IntDiv,
+                TokenKind::Mod,
+                TokenKind::Add,
+                TokenKind::Sub,
+                TokenKind::BitRight,
+                TokenKind::BitLeft,
+                TokenKind::BitAnd,
+                TokenKind::BitOr,
+                TokenKind::Greater,
+                TokenKind::GreaterEq,
+                TokenKind::Less,
+                TokenKind::LessEq,
+                TokenKind::Eq,
+                TokenKind::NotEq,
+                TokenKind::ApproxEq,
+                TokenKind::ApproxNotEq,
+                TokenKind::And,
+                TokenKind::Or,
+            ]))]),
         }?;
 
         Ok(Self {
@@ -344,6 +358,7 @@ impl std::fmt::Display for BinOp {
             BinOpKind::Exp => write!(f, "^"),
             BinOpKind::Mul => write!(f, "*"),
             BinOpKind::Div => write!(f, "/"),
+            BinOpKind::IntDiv => write!(f, " div "),
             BinOpKind::Mod => write!(f, "%"),
             BinOpKind::Add => write!(f, "+"),
             BinOpKind::Sub => write!(f, "-"),
@@ -375,6 +390,7 @@ impl Latex for BinOp {
             BinOpKind::Exp => write!(f, "^"),
             BinOpKind::Mul => write!(f, "\\cdot "),
             BinOpKind::Div => write!(f, "\\div "),
+            BinOpKind::IntDiv => write!(f, "\\operatorname{{div}} "),
             BinOpKind::Mod => write!(f, "\\mod "),
             BinOpKind::Add => write!(f, "+"),
             BinOpKind::Sub => write!(f, "-"),
@@ -397,7 +413,7 @@ impl Latex for BinOp {
 }
 
 /// The kind of assignment operator.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AssignOpKind {
     Assign,
@@ -437,7 +453,7 @@ impl From<AssignOpKind> for BinOpKind {
 
 /// An assignment operator that takes two operands, assigning the value of the right operand to the
 /// left operand, possibly with an intermediate operation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AssignOp {
     /// The kind of assignment operator.
@@ -464,7 +480,7 @@ impl<'source> Parse<'source> for AssignOp {
         input: &mut Parser<'source>,
         _: &mut Vec<Error>
     ) -> Result<Self, Vec<Error>> {
-        let token = input.next_token().map_err(|e| vec![e])?;
+        let token = input.next_token();
         let kind = match token.kind {
             TokenKind::Assign => Ok(AssignOpKind::Assign),
             TokenKind::AddAssign => Ok(AssignOpKind::Add),
@@ -479,27 +495,21 @@ impl<'source> Parse<'source> for AssignOp {
             TokenKind::BitOrAssign => Ok(AssignOpKind::BitOr),
             TokenKind::BitRightAssign => Ok(AssignOpKind::BitRight),
             TokenKind::BitLeftAssign => Ok(AssignOpKind::BitLeft),
-            _ => Err(vec![Error::new(
-                vec![token.span.clone()],
-                kind::UnexpectedToken {
-                    expected: &[
-                        TokenKind::Assign,
-                        TokenKind::AddAssign,
-                        TokenKind::SubAssign,
-                        TokenKind::MulAssign,
-                        TokenKind::DivAssign,
-                        TokenKind::ModAssign,
-                        TokenKind::ExpAssign,
-                        TokenKind::AndAssign,
-                        TokenKind::OrAssign,
-                        TokenKind::BitAndAssign,
-                        TokenKind::BitOrAssign,
-                        TokenKind::BitRightAssign,
-                        TokenKind::BitLeftAssign,
-                    ],
-                    found: token.kind,
-                },
-            )]),
+            _ => Err(vec![input.unexpected_token(&token, Cow::Borrowed(&[
+                TokenKind::Assign,
+                TokenKind::AddAssign,
+                TokenKind::SubAssign,
+                TokenKind::MulAssign,
+                TokenKind::DivAssign,
+                TokenKind::ModAssign,
+                TokenKind::ExpAssign,
+                TokenKind::AndAssign,
+                TokenKind::OrAssign,
+                TokenKind::BitAndAssign,
+                TokenKind::BitOrAssign,
+                TokenKind::BitRightAssign,
+                TokenKind::BitLeftAssign,
+            ]))]),
         }?;
 
         Ok(Self {
@@ -1,28 +1,112 @@
 pub mod token;
 
 use logos::{Lexer, Logos};
-pub use token::{Token, TokenKind};
+use std::ops::Range;
+pub use token::{Keyword, Token, TokenKind};
 
 /// Returns an iterator over the token kinds produced by the tokenizer.
 pub fn tokenize(input: &str) -> Lexer<TokenKind> {
     TokenKind::lexer(input)
 }
 
+/// Returns an iterator over the tokens in the given input, starting from the byte offset `start`,
+/// which must be at the start of a token. The spans of the tokens are relative to the start of the
+/// entire input.
+fn tokenize_from(input: &str, start: usize) -> impl Iterator<Item = Token<'_>> {
+    let mut lexer = tokenize(&input[start..]);
+    std::iter::from_fn(move || {
+        let kind = lexer.next()?.ok()?;
+        if kind == TokenKind::Symbol {
+            // the catch-all `Symbol` pattern matches a single byte; extend it to cover the entire
+            // character, so that multi-byte characters are not split into invalid slices
+            let span = lexer.span();
+            let ch_len = lexer.source()[span.start..].chars().next().map_or(0, char::len_utf8);
+            lexer.bump(ch_len.saturating_sub(span.len()));
+        }
+
+        let span = lexer.span();
+        Some(Token {
+            span: span.start + start..span.end + start,
+            kind,
+            lexeme: lexer.slice(),
+        })
+    })
+}
+
+/// Returns the [`TokenKind::Eof`] token marking the end of the given input.
+fn eof(input: &str) -> Token<'_> {
+    Token {
+        span: input.len()..input.len(),
+        kind: TokenKind::Eof,
+        lexeme: "",
+    }
+}
+
 /// Returns an owned array containing all of the tokens produced by the tokenizer. This allows us
 /// to backtrack in case of an error.
+///
+/// The last token is always a [`TokenKind::Eof`] token, with an empty span at the end of the
+/// input.
 pub fn tokenize_complete(input: &str) -> Box<[Token]> {
-    let mut lexer = tokenize(input);
-    let mut tokens = Vec::new();
+    tokenize_from(input, 0).chain(std::iter::once(eof(input))).collect()
+}
 
-    while let Some(Ok(kind)) = lexer.next() {
-        tokens.push(Token {
-            span: lexer.span(),
-            kind,
-            lexeme: lexer.slice(),
-        });
+/// Updates the tokens of a source after an edit, re-tokenizing only the region affected by the
+/// edit. The result is the same as calling [`tokenize_complete`] on the new source.
+///
+/// `tokens` are the tokens of the source before the edit, `edit` is the range of the old source
+/// that was replaced, and `input` is the new source with the replacement text at `edit.start`.
+///
+/// Tokenization restarts after the last newline before the edit, since no token spans across a
+/// newline. It stops as soon as a new token ends where one of the old tokens after the edit
+/// starts; the text from that point onward is unchanged, so the rest of the old tokens are reused
+/// with their spans shifted.
+pub fn retokenize<'source>(
+    tokens: &[Token],
+    edit: Range<usize>,
+    input: &'source str,
+) -> Box<[Token<'source>]> {
+    let old_len = tokens.last().map_or(0, |token| token.span.end);
+
+    // the end of the replacement text in the new source
+    let edit_end = input.len() + edit.end - old_len;
+    let reuse = |token: &Token, span: Range<usize>| Token {
+        lexeme: &input[span.clone()],
+        span,
+        kind: token.kind,
+    };
+
+    let restart = tokens.iter()
+        .rposition(|token| token.kind == TokenKind::NewLine && token.span.end < edit.start)
+        .map_or(0, |i| i + 1);
+    let restart_at = tokens.get(restart).map_or(0, |token| token.span.start);
+
+    let mut new_tokens = tokens[..restart].iter()
+        .map(|token| reuse(token, token.span.clone()))
+        .collect::<Vec<_>>();
+
+    for token in tokenize_from(input, restart_at) {
+        let end = token.span.end;
+        new_tokens.push(token);
+
+        if end >= edit_end {
+            let old_end = end - edit_end + edit.end;
+            if let Ok(i) = tokens.binary_search_by_key(&old_end, |token| token.span.start) {
+                new_tokens.extend(tokens[i..].iter().map(|token| {
+                    let shift = |pos: usize| pos - edit.end + edit_end;
+                    reuse(token, shift(token.span.start)..shift(token.span.end))
+                }));
+                break;
+            }
+        }
     }
 
-    tokens.into_boxed_slice()
+    // the old `Eof` token is only reused if tokenization stopped early
+    if new_tokens.last().is_none_or(|token| !token.is_eof()) {
+        new_tokens.push(eof(input));
+    }
+
+    new_tokens.into_boxed_slice()
 }
 
 #[cfg(test)]
@@ -41,6 +125,191 @@ mod tests {
         assert_eq!(lexer.next(), None);
     }
 
+    #[test]
+    fn classify_tokens() {
+        assert!(TokenKind::Add.is_operator());
+        assert!(TokenKind::Not.is_operator());
+        assert!(TokenKind::AddAssign.is_operator());
+        assert!(!TokenKind::Comma.is_operator());
+
+        assert!(TokenKind::Int.is_literal_start());
+        assert!(TokenKind::Name.is_literal_start());
+        assert!(TokenKind::OpenSquare.is_literal_start());
+        assert!(!TokenKind::Add.is_literal_start());
+
+        assert!(TokenKind::Keyword.is_keyword());
+        assert!(TokenKind::Boolean.is_keyword());
+        assert!(!TokenKind::Name.is_keyword());
+
+        let tokens = tokenize_complete("mod % x");
+        assert!(tokens[0].is_keyword());
+        assert!(!tokens[2].is_keyword());
+        assert!(!tokens[4].is_keyword());
+    }
+
+    /// Applies an edit to the source, and checks that re-tokenizing the edited source produces the
+    /// same tokens as tokenizing it from scratch.
+    fn compare_retokenize(old: &str, edit: Range<usize>, replacement: &str) {
+        let tokens = tokenize_complete(old);
+        let new = format!("{}{}{}", &old[..edit.start], replacement, &old[edit.end..]);
+        assert_eq!(retokenize(&tokens, edit, &new), tokenize_complete(&new), "for `{}`", new);
+    }
+
+    #[test]
+    fn retokenize_edit() {
+        let source = "f(x) = x^2 + 3x - 1\n\
+            g(x) = {\n\
+                // some comment\n\
+                y = f(x) * 0b101;\n\
+                y == 5 // trailing\n\
+            }\n\
+            g(3) + g(4)\n";
+        let middle = source.find("y =").unwrap();
+
+        // insertion, deletion, and replacement in the middle of the source
+        compare_retokenize(source, middle..middle, "z = 2;\n");
+        compare_retokenize(source, middle..middle + 4, "");
+        compare_retokenize(source, middle..middle + 1, "total");
+
+        // edits that merge or split neighboring tokens
+        let eq = source.find("==").unwrap();
+        compare_retokenize(source, eq + 1..eq + 2, "");
+        compare_retokenize(source, eq..eq, "~");
+        compare_retokenize(source, eq + 1..eq + 1, " ");
+        let bin = source.find("0b").unwrap();
+        compare_retokenize(source, bin + 1..bin + 2, "");
+
+        // edits to comments and newlines
+        let comment = source.find("// some").unwrap();
+        compare_retokenize(source, comment..comment + 1, "");
+        compare_retokenize(source, comment..comment, "\n\n");
+        let newline = source.find('\n').unwrap();
+        compare_retokenize(source, newline..newline + 1, "");
+
+        // edits at the start and end of the source
+        compare_retokenize(source, 0..1, "h");
+        compare_retokenize(source, source.len()..source.len(), "+ 1");
+        compare_retokenize(source, 0..source.len(), "");
+        compare_retokenize("", 0..0, "1 + 2");
+    }
+
+    #[test]
+    fn eof_token() {
+        let tokens = tokenize_complete("1 + 2");
+        assert_eq!(tokens.len(), 6);
+        assert_eq!(tokens[5], Token { span: 5..5, kind: TokenKind::Eof, lexeme: "" });
+
+        let tokens = tokenize_complete("");
+        assert_eq!(&*tokens, [Token { span: 0..0, kind: TokenKind::Eof, lexeme: "" }]);
+    }
+
+    #[test]
+    fn token_keyword() {
+        let tokens = tokenize_complete("not x mod true");
+        assert_eq!(tokens[0].keyword(), Some(Keyword::Not));
+        assert_eq!(tokens[2].keyword(), None);
+        assert_eq!(tokens[4].keyword(), Some(Keyword::Mod));
+        assert_eq!(tokens[6].keyword(), Some(Keyword::True));
+
+        let tokens = tokenize_complete("while % info");
+        assert_eq!(tokens[0].keyword(), Some(Keyword::While));
+        assert_eq!(tokens[0].keyword().map(Keyword::as_str), Some("while"));
+        assert_eq!(tokens[2].keyword(), None);
+        assert_eq!(tokens[4].keyword(), None);
+    }
+
+    #[test]
+    fn crlf_comment() {
+        compare_tokens(
+            "1 // one\r\n2",
+            [
+                (TokenKind::Int, "1"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Comment, "// one"),
+                (TokenKind::NewLine, "\r\n"),
+                (TokenKind::Int, "2"),
+            ],
+        );
+
+        let tokens = tokenize_complete("1 // one\r\n2");
+        assert_eq!(tokens[2].span, 2..8);
+        assert_eq!(tokens[3].span, 8..10);
+    }
+
+    #[test]
+    fn int_div_is_not_comment() {
+        // `//` always starts a comment, so integer division is written `div`
+        compare_tokens(
+            "7 div 2 // 2",
+            [
+                (TokenKind::Int, "7"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::IntDiv, "div"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Int, "2"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Comment, "// 2"),
+            ],
+        );
+    }
+
+    #[test]
+    fn unicode_operators() {
+        let tokens = tokenize_complete("3×4÷2−1");
+        let kinds = tokens.iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, [
+            TokenKind::Int, TokenKind::Mul, TokenKind::Int, TokenKind::Div,
+            TokenKind::Int, TokenKind::Sub, TokenKind::Int, TokenKind::Eof,
+        ]);
+        assert_eq!(tokens[1].span, 1..3);
+        assert_eq!(tokens[3].span, 4..6);
+        assert_eq!(tokens[5].span, 7..10);
+    }
+
+    #[test]
+    fn subscript_name() {
+        compare_tokens("x_1", [(TokenKind::Name, "x_1")]);
+        compare_tokens("x2", [(TokenKind::Name, "x"), (TokenKind::Int, "2")]);
+    }
+
+    #[test]
+    fn unicode_name() {
+        compare_tokens(
+            "α + θ_0",
+            [
+                (TokenKind::Name, "α"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Add, "+"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Name, "θ_0"),
+            ],
+        );
+    }
+
+    #[test]
+    fn keyword_not_shadowed() {
+        compare_tokens("if", [(TokenKind::Keyword, "if")]);
+        compare_tokens("not", [(TokenKind::Not, "not")]);
+        compare_tokens("true", [(TokenKind::Boolean, "true")]);
+        compare_tokens("iffy", [(TokenKind::Name, "iffy")]);
+    }
+
+    #[test]
+    fn special_float() {
+        compare_tokens("inf", [(TokenKind::Float, "inf")]);
+        compare_tokens("nan", [(TokenKind::Float, "nan")]);
+        compare_tokens("info", [(TokenKind::Name, "info")]);
+    }
+
+    #[test]
+    fn multi_byte_symbol() {
+        let tokens = tokenize_complete("1 + €");
+        let last = &tokens[tokens.len() - 2];
+        assert_eq!(last.kind, TokenKind::Symbol);
+        assert_eq!(last.span, 4..7);
+        assert_eq!(last.lexeme, "€");
+    }
+
     #[test]
     fn basic_expr() {
         compare_tokens(
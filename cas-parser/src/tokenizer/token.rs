@@ -2,7 +2,7 @@ use logos::Logos;
 use std::ops::Range;
 
 /// The different kinds of tokens that can be produced by the tokenizer.
-#[derive(Logos, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Logos, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TokenKind {
     #[regex(r"[\n\r]+")]
     NewLine,
@@ -10,7 +10,8 @@ pub enum TokenKind {
     #[regex(r"[ \t]+")]
     Whitespace,
 
-    #[regex(r"//.*")]
+    // the comment ends before a `\r` as well, so that a CRLF line ending is not part of it
+    #[regex(r"//[^\r\n]*")]
     Comment,
 
     #[token("==")]
@@ -29,18 +30,27 @@ pub enum TokenKind {
     Add,
 
     #[token("-")]
+    #[token("−")] // U+2212 MINUS SIGN
     Sub,
 
     #[token("*")]
+    #[token("×")]
     Mul,
 
     #[token("/")]
+    #[token("÷")]
     Div,
 
+    // floored integer division is spelled `div`, since `//` already starts a comment
+    #[token("div")]
+    IntDiv,
+
     #[token("%")]
+    #[token("mod")]
     Mod,
 
     #[token("^")]
+    #[token("**")]
     Exp,
 
     #[token(">")]
@@ -101,6 +111,7 @@ pub enum TokenKind {
     ModAssign,
 
     #[token("^=")]
+    #[token("**=")]
     ExpAssign,
 
     #[token("&&=")]
@@ -130,15 +141,20 @@ pub enum TokenKind {
     #[token("0x")]
     Hex,
 
-    #[regex(r"([a-zA-Z_]+|atan2)")] // TODO: includes horrible hard-coded test for atan2
+    // digits are only allowed after an underscore (e.g. `x_1`), so that `x2` is still tokenized as
+    // the name `x` followed by the integer `2`
+    #[regex(r"([\p{XID_Start}_](\p{XID_Start}|_\p{XID_Continue}*)*|atan2)")] // TODO: includes horrible hard-coded test for atan2
     Name,
 
-    #[regex(r"(if|then|else|loop|while|break|continue)")]
+    #[regex(r"(if|then|else|otherwise|loop|while|break|continue|let)")]
     Keyword,
 
     #[token(",")]
     Comma,
 
+    #[token(":")]
+    Colon,
+
     #[token("(")]
     OpenParen,
 
@@ -167,16 +183,27 @@ pub enum TokenKind {
     Int,
 
     #[regex(r"([0-9]+\.[0-9]*|\.[0-9]+)")]
+    #[token("inf")]
+    #[token("nan")]
     Float,
 
     #[regex(r"(true|false)")]
     Boolean,
 
+    #[regex(r"[⁰¹²³⁴⁵⁶⁷⁸⁹]+")]
+    Superscript,
+
     #[token(".")]
     Dot,
 
     #[regex(r".", priority = 0)]
     Symbol,
+
+    /// The end of the source code. This is never produced by the lexer; [`tokenize_complete`]
+    /// appends exactly one such token, with an empty span at the end of the source code.
+    ///
+    /// [`tokenize_complete`]: crate::tokenizer::tokenize_complete
+    Eof,
 }
 
 impl TokenKind {
@@ -189,6 +216,134 @@ impl TokenKind {
     pub fn is_significant_whitespace(self) -> bool {
         matches!(self, TokenKind::NewLine)
     }
+
+    /// Returns true if the token is a unary, binary, or assignment operator.
+    pub fn is_operator(self) -> bool {
+        matches!(
+            self,
+            TokenKind::Eq | TokenKind::NotEq | TokenKind::ApproxEq | TokenKind::ApproxNotEq
+                | TokenKind::Add | TokenKind::Sub | TokenKind::Mul | TokenKind::Div
+                | TokenKind::IntDiv | TokenKind::Mod | TokenKind::Exp
+                | TokenKind::Greater | TokenKind::GreaterEq | TokenKind::Less | TokenKind::LessEq
+                | TokenKind::Not | TokenKind::Factorial | TokenKind::And | TokenKind::Or
+                | TokenKind::BitAnd | TokenKind::BitOr | TokenKind::BitNot
+                | TokenKind::BitRight | TokenKind::BitLeft
+                | TokenKind::Assign | TokenKind::AddAssign | TokenKind::SubAssign
+                | TokenKind::MulAssign | TokenKind::DivAssign | TokenKind::ModAssign
+                | TokenKind::ExpAssign | TokenKind::AndAssign | TokenKind::OrAssign
+                | TokenKind::BitAndAssign | TokenKind::BitOrAssign
+                | TokenKind::BitRightAssign | TokenKind::BitLeftAssign
+        )
+    }
+
+    /// Returns true if the token can be the first token of a literal, such as `3`, `x`, `0b101`,
+    /// or `[1, 2]`. This includes `(`, which starts the unit literal `()`.
+    pub fn is_literal_start(self) -> bool {
+        matches!(
+            self,
+            TokenKind::Int | TokenKind::Float | TokenKind::Boolean | TokenKind::Name
+                | TokenKind::Bin | TokenKind::Oct | TokenKind::Hex
+                | TokenKind::OpenSquare | TokenKind::OpenParen
+        )
+    }
+
+    /// Returns true if the token is a reserved word, such as `if`, `not`, or `true`, which cannot
+    /// be used as a symbol name.
+    ///
+    /// The `mod` keyword produces the same kind of token as `%`, so this returns false for
+    /// [`TokenKind::Mod`]; use [`Token::is_keyword`] to check for it.
+    pub fn is_keyword(self) -> bool {
+        matches!(self, TokenKind::Keyword | TokenKind::Not | TokenKind::IntDiv | TokenKind::Boolean)
+    }
+}
+
+/// A reserved word in the language, which cannot be used as a symbol name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keyword {
+    /// `if`
+    If,
+
+    /// `then`
+    Then,
+
+    /// `else`
+    Else,
+
+    /// `otherwise`
+    Otherwise,
+
+    /// `loop`
+    Loop,
+
+    /// `while`
+    While,
+
+    /// `break`
+    Break,
+
+    /// `continue`
+    Continue,
+
+    /// `let`
+    Let,
+
+    /// `not`
+    Not,
+
+    /// `div`
+    Div,
+
+    /// `mod`
+    Mod,
+
+    /// `true`
+    True,
+
+    /// `false`
+    False,
+}
+
+impl Keyword {
+    /// Returns the keyword with the given lexeme, or [`None`] if the lexeme is not a keyword.
+    pub fn from_lexeme(lexeme: &str) -> Option<Self> {
+        Some(match lexeme {
+            "if" => Self::If,
+            "then" => Self::Then,
+            "else" => Self::Else,
+            "otherwise" => Self::Otherwise,
+            "loop" => Self::Loop,
+            "while" => Self::While,
+            "break" => Self::Break,
+            "continue" => Self::Continue,
+            "let" => Self::Let,
+            "not" => Self::Not,
+            "div" => Self::Div,
+            "mod" => Self::Mod,
+            "true" => Self::True,
+            "false" => Self::False,
+            _ => return None,
+        })
+    }
+
+    /// Returns the lexeme of the keyword.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Then => "then",
+            Self::Else => "else",
+            Self::Otherwise => "otherwise",
+            Self::Loop => "loop",
+            Self::While => "while",
+            Self::Break => "break",
+            Self::Continue => "continue",
+            Self::Let => "let",
+            Self::Not => "not",
+            Self::Div => "div",
+            Self::Mod => "mod",
+            Self::True => "true",
+            Self::False => "false",
+        }
+    }
 }
 
 /// A token produced by the tokenizer.
@@ -214,4 +369,24 @@ impl Token<'_> {
     pub fn is_significant_whitespace(&self) -> bool {
         self.kind.is_significant_whitespace()
     }
+
+    /// Returns true if the token marks the end of the source code.
+    pub fn is_eof(&self) -> bool {
+        self.kind == TokenKind::Eof
+    }
+
+    /// Returns true if the token is a reserved word, including `mod`. See
+    /// [`TokenKind::is_keyword`].
+    pub fn is_keyword(&self) -> bool {
+        self.keyword().is_some()
+    }
+
+    /// Returns the keyword this token represents, or [`None`] if the token is not a keyword.
+    pub fn keyword(&self) -> Option<Keyword> {
+        if self.kind.is_keyword() || self.kind == TokenKind::Mod {
+            Keyword::from_lexeme(self.lexeme)
+        } else {
+            None
+        }
+    }
 }
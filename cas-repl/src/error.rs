@@ -1,5 +1,5 @@
 use ariadne::Source;
-use cas_compute::numerical::error::Error as EvalError;
+use cas_compute::numerical::{error::Error as EvalError, session::SessionError};
 use cas_parser::parser::error::Error as ParseError;
 
 /// Utility enum to package errors that can occur while parsing / evaluating.
@@ -36,6 +36,15 @@ impl From<Vec<ParseError>> for Error {
     }
 }
 
+impl From<SessionError> for Error {
+    fn from(err: SessionError) -> Self {
+        match err {
+            SessionError::ParseError(errs) => Self::ParseError(errs),
+            SessionError::EvalError(err) => Self::EvalError(err),
+        }
+    }
+}
+
 impl From<EvalError> for Error {
     fn from(err: EvalError) -> Self {
         Self::EvalError(err)
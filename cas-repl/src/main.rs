@@ -1,21 +1,13 @@
 mod error;
 
-use cas_compute::numerical::{ctxt::Ctxt, eval::eval_stmts, fmt::{FormatOptionsBuilder, NumberFormat, Scientific, Separator}, value::Value};
-use cas_parser::parser::Parser;
+use cas_compute::numerical::{fmt::{FormatOptionsBuilder, NumberFormat, Scientific, Separator}, session::Session, value::Value};
 use error::Error;
 use rustyline::{error::ReadlineError, DefaultEditor};
 use std::{fs::File, io::{self, BufReader, IsTerminal, Read}};
 
-/// Parses and evaluates the given input string, returning the results of both operations.
-fn parse_eval(input: &str, ctxt: &mut Ctxt) -> Result<Value, Error> {
-    let ast = Parser::new(input).try_parse_full_many()?;
-    let res = eval_stmts(&ast, ctxt)?;
-    Ok(res)
-}
-
 /// Reads from the provided file or stdin and parses / evaluates the input, printing the success or
 /// failure.
-fn read_eval(input: &str, ctxt: &mut Ctxt) {
+fn read_eval(input: &str, session: &mut Session) {
     // let fmt = FormatOptions {
     let fmt = FormatOptionsBuilder::new()
         .number(NumberFormat::Auto)
@@ -24,10 +16,10 @@ fn read_eval(input: &str, ctxt: &mut Ctxt) {
         .separators(Separator::Never)
         .build();
 
-    match parse_eval(input, ctxt) {
+    match session.eval_line(input) {
         Ok(Value::Unit) => (), // intentionally print nothing
         Ok(res) => println!("{}", res.fmt(fmt)),
-        Err(err) => err.report_to_stderr(input),
+        Err(err) => Error::from(err).report_to_stderr(input),
     }
 }
 
@@ -35,7 +27,7 @@ fn main() {
     let mut args = std::env::args();
     args.next();
 
-    let mut ctxt = Ctxt::default();
+    let mut session = Session::new();
 
     if let Some(filename) = args.next() {
         // run source file
@@ -43,26 +35,26 @@ fn main() {
         let mut input = String::new();
         file.read_to_string(&mut input).unwrap();
 
-        read_eval(&input, &mut ctxt);
+        read_eval(&input, &mut session);
     } else if !io::stdin().is_terminal() {
         // read source from stdin
         let mut input = String::new();
         io::stdin().read_to_string(&mut input).unwrap();
 
-        read_eval(&input, &mut ctxt);
+        read_eval(&input, &mut session);
     } else {
         // run the repl / interactive mode
         let mut rl = DefaultEditor::new().unwrap();
 
-        fn process_line(rl: &mut DefaultEditor, ctxt: &mut Ctxt) -> Result<(), ReadlineError> {
+        fn process_line(rl: &mut DefaultEditor, session: &mut Session) -> Result<(), ReadlineError> {
             let input = rl.readline("> ")?;
             rl.add_history_entry(&input)?;
-            read_eval(&input, ctxt);
+            read_eval(&input, session);
             Ok(())
         }
 
         loop {
-            if let Err(err) = process_line(&mut rl, &mut ctxt) {
+            if let Err(err) = process_line(&mut rl, &mut session) {
                 match err {
                     ReadlineError::Eof | ReadlineError::Interrupted => (),
                     _ => eprintln!("{}", err),